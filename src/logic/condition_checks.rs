@@ -1,16 +1,24 @@
 pub mod condition_checks {
     use std::collections::{HashMap, HashSet, VecDeque};
 
-    use crate::structs::immutable::{
-        game_node::game_node::{GameNode, GameNodeId},
-        solution::solution::{solution_to_adjacency_matrix, Solution},
+    use crate::{
+        logic::{
+            diagnostic::diagnostic::{CheckResult, Diagnostic},
+            util::get_opposite_node_pairs,
+        },
+        structs::immutable::{
+            game_node::game_node::{GameNode, GameNodeId},
+            puzzle::puzzle::Puzzle,
+            solution::solution::Solution,
+        },
     };
 
-    /// Checks if all nodes connected to the start node are of equal distance.
+    /// Checks if all nodes connected to the start node are of equal distance, reporting the
+    /// depths at which the branches end.
     ///
-    /// NOTE: Currently does NOT consider Scope.
-    pub fn is_branch_equal(node: &GameNode, solution: &Solution) -> bool {
-        let adj_matrix = solution_to_adjacency_matrix(solution);
+    /// NOTE: Scope is applied by passing the solution filtered with `get_bounded_solutions`.
+    pub fn is_branch_equal(node: &GameNode, solution: &Solution) -> CheckResult {
+        let adj_matrix = solution.adjacency();
 
         // Check if the node has no neighbors
         if adj_matrix
@@ -18,33 +26,30 @@ pub mod condition_checks {
             .unwrap_or(&HashSet::new())
             .is_empty()
         {
-            return false;
+            return CheckResult::new(false, Diagnostic::NoLines);
         }
 
-        // Perform DFS and keep track of the deepest, returning false if depth is ever
-        // different at the lowest node in a branch (no neighbors besides its parent).
+        // Perform DFS and keep track of the depth of the lowest node in every branch (no
+        // neighbors besides its parent), which must all be equal.
         let mut visited: HashSet<GameNodeId> = HashSet::new();
         let mut stack: VecDeque<(GameNodeId, GameNodeId, u16)> = VecDeque::new(); // Store (node, parent, depth).
-        let mut deepest: u16 = 0;
+        let mut leaf_depths: Vec<u16> = Vec::new();
 
         stack.push_back((node.id, node.id, 0));
         visited.insert(node.id);
 
         while let Some((node_id, parent_id, node_depth)) = stack.pop_back() {
             if let Some(neighbors) = adj_matrix.get(&node_id) {
-                // If no neighbors (parent will always exist), it's a leaf node and we check depth
-                if neighbors.len() == 1 {
-                    if deepest == 0 {
-                        deepest = node_depth;
-                    } else if node_depth != deepest {
-                        return false;
-                    }
+                // If no neighbors (parent will always exist), it's a leaf node and we note its depth
+                if neighbors.len() == 1 && node_id != node.id && !leaf_depths.contains(&node_depth)
+                {
+                    leaf_depths.push(node_depth);
                 }
                 for &neighbor in neighbors {
                     if visited.contains(&neighbor) {
                         if neighbor != parent_id {
                             // If it ran into a visited node that isn't the parent, it's a cycle
-                            return false;
+                            return CheckResult::new(false, Diagnostic::BranchCycle);
                         }
                     } else {
                         // Unvisited nodes pushed back with depth incremented
@@ -55,27 +60,75 @@ pub mod condition_checks {
             }
         }
 
-        true
+        leaf_depths.sort_unstable();
+        CheckResult::new(leaf_depths.len() <= 1, Diagnostic::LeafDepths(leaf_depths))
     }
 
-    pub fn is_leaf(node: &GameNode, solution: &Solution) -> bool {
-        solution_to_adjacency_matrix(solution)
-            .get(&node.id)
-            .unwrap_or(&HashSet::new())
-            .len()
-            == 1
+    pub fn is_leaf(node: &GameNode, solution: &Solution) -> CheckResult {
+        let degree = solution.degree(&node.id);
+        CheckResult::new(degree == 1, Diagnostic::Degree(degree))
     }
 
-    pub fn is_internal(node: &GameNode, solution: &Solution) -> bool {
-        solution_to_adjacency_matrix(solution)
-            .get(&node.id)
-            .unwrap_or(&HashSet::new())
-            .len()
-            > 1
+    pub fn is_internal(node: &GameNode, solution: &Solution) -> CheckResult {
+        let degree = solution.degree(&node.id);
+        CheckResult::new(degree > 1, Diagnostic::Degree(degree))
+    }
+
+    pub fn is_degree(node: &GameNode, solution: &Solution, degree: u8) -> CheckResult {
+        let node_degree = solution.degree(&node.id);
+        CheckResult::new(
+            node_degree == degree as usize,
+            Diagnostic::Degree(node_degree),
+        )
+    }
+
+    /// Checks if removing the node splits its network, reporting how many networks its
+    /// neighbors are left in.
+    pub fn is_cut_vertex(node: &GameNode, solution: &Solution) -> CheckResult {
+        let adj_matrix = solution.adjacency();
+        let Some(neighbors) = adj_matrix.get(&node.id) else {
+            return CheckResult::new(false, Diagnostic::NoLines);
+        };
+
+        // Flood fill from every neighbor without passing through the node, each fill starting
+        // from an unvisited neighbor being a separate network
+        let mut visited: HashSet<GameNodeId> = HashSet::from([node.id]);
+        let mut networks: usize = 0;
+        for &neighbor in neighbors {
+            if !visited.insert(neighbor) {
+                continue;
+            }
+            networks += 1;
+            let mut stack: Vec<GameNodeId> = vec![neighbor];
+            while let Some(curr_node) = stack.pop() {
+                for &next in adj_matrix.get(&curr_node).into_iter().flatten() {
+                    if visited.insert(next) {
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+
+        CheckResult::new(networks > 1, Diagnostic::NetworksWithout(networks))
     }
 
-    pub fn is_cycle(node: &GameNode, solution: &Solution) -> bool {
-        let adj_matrix = solution_to_adjacency_matrix(solution);
+    /// Checks if the node has exactly two lines in opposite directions. Nodes without two lines
+    /// report their degree instead.
+    pub fn is_straight(node: &GameNode, solution: &Solution, puzzle: &Puzzle) -> CheckResult {
+        let degree = solution.degree(&node.id);
+        if degree != 2 {
+            return CheckResult::new(false, Diagnostic::Degree(degree));
+        }
+
+        let neighbors = solution.adjacency().remove(&node.id).unwrap_or_default();
+        let straight = get_opposite_node_pairs(&node.id, puzzle)
+            .iter()
+            .any(|(node_a, node_b)| neighbors.contains(node_a) && neighbors.contains(node_b));
+        CheckResult::new(straight, Diagnostic::Straight(straight))
+    }
+
+    pub fn is_cycle(node: &GameNode, solution: &Solution) -> CheckResult {
+        let adj_matrix = solution.adjacency();
 
         let mut visited = HashSet::new();
 
@@ -110,7 +163,10 @@ pub mod condition_checks {
 
                                         for cycle_node in cycle {
                                             if cycle_node == node.id {
-                                                return true;
+                                                return CheckResult::new(
+                                                    true,
+                                                    Diagnostic::OnCycle(true),
+                                                );
                                             }
                                         }
                                     } else {
@@ -124,19 +180,25 @@ pub mod condition_checks {
             }
         }
 
-        false
+        CheckResult::new(false, Diagnostic::OnCycle(false))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        logic::condition_checks::condition_checks::{
-            is_branch_equal, is_cycle, is_internal, is_leaf,
+        logic::{
+            condition_checks::condition_checks::{
+                is_branch_equal, is_cut_vertex, is_cycle, is_degree, is_internal, is_leaf,
+                is_straight,
+            },
+            diagnostic::diagnostic::Diagnostic,
+            topology::topology::Topology,
         },
         structs::immutable::{
             game_line::game_line::GameLine, game_node::game_node::GameNode,
-            node_condition::node_condition::NodeCondition, solution::solution::Solution,
+            node_condition::node_condition::NodeCondition, puzzle::puzzle::Puzzle,
+            solution::solution::Solution,
         },
     };
 
@@ -148,25 +210,28 @@ mod tests {
         }
     }
 
+    /// Returns a 3x3 puzzle without conditions, whose center node 4 has a neighbor in every
+    /// direction.
+    fn get_test_puzzle() -> Puzzle {
+        Puzzle::new(3, 3)
+    }
+
     #[test]
     fn test_branch_equal_node_with_no_connections_returns_false() {
         let node = get_test_node([NodeCondition::BranchEqual].to_vec());
 
         let solution = Solution::new();
 
-        assert!(!is_branch_equal(&node, &solution))
+        assert!(!is_branch_equal(&node, &solution).satisfied)
     }
 
     #[test]
     fn test_branch_equal_node_with_one_connection_returns_true() {
         let node = get_test_node([NodeCondition::BranchEqual].to_vec());
 
-        let solution = Solution::from([GameLine {
-            node_a_id: 0,
-            node_b_id: 1,
-        }]);
+        let solution = Solution::from([GameLine::new(0, 1).unwrap()]);
 
-        assert!(is_branch_equal(&node, &solution))
+        assert!(is_branch_equal(&node, &solution).satisfied)
     }
 
     #[test]
@@ -174,21 +239,12 @@ mod tests {
         let node = get_test_node([NodeCondition::BranchEqual].to_vec());
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 2,
-            },
-            GameLine {
-                node_a_id: 2,
-                node_b_id: 3,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(2, 3).unwrap(),
         ]);
 
-        assert!(is_branch_equal(&node, &solution))
+        assert!(is_branch_equal(&node, &solution).satisfied)
     }
 
     #[test]
@@ -196,21 +252,12 @@ mod tests {
         let node = get_test_node([NodeCondition::BranchEqual].to_vec());
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 2,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 3,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(0, 2).unwrap(),
+            GameLine::new(0, 3).unwrap(),
         ]);
 
-        assert!(is_branch_equal(&node, &solution))
+        assert!(is_branch_equal(&node, &solution).satisfied)
     }
 
     #[test]
@@ -218,29 +265,14 @@ mod tests {
         let node = get_test_node([NodeCondition::BranchEqual].to_vec());
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 4,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 2,
-            },
-            GameLine {
-                node_a_id: 2,
-                node_b_id: 5,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(1, 4).unwrap(),
+            GameLine::new(0, 2).unwrap(),
+            GameLine::new(2, 5).unwrap(),
         ]);
 
-        assert!(is_branch_equal(&node, &solution))
+        assert!(is_branch_equal(&node, &solution).satisfied)
     }
 
     #[test]
@@ -248,29 +280,30 @@ mod tests {
         let node = get_test_node([NodeCondition::BranchEqual].to_vec());
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 2,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 3,
-                node_b_id: 4,
-            },
-            GameLine {
-                node_a_id: 4,
-                node_b_id: 5,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(0, 3).unwrap(),
+            GameLine::new(3, 4).unwrap(),
+            GameLine::new(4, 5).unwrap(),
+        ]);
+
+        assert!(!is_branch_equal(&node, &solution).satisfied)
+    }
+
+    #[test]
+    fn test_branch_equal_node_with_length_one_and_length_two_branch_reports_depths() {
+        let node = get_test_node([NodeCondition::BranchEqual].to_vec());
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(0, 3).unwrap(),
         ]);
 
-        assert!(!is_branch_equal(&node, &solution))
+        assert_eq!(
+            is_branch_equal(&node, &solution).diagnostic,
+            Diagnostic::LeafDepths([1, 2].to_vec())
+        )
     }
 
     #[test]
@@ -278,25 +311,13 @@ mod tests {
         let node = get_test_node([NodeCondition::BranchEqual].to_vec());
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 2,
-            },
-            GameLine {
-                node_a_id: 2,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 3,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+            GameLine::new(0, 3).unwrap(),
         ]);
 
-        assert!(!is_branch_equal(&node, &solution))
+        assert!(!is_branch_equal(&node, &solution).satisfied)
     }
 
     #[test]
@@ -304,25 +325,13 @@ mod tests {
         let node = get_test_node([NodeCondition::BranchEqual].to_vec());
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 2,
-            },
-            GameLine {
-                node_a_id: 2,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 3,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+            GameLine::new(1, 3).unwrap(),
         ]);
 
-        assert!(!is_branch_equal(&node, &solution))
+        assert!(!is_branch_equal(&node, &solution).satisfied)
     }
 
     #[test]
@@ -331,37 +340,25 @@ mod tests {
 
         let solution = Solution::new();
 
-        assert!(!is_leaf(&node, &solution))
+        assert!(!is_leaf(&node, &solution).satisfied)
     }
 
     #[test]
     fn test_leaf_one_neighbor_returns_true() {
         let node = get_test_node([NodeCondition::Leaf].to_vec());
 
-        let solution = Solution::from([GameLine {
-            node_a_id: 0,
-            node_b_id: 1,
-        }]);
+        let solution = Solution::from([GameLine::new(0, 1).unwrap()]);
 
-        assert!(is_leaf(&node, &solution))
+        assert!(is_leaf(&node, &solution).satisfied)
     }
 
     #[test]
     fn test_leaf_two_neighbors_returns_false() {
         let node = get_test_node([NodeCondition::Leaf].to_vec());
 
-        let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 2,
-            },
-        ]);
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(0, 2).unwrap()]);
 
-        assert!(!is_leaf(&node, &solution))
+        assert!(!is_leaf(&node, &solution).satisfied)
     }
 
     #[test]
@@ -370,37 +367,25 @@ mod tests {
 
         let solution = Solution::new();
 
-        assert!(!is_internal(&node, &solution))
+        assert!(!is_internal(&node, &solution).satisfied)
     }
 
     #[test]
     fn test_internal_one_neighbor_returns_false() {
         let node = get_test_node([NodeCondition::Internal].to_vec());
 
-        let solution = Solution::from([GameLine {
-            node_a_id: 0,
-            node_b_id: 1,
-        }]);
+        let solution = Solution::from([GameLine::new(0, 1).unwrap()]);
 
-        assert!(!is_internal(&node, &solution))
+        assert!(!is_internal(&node, &solution).satisfied)
     }
 
     #[test]
     fn test_internal_two_neighbors_returns_true() {
         let node = get_test_node([NodeCondition::Internal].to_vec());
 
-        let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 2,
-            },
-        ]);
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(0, 2).unwrap()]);
 
-        assert!(is_internal(&node, &solution))
+        assert!(is_internal(&node, &solution).satisfied)
     }
 
     #[test]
@@ -409,37 +394,25 @@ mod tests {
 
         let solution = Solution::new();
 
-        assert!(!is_cycle(&node, &solution))
+        assert!(!is_cycle(&node, &solution).satisfied)
     }
 
     #[test]
     fn test_cycle_leaf_returns_false() {
         let node = get_test_node([NodeCondition::Cycle].to_vec());
 
-        let solution = Solution::from([GameLine {
-            node_a_id: 0,
-            node_b_id: 1,
-        }]);
+        let solution = Solution::from([GameLine::new(0, 1).unwrap()]);
 
-        assert!(!is_cycle(&node, &solution))
+        assert!(!is_cycle(&node, &solution).satisfied)
     }
 
     #[test]
     fn test_cycle_in_branch_no_cycle_returns_false() {
         let node = get_test_node([NodeCondition::Cycle].to_vec());
 
-        let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 2,
-            },
-        ]);
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(0, 2).unwrap()]);
 
-        assert!(!is_cycle(&node, &solution))
+        assert!(!is_cycle(&node, &solution).satisfied)
     }
 
     #[test]
@@ -447,25 +420,13 @@ mod tests {
         let node = get_test_node([NodeCondition::Cycle].to_vec());
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 2,
-            },
-            GameLine {
-                node_a_id: 2,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 3,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+            GameLine::new(0, 3).unwrap(),
         ]);
 
-        assert!(is_cycle(&node, &solution))
+        assert!(is_cycle(&node, &solution).satisfied)
     }
 
     #[test]
@@ -473,25 +434,13 @@ mod tests {
         let node = get_test_node([NodeCondition::Cycle].to_vec());
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 2,
-            },
-            GameLine {
-                node_a_id: 2,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+            GameLine::new(0, 1).unwrap(),
         ]);
 
-        assert!(!is_cycle(&node, &solution))
+        assert!(!is_cycle(&node, &solution).satisfied)
     }
 
     #[test]
@@ -499,24 +448,152 @@ mod tests {
         let node = get_test_node([NodeCondition::Cycle].to_vec());
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 2,
-            },
-            GameLine {
-                node_a_id: 2,
-                node_b_id: 0,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 3,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(2, 0).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+        ]);
+
+        assert!(is_cycle(&node, &solution).satisfied)
+    }
+
+    #[test]
+    fn test_degree_matching_degree_returns_true() {
+        let node = get_test_node([NodeCondition::Degree(2)].to_vec());
+
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(0, 2).unwrap()]);
+
+        assert!(is_degree(&node, &solution, 2).satisfied)
+    }
+
+    #[test]
+    fn test_degree_other_degree_returns_false() {
+        let node = get_test_node([NodeCondition::Degree(3)].to_vec());
+
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(0, 2).unwrap()]);
+
+        assert!(!is_degree(&node, &solution, 3).satisfied)
+    }
+
+    #[test]
+    fn test_degree_no_neighbors_returns_false() {
+        let node = get_test_node([NodeCondition::Degree(1)].to_vec());
+
+        let solution = Solution::new();
+
+        assert!(!is_degree(&node, &solution, 1).satisfied)
+    }
+
+    #[test]
+    fn test_cut_vertex_no_neighbors_returns_false() {
+        let node = get_test_node([NodeCondition::CutVertex].to_vec());
+
+        let solution = Solution::new();
+
+        assert!(!is_cut_vertex(&node, &solution).satisfied)
+    }
+
+    #[test]
+    fn test_cut_vertex_leaf_returns_false() {
+        let node = get_test_node([NodeCondition::CutVertex].to_vec());
+
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(1, 2).unwrap()]);
+
+        assert!(!is_cut_vertex(&node, &solution).satisfied)
+    }
+
+    #[test]
+    fn test_cut_vertex_middle_of_path_returns_true() {
+        let node = get_test_node([NodeCondition::CutVertex].to_vec());
+
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(0, 2).unwrap()]);
+
+        assert!(is_cut_vertex(&node, &solution).satisfied)
+    }
+
+    #[test]
+    fn test_cut_vertex_in_cycle_returns_false() {
+        let node = get_test_node([NodeCondition::CutVertex].to_vec());
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(2, 0).unwrap(),
+        ]);
+
+        assert!(!is_cut_vertex(&node, &solution).satisfied)
+    }
+
+    #[test]
+    fn test_cut_vertex_joining_cycle_and_branch_reports_networks() {
+        let node = get_test_node([NodeCondition::CutVertex].to_vec());
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(2, 0).unwrap(),
+            GameLine::new(0, 3).unwrap(),
+        ]);
+
+        let result = is_cut_vertex(&node, &solution);
+        assert!(result.satisfied);
+        assert_eq!(result.diagnostic, Diagnostic::NetworksWithout(2))
+    }
+
+    #[test]
+    fn test_straight_horizontal_lines_returns_true() {
+        let mut node = get_test_node([NodeCondition::Straight].to_vec());
+        node.id = 4;
+
+        let solution = Solution::from([GameLine::new(1, 4).unwrap(), GameLine::new(4, 7).unwrap()]);
+
+        assert!(is_straight(&node, &solution, &get_test_puzzle()).satisfied)
+    }
+
+    #[test]
+    fn test_straight_diagonal_lines_returns_true() {
+        let mut node = get_test_node([NodeCondition::Straight].to_vec());
+        node.id = 4;
+
+        let solution = Solution::from([GameLine::new(0, 4).unwrap(), GameLine::new(4, 8).unwrap()]);
+
+        assert!(is_straight(&node, &solution, &get_test_puzzle()).satisfied)
+    }
+
+    #[test]
+    fn test_straight_hex_opposite_lines_returns_true() {
+        let mut node = get_test_node([NodeCondition::Straight].to_vec());
+        node.id = 4;
+        let mut puzzle = get_test_puzzle();
+        puzzle.topology = Topology::Hex;
+
+        // Node 4 is the center hex, between its upper left neighbor 2 and lower right neighbor 7
+        let solution = Solution::from([GameLine::new(2, 4).unwrap(), GameLine::new(4, 7).unwrap()]);
+
+        assert!(is_straight(&node, &solution, &puzzle).satisfied)
+    }
+
+    #[test]
+    fn test_straight_turning_lines_returns_false() {
+        let mut node = get_test_node([NodeCondition::Straight].to_vec());
+        node.id = 4;
+
+        let solution = Solution::from([GameLine::new(1, 4).unwrap(), GameLine::new(4, 5).unwrap()]);
+
+        assert!(!is_straight(&node, &solution, &get_test_puzzle()).satisfied)
+    }
+
+    #[test]
+    fn test_straight_three_lines_returns_false() {
+        let mut node = get_test_node([NodeCondition::Straight].to_vec());
+        node.id = 4;
+
+        let solution = Solution::from([
+            GameLine::new(1, 4).unwrap(),
+            GameLine::new(4, 7).unwrap(),
+            GameLine::new(3, 4).unwrap(),
         ]);
 
-        assert!(is_cycle(&node, &solution))
+        assert!(!is_straight(&node, &solution, &get_test_puzzle()).satisfied)
     }
 }
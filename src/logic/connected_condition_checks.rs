@@ -4,9 +4,12 @@ pub mod connected_condition_checks {
         usize,
     };
 
-    use crate::structs::immutable::{
-        game_node::game_node::{GameNode, GameNodeId},
-        solution::solution::{solution_to_adjacency_matrix, Solution},
+    use crate::{
+        logic::diagnostic::diagnostic::{CheckResult, Diagnostic},
+        structs::immutable::{
+            game_node::game_node::{GameNode, GameNodeId},
+            solution::solution::{AdjacencyMatrix, Solution},
+        },
     };
 
     /// Checks if all nodes with the same condition and class have the same
@@ -19,27 +22,18 @@ pub mod connected_condition_checks {
     ///
     /// # Returns
     ///
-    /// Returns true if all nodes have equal degree, otherwise false.  
-    pub fn is_degree_equal(nodes: Vec<&GameNode>, solution: &Solution) -> bool {
-        let adj_matrix = solution_to_adjacency_matrix(solution);
-
-        // This is safe since max degree is 8 in this game
-        let mut degree: u8 = u8::MAX;
-        for node in nodes.iter() {
-            let node_degree = adj_matrix.get(&node.id).unwrap_or(&HashSet::new()).len() as u8;
-            if degree == u8::MAX {
-                degree = node_degree;
-                if degree == 0 {
-                    return false;
-                }
-            } else {
-                if degree != node_degree {
-                    return false;
-                }
-            }
-        }
-
-        return true;
+    /// Returns true if all nodes have equal degree, otherwise false, along with the degree of
+    /// every node.
+    pub fn is_degree_equal(nodes: Vec<&GameNode>, solution: &Solution) -> CheckResult {
+        let degrees: Vec<usize> = nodes.iter().map(|node| solution.degree(&node.id)).collect();
+
+        let satisfied = match degrees.first() {
+            Some(0) => false,
+            Some(degree) => degrees.iter().all(|node_degree| node_degree == degree),
+            None => true,
+        };
+
+        CheckResult::new(satisfied, Diagnostic::Degrees(degrees))
     }
 
     /// Checks if all nodes with the same condition and class have the same
@@ -52,19 +46,182 @@ pub mod connected_condition_checks {
     ///
     /// # Returns
     ///
-    /// Returns true if all nodes have equal distance, otherwise false.
-    pub fn is_distance_equal(nodes: Vec<&GameNode>, solution: &Solution) -> bool {
-        let adj_matrix = solution_to_adjacency_matrix(solution);
+    /// Returns true if all nodes have equal distance, otherwise false, along with the distance
+    /// between every pair of nodes.
+    pub fn is_distance_equal(nodes: Vec<&GameNode>, solution: &Solution) -> CheckResult {
+        // If only one node, check if it has any connections
+        if nodes.len() == 1 {
+            let degree = solution.degree(&nodes[0].id);
+            return CheckResult::new(degree > 0, Diagnostic::Degree(degree));
+        }
 
+        let distances = get_pairwise_distances(&nodes, solution);
+
+        // Final check to verify they aren't all disconnected
+        let satisfied = match distances.first() {
+            Some(None) => false,
+            Some(distance) => distances.iter().all(|distance_v| distance_v == distance),
+            None => true,
+        };
+
+        CheckResult::new(satisfied, Diagnostic::Distances(distances))
+    }
+
+    /// Checks if all nodes with the same condition and class have a different
+    /// number of connected edges from each other.
+    ///
+    /// # Parameters
+    ///
+    /// - `nodes`: Nodes of the same condition and class in the puzzle.
+    /// - `solution`: Proposed solution in the puzzle to check against.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if every node has a degree no other node has, otherwise false, along with
+    /// the degree of every node.
+    pub fn is_degree_distinct(nodes: Vec<&GameNode>, solution: &Solution) -> CheckResult {
+        let degrees: Vec<usize> = nodes.iter().map(|node| solution.degree(&node.id)).collect();
+
+        let mut seen: HashSet<usize> = HashSet::new();
+        let satisfied = degrees
+            .iter()
+            .all(|degree| *degree > 0 && seen.insert(*degree));
+
+        CheckResult::new(satisfied, Diagnostic::Degrees(degrees))
+    }
+
+    /// Checks if all nodes with the same condition and class have a different
+    /// shortest distance between each pair of them.
+    ///
+    /// # Parameters
+    ///
+    /// - `nodes`: Nodes of the same condition and class in the puzzle.
+    /// - `solution`: Proposed solution in the puzzle to check against.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if every pair of nodes is connected by a distance no other pair has,
+    /// otherwise false, along with the distance between every pair of nodes.
+    pub fn is_distance_distinct(nodes: Vec<&GameNode>, solution: &Solution) -> CheckResult {
         // If only one node, check if it has any connections
         if nodes.len() == 1 {
-            return adj_matrix.get(&nodes[0].id).is_some();
+            let degree = solution.degree(&nodes[0].id);
+            return CheckResult::new(degree > 0, Diagnostic::Degree(degree));
+        }
+
+        let distances = get_pairwise_distances(&nodes, solution);
+
+        let mut seen: HashSet<usize> = HashSet::new();
+        let satisfied = distances.iter().all(|distance| match distance {
+            Some(distance) => seen.insert(*distance),
+            None => false,
+        });
+
+        CheckResult::new(satisfied, Diagnostic::Distances(distances))
+    }
+
+    /// Checks if all nodes with the same condition and class lie on a single simple path
+    /// of the solution, which visits no node twice.
+    ///
+    /// # Parameters
+    ///
+    /// - `nodes`: Nodes of the same condition and class in the puzzle.
+    /// - `solution`: Proposed solution in the puzzle to check against.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if a single simple path passes through every node, otherwise false.
+    pub fn is_same_component_path(nodes: Vec<&GameNode>, solution: &Solution) -> CheckResult {
+        let satisfied = has_single_path(&nodes, &solution.adjacency());
+
+        CheckResult::new(satisfied, Diagnostic::SinglePath(satisfied))
+    }
+
+    /// Searches the lines for a simple path passing through every node.
+    ///
+    /// # Parameters
+    ///
+    /// - `nodes`: Nodes the path must pass through.
+    /// - `adj_matrix`: Adjacency of the lines the path may follow.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if such a path exists, otherwise false. A single node only needs a line.
+    pub fn has_single_path(nodes: &[&GameNode], adj_matrix: &AdjacencyMatrix) -> bool {
+        let targets: HashSet<GameNodeId> = nodes.iter().map(|node| node.id).collect();
+
+        match nodes.first() {
+            Some(node) if targets.len() == 1 => adj_matrix
+                .get(&node.id)
+                .is_some_and(|neighbors| !neighbors.is_empty()),
+            // Any such path can be cut down to start and end at one of the nodes
+            Some(_) => targets.iter().any(|start| {
+                let mut path: Vec<GameNodeId> = Vec::from([*start]);
+                extend_path(adj_matrix, &targets, &mut path, 1)
+            }),
+            None => true,
+        }
+    }
+
+    /// Extends the simple path one node at a time, depth first, until it has visited every
+    /// target node.
+    ///
+    /// # Parameters
+    ///
+    /// - `adj_matrix`: Adjacency of the solution the path follows.
+    /// - `targets`: Nodes the path must visit.
+    /// - `path`: Nodes visited so far, in order, ending at the node to extend from.
+    /// - `visited_targets`: Number of target nodes on the path so far.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if the path can be extended to visit every target node.
+    fn extend_path(
+        adj_matrix: &AdjacencyMatrix,
+        targets: &HashSet<GameNodeId>,
+        path: &mut Vec<GameNodeId>,
+        visited_targets: usize,
+    ) -> bool {
+        if visited_targets == targets.len() {
+            return true;
         }
 
+        let Some(neighbors) = path.last().and_then(|node| adj_matrix.get(node)) else {
+            return false;
+        };
+        for neighbor in neighbors {
+            if path.contains(neighbor) {
+                continue;
+            }
+            path.push(*neighbor);
+            let visited = visited_targets + usize::from(targets.contains(neighbor));
+            if extend_path(adj_matrix, targets, path, visited) {
+                return true;
+            }
+            path.pop();
+        }
+
+        false
+    }
+
+    /// Computes the shortest distance between every pair of nodes through the lines of the
+    /// solution, with every line 1 long.
+    ///
+    /// # Parameters
+    ///
+    /// - `nodes`: Nodes to measure the distances between.
+    /// - `solution`: Solution whose lines join the nodes.
+    ///
+    /// # Returns
+    ///
+    /// Returns the distance of every pair of nodes in order, None if no path joins them.
+    fn get_pairwise_distances(nodes: &[&GameNode], solution: &Solution) -> Vec<Option<usize>> {
+        let adj_matrix = solution.adjacency();
+
         let mut distances_map: HashMap<GameNodeId, HashMap<GameNodeId, usize>> = HashMap::new();
 
         // Distance to itself is 0
-        for node in &nodes {
+        for node in nodes {
             distances_map.insert(node.id, HashMap::from([(node.id, 0)]));
         }
 
@@ -118,35 +275,32 @@ pub mod connected_condition_checks {
             }
         }
 
-        // Verify that all distances of the relevant nodes are the same
-        let mut common_distance: usize = 0;
-        for node_u in &nodes {
-            for node_v in &nodes {
-                if node_u.id != node_v.id {
-                    let distance_v = distances_map
-                        .get(&node_u.id)
-                        .and_then(|m| m.get(&node_v.id))
-                        .copied()
-                        .unwrap_or(usize::MAX);
-                    if common_distance == 0 {
-                        common_distance = distance_v;
-                    } else if distance_v != common_distance {
-                        return false;
-                    }
-                }
+        // Collect the distance of every pair of the relevant nodes
+        let mut distances: Vec<Option<usize>> = Vec::new();
+        for (idx, node_u) in nodes.iter().enumerate() {
+            for node_v in nodes.iter().skip(idx + 1) {
+                let distance_v = distances_map
+                    .get(&node_u.id)
+                    .and_then(|m| m.get(&node_v.id))
+                    .copied()
+                    .filter(|distance| *distance != usize::MAX);
+                distances.push(distance_v);
             }
         }
 
-        // Final check to verify they aren't all disconnected
-        common_distance != usize::MAX
+        distances
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        logic::connected_condition_checks::connected_condition_checks::{
-            is_degree_equal, is_distance_equal,
+        logic::{
+            connected_condition_checks::connected_condition_checks::{
+                is_degree_distinct, is_degree_equal, is_distance_distinct, is_distance_equal,
+                is_same_component_path,
+            },
+            diagnostic::diagnostic::Diagnostic,
         },
         structs::immutable::{
             connected_node_condition::connected_node_condition::{
@@ -170,53 +324,44 @@ mod tests {
     fn test_degree_equal_one_node_with_no_connections_returns_false() {
         let node = get_test_node(
             0,
-            [ConnectedNodeCondition::DegreeEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DegreeEqual(ConditionClass(0))].to_vec(),
         );
 
         let solution = Solution::new();
 
-        assert!(!is_degree_equal(Vec::from([&node]), &solution))
+        assert!(!is_degree_equal(Vec::from([&node]), &solution).satisfied)
     }
 
     #[test]
     fn test_degree_equal_one_node_with_many_connections_returns_true() {
         let node = get_test_node(
             0,
-            [ConnectedNodeCondition::DegreeEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DegreeEqual(ConditionClass(0))].to_vec(),
         );
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 2,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 3,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(0, 2).unwrap(),
+            GameLine::new(0, 3).unwrap(),
         ]);
 
-        assert!(is_degree_equal(Vec::from([&node]), &solution))
+        assert!(is_degree_equal(Vec::from([&node]), &solution).satisfied)
     }
 
     #[test]
     fn test_degree_equal_two_nodes_with_no_connections_returns_false() {
         let node_a = get_test_node(
             0,
-            [ConnectedNodeCondition::DegreeEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DegreeEqual(ConditionClass(0))].to_vec(),
         );
         let node_b = get_test_node(
             1,
-            [ConnectedNodeCondition::DegreeEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DegreeEqual(ConditionClass(0))].to_vec(),
         );
 
         let solution = Solution::new();
 
-        assert!(!is_degree_equal(Vec::from([&node_a, &node_b]), &solution))
+        assert!(!is_degree_equal(Vec::from([&node_a, &node_b]), &solution).satisfied)
     }
 
     #[test]
@@ -224,284 +369,361 @@ mod tests {
     fn test_degree_equal_two_nodes_with_two_connections_returns_true() {
         let node_a = get_test_node(
             0,
-            [ConnectedNodeCondition::DegreeEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DegreeEqual(ConditionClass(0))].to_vec(),
         );
         let node_b = get_test_node(
             1,
-            [ConnectedNodeCondition::DegreeEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DegreeEqual(ConditionClass(0))].to_vec(),
         );
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 2,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 2,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(0, 2).unwrap(),
+            GameLine::new(1, 2).unwrap(),
         ]);
 
-        assert!(is_degree_equal(Vec::from([&node_a, &node_b]), &solution))
+        assert!(is_degree_equal(Vec::from([&node_a, &node_b]), &solution).satisfied)
     }
 
     #[test]
     fn test_degree_equal_two_nodes_with_different_degree_returns_false() {
         let node_a = get_test_node(
             0,
-            [ConnectedNodeCondition::DegreeEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DegreeEqual(ConditionClass(0))].to_vec(),
         );
         let node_b = get_test_node(
             1,
-            [ConnectedNodeCondition::DegreeEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DegreeEqual(ConditionClass(0))].to_vec(),
         );
 
-        let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 2,
-            },
-        ]);
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(0, 2).unwrap()]);
 
-        assert!(!is_degree_equal(Vec::from([&node_a, &node_b]), &solution))
+        assert!(!is_degree_equal(Vec::from([&node_a, &node_b]), &solution).satisfied)
     }
 
     #[test]
     fn test_distance_equal_multiple_nodes_with_some_distance_equal_returns_false() {
         let node_a = get_test_node(
             1,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
         let node_b = get_test_node(
             3,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
         let node_c = get_test_node(
             2,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
         let node_d = get_test_node(
             0,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
 
-        let solution = Solution::from([GameLine {
-            node_a_id: 1,
-            node_b_id: 3,
-        }]);
+        let solution = Solution::from([GameLine::new(1, 3).unwrap()]);
 
-        assert!(!is_distance_equal(
-            Vec::from([&node_d, &node_a, &node_b, &node_c]),
-            &solution
-        ))
+        assert!(
+            !is_distance_equal(Vec::from([&node_d, &node_a, &node_b, &node_c]), &solution)
+                .satisfied
+        )
     }
 
     #[test]
     fn test_distance_equal_one_node_with_no_connections_returns_false() {
         let node = get_test_node(
             0,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
 
         let solution = Solution::new();
 
-        assert!(!is_distance_equal(Vec::from([&node]), &solution))
+        assert!(!is_distance_equal(Vec::from([&node]), &solution).satisfied)
     }
 
     #[test]
     fn test_distance_equal_one_node_with_many_connections_returns_true() {
         let node = get_test_node(
             0,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 2,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 3,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(0, 2).unwrap(),
+            GameLine::new(0, 3).unwrap(),
         ]);
 
-        assert!(is_distance_equal(Vec::from([&node]), &solution))
+        assert!(is_distance_equal(Vec::from([&node]), &solution).satisfied)
     }
 
     #[test]
     fn test_distance_equal_two_nodes_connected_returns_true() {
         let node_a = get_test_node(
             0,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
         let node_b = get_test_node(
             1,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
 
-        let solution = Solution::from([GameLine {
-            node_a_id: 0,
-            node_b_id: 1,
-        }]);
+        let solution = Solution::from([GameLine::new(0, 1).unwrap()]);
 
-        assert!(is_distance_equal(Vec::from([&node_a, &node_b]), &solution))
+        assert!(is_distance_equal(Vec::from([&node_a, &node_b]), &solution).satisfied)
     }
 
     #[test]
     fn test_distance_equal_two_nodes_disconnected_returns_false() {
         let node_a = get_test_node(
             0,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
         let node_b = get_test_node(
             1,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
 
-        let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 2,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 3,
-            },
-        ]);
+        let solution = Solution::from([GameLine::new(0, 2).unwrap(), GameLine::new(1, 3).unwrap()]);
 
-        assert!(!is_distance_equal(Vec::from([&node_a, &node_b]), &solution))
+        assert!(!is_distance_equal(Vec::from([&node_a, &node_b]), &solution).satisfied)
     }
 
     #[test]
     fn test_distance_equal_three_nodes_same_distance_returns_true() {
         let node_a = get_test_node(
             0,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
         let node_b = get_test_node(
             1,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
         let node_c = get_test_node(
             2,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 2,
-                node_b_id: 3,
-            },
+            GameLine::new(0, 3).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(2, 3).unwrap(),
         ]);
 
-        assert!(is_distance_equal(
-            Vec::from([&node_a, &node_b, &node_c]),
-            &solution
-        ))
+        assert!(is_distance_equal(Vec::from([&node_a, &node_b, &node_c]), &solution).satisfied)
     }
 
     #[test]
     fn test_distance_equal_three_nodes_different_distance_returns_false() {
         let node_a = get_test_node(
             0,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
         let node_b = get_test_node(
             1,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
         let node_c = get_test_node(
             2,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 2,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 2,
-            },
+            GameLine::new(0, 3).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+            GameLine::new(0, 2).unwrap(),
         ]);
 
-        assert!(!is_distance_equal(
-            Vec::from([&node_a, &node_b, &node_c]),
-            &solution
-        ))
+        assert!(!is_distance_equal(Vec::from([&node_a, &node_b, &node_c]), &solution).satisfied)
+    }
+
+    #[test]
+    fn test_distance_equal_three_nodes_different_distance_reports_pairwise_distances() {
+        let node_a = get_test_node(
+            0,
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
+        );
+        let node_b = get_test_node(
+            1,
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
+        );
+        let node_c = get_test_node(
+            2,
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
+        );
+
+        let solution = Solution::from([
+            GameLine::new(0, 3).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+            GameLine::new(0, 2).unwrap(),
+        ]);
+
+        assert_eq!(
+            is_distance_equal(Vec::from([&node_a, &node_b, &node_c]), &solution).diagnostic,
+            Diagnostic::Distances([Some(2), Some(1), Some(2)].to_vec())
+        )
     }
 
     #[test]
     fn test_distance_equal_three_nodes_same_distance_multiple_paths_longer_distance_returns_true() {
         let node_a = get_test_node(
             0,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
         let node_b = get_test_node(
             1,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
         let node_c = get_test_node(
             2,
-            [ConnectedNodeCondition::DistanceEqual(ConditionClass::Blue)].to_vec(),
+            [ConnectedNodeCondition::DistanceEqual(ConditionClass(0))].to_vec(),
         );
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 2,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 4,
-            },
-            GameLine {
-                node_a_id: 3,
-                node_b_id: 4,
-            },
+            GameLine::new(0, 3).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+            GameLine::new(0, 4).unwrap(),
+            GameLine::new(3, 4).unwrap(),
+        ]);
+
+        assert!(is_distance_equal(Vec::from([&node_a, &node_b, &node_c]), &solution).satisfied)
+    }
+
+    #[test]
+    fn test_degree_distinct_three_nodes_with_different_degrees_returns_true() {
+        let con_cdtns = [ConnectedNodeCondition::DegreeDistinct(ConditionClass(0))].to_vec();
+        let node_a = get_test_node(0, con_cdtns.clone());
+        let node_b = get_test_node(1, con_cdtns.clone());
+        let node_c = get_test_node(2, con_cdtns);
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(0, 2).unwrap(),
+            GameLine::new(0, 3).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+        ]);
+
+        assert!(is_degree_distinct(Vec::from([&node_a, &node_b, &node_c]), &solution).satisfied)
+    }
+
+    #[test]
+    fn test_degree_distinct_two_nodes_with_same_degree_returns_false() {
+        let con_cdtns = [ConnectedNodeCondition::DegreeDistinct(ConditionClass(0))].to_vec();
+        let node_a = get_test_node(0, con_cdtns.clone());
+        let node_b = get_test_node(1, con_cdtns);
+
+        let solution = Solution::from([GameLine::new(0, 1).unwrap()]);
+
+        assert!(!is_degree_distinct(Vec::from([&node_a, &node_b]), &solution).satisfied)
+    }
+
+    #[test]
+    fn test_degree_distinct_node_with_no_connections_returns_false() {
+        let con_cdtns = [ConnectedNodeCondition::DegreeDistinct(ConditionClass(0))].to_vec();
+        let node_a = get_test_node(0, con_cdtns.clone());
+        let node_b = get_test_node(1, con_cdtns);
+
+        let solution = Solution::from([GameLine::new(0, 2).unwrap()]);
+
+        assert!(!is_degree_distinct(Vec::from([&node_a, &node_b]), &solution).satisfied)
+    }
+
+    #[test]
+    fn test_distance_distinct_three_nodes_on_path_returns_true() {
+        let con_cdtns = [ConnectedNodeCondition::DistanceDistinct(ConditionClass(0))].to_vec();
+        let node_a = get_test_node(0, con_cdtns.clone());
+        let node_b = get_test_node(1, con_cdtns.clone());
+        let node_c = get_test_node(2, con_cdtns);
+
+        // Distances are 1 between 0 and 1, 2 between 1 and 2 and 3 between 0 and 2
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+        ]);
+
+        assert!(is_distance_distinct(Vec::from([&node_a, &node_b, &node_c]), &solution).satisfied)
+    }
+
+    #[test]
+    fn test_distance_distinct_three_nodes_same_distance_returns_false() {
+        let con_cdtns = [ConnectedNodeCondition::DistanceDistinct(ConditionClass(0))].to_vec();
+        let node_a = get_test_node(0, con_cdtns.clone());
+        let node_b = get_test_node(1, con_cdtns.clone());
+        let node_c = get_test_node(2, con_cdtns);
+
+        let solution = Solution::from([
+            GameLine::new(0, 3).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+        ]);
+
+        assert!(!is_distance_distinct(Vec::from([&node_a, &node_b, &node_c]), &solution).satisfied)
+    }
+
+    #[test]
+    fn test_distance_distinct_two_nodes_disconnected_returns_false() {
+        let con_cdtns = [ConnectedNodeCondition::DistanceDistinct(ConditionClass(0))].to_vec();
+        let node_a = get_test_node(0, con_cdtns.clone());
+        let node_b = get_test_node(1, con_cdtns);
+
+        let solution = Solution::from([GameLine::new(0, 2).unwrap(), GameLine::new(1, 3).unwrap()]);
+
+        assert!(!is_distance_distinct(Vec::from([&node_a, &node_b]), &solution).satisfied)
+    }
+
+    #[test]
+    fn test_same_component_path_nodes_along_path_returns_true() {
+        let con_cdtns = [ConnectedNodeCondition::SameComponentPath(ConditionClass(0))].to_vec();
+        let node_a = get_test_node(0, con_cdtns.clone());
+        let node_b = get_test_node(1, con_cdtns.clone());
+        let node_c = get_test_node(2, con_cdtns);
+
+        let solution = Solution::from([
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(0, 3).unwrap(),
+            GameLine::new(0, 2).unwrap(),
+            GameLine::new(2, 4).unwrap(),
+        ]);
+
+        assert!(is_same_component_path(Vec::from([&node_a, &node_b, &node_c]), &solution).satisfied)
+    }
+
+    #[test]
+    fn test_same_component_path_nodes_on_star_returns_false() {
+        let con_cdtns = [ConnectedNodeCondition::SameComponentPath(ConditionClass(0))].to_vec();
+        let node_a = get_test_node(0, con_cdtns.clone());
+        let node_b = get_test_node(1, con_cdtns.clone());
+        let node_c = get_test_node(2, con_cdtns);
+
+        let solution = Solution::from([
+            GameLine::new(0, 3).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+        ]);
+
+        assert_eq!(
+            is_same_component_path(Vec::from([&node_a, &node_b, &node_c]), &solution).diagnostic,
+            Diagnostic::SinglePath(false)
+        )
+    }
+
+    #[test]
+    fn test_same_component_path_nodes_on_cycle_returns_true() {
+        let con_cdtns = [ConnectedNodeCondition::SameComponentPath(ConditionClass(0))].to_vec();
+        let node_a = get_test_node(0, con_cdtns.clone());
+        let node_b = get_test_node(1, con_cdtns.clone());
+        let node_c = get_test_node(2, con_cdtns);
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(0, 2).unwrap(),
+            GameLine::new(0, 3).unwrap(),
         ]);
 
-        assert!(is_distance_equal(
-            Vec::from([&node_a, &node_b, &node_c]),
-            &solution
-        ))
+        assert!(is_same_component_path(Vec::from([&node_a, &node_b, &node_c]), &solution).satisfied)
     }
 }
@@ -1,13 +1,25 @@
 pub mod connected_rule_checks {
-    use crate::structs::immutable::{
-        game_node::game_node::GameNodeId,
-        game_set::game_set::GameSet,
-        solution::solution::{
-            filter_solution_to_set, solution_to_adjacency_matrix, AdjacencyMatrix, Solution,
+    use crate::{
+        logic::{
+            diagnostic::diagnostic::{CheckResult, Diagnostic},
+            topology::topology::GridPosition,
+            util::node_to_grid_position,
+        },
+        structs::immutable::{
+            game_node::game_node::GameNodeId,
+            game_set::game_set::GameSet,
+            puzzle::puzzle::Puzzle,
+            solution::solution::{AdjacencyMatrix, Solution},
         },
     };
-    use itertools::Itertools;
-    use std::collections::HashMap;
+    use std::{
+        cmp::Reverse,
+        collections::{HashMap, HashSet},
+    };
+
+    /// Most pair results kept by a HomomorphismCache before it is emptied, bounding its memory
+    /// when many different solutions are checked (e.g. by the solver).
+    const MAX_CACHED_HOMOMORPHISMS: usize = 4096;
 
     /// Checks if all sets with the same rule and class are homomorphic.
     ///
@@ -18,91 +30,387 @@ pub mod connected_rule_checks {
     ///
     /// # Returns
     ///
-    /// Returns true if all sets are homomorphic, otherwise false.
-    pub fn are_homomorphic(sets: Vec<&GameSet>, solution: &Solution) -> bool {
+    /// Returns true if all sets are homomorphic, otherwise false, along with the number of lines
+    /// within every set.
+    pub fn are_homomorphic(sets: Vec<&GameSet>, solution: &Solution) -> CheckResult {
+        let set_solutions: Vec<Solution> =
+            sets.iter().map(|set| solution.filter_to_set(set)).collect();
+
         // If set I homo J and J homo K, then I homo K. So we check sets (i, i+1) for all sets as homomorphic.
-        for (set_i, set_j) in sets.iter().zip(sets.iter().skip(1)) {
-            // This should be static for a puzzle, since it's unsolvable if there are sets with different sizes.
-            if set_i.nodes.len() != set_j.nodes.len() {
-                return false;
-            }
+        let matching = (1..sets.len()).all(|j| {
+            is_homomorphic(
+                sets[j - 1],
+                &set_solutions[j - 1],
+                sets[j],
+                &set_solutions[j],
+            )
+        });
 
-            // Get adj matrices of only solutions within sets I and J.
-            let set_i_sol = solution_to_adjacency_matrix(&filter_solution_to_set(set_i, solution));
-            let set_j_sol = solution_to_adjacency_matrix(&filter_solution_to_set(set_j, solution));
+        get_set_shapes_result(&set_solutions, matching)
+    }
 
-            // If either don't have any lines, return false.
-            if set_i_sol.is_empty() || set_j_sol.is_empty() {
-                return false;
-            }
+    /// Checks if all sets with the same rule and class are isomorphic. Sets with the same number
+    /// of lines are isomorphic if they are homomorphic, as every line then maps onto a line and
+    /// every missing line onto a missing line.
+    ///
+    /// # Parameters
+    ///
+    /// - `sets`: Sets of the same rule and class in the puzzle.
+    /// - `solution`: Proposed solution in the puzzle to check against.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if all sets are isomorphic, otherwise false, along with the number of lines
+    /// within every set.
+    pub fn are_isomorphic(sets: Vec<&GameSet>, solution: &Solution) -> CheckResult {
+        let set_solutions: Vec<Solution> =
+            sets.iter().map(|set| solution.filter_to_set(set)).collect();
+
+        let matching = have_equal_line_counts(&set_solutions)
+            && (1..sets.len()).all(|j| {
+                is_homomorphic(
+                    sets[j - 1],
+                    &set_solutions[j - 1],
+                    sets[j],
+                    &set_solutions[j],
+                )
+            });
+
+        get_set_shapes_result(&set_solutions, matching)
+    }
+
+    /// Checks if the lines within all sets with the same rule and class form the same pattern on
+    /// the grid, once moved, rotated or reflected.
+    ///
+    /// # Parameters
+    ///
+    /// - `sets`: Sets of the same rule and class in the puzzle.
+    /// - `solution`: Proposed solution in the puzzle to check against.
+    /// - `puzzle`: The puzzle, placing the nodes of the sets on the grid.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if all sets have congruent lines, otherwise false, along with the number of
+    /// lines within every set.
+    pub fn are_congruent(sets: Vec<&GameSet>, solution: &Solution, puzzle: &Puzzle) -> CheckResult {
+        let set_solutions: Vec<Solution> =
+            sets.iter().map(|set| solution.filter_to_set(set)).collect();
+
+        let shapes: Vec<Option<Vec<GridLine>>> = set_solutions
+            .iter()
+            .map(|set_sol| get_canonical_shape(set_sol, puzzle))
+            .collect();
+        let matching =
+            shapes.iter().all(Option::is_some) && shapes.windows(2).all(|pair| pair[0] == pair[1]);
 
-            // Generate all mappings from nodes in I to nodes in J.
-            let mappings = generate_all_mappings(&set_i.nodes, &set_j.nodes);
+        get_set_shapes_result(&set_solutions, matching)
+    }
 
-            // Check if any mapping is a homomorphism.
-            let mut homomorphism = false;
-            for mapping in mappings {
-                if is_homomorphism(&mapping, &set_i_sol, &set_j_sol) {
-                    homomorphism = true;
-                    break;
+    /// Checks if all sets with the same rule and class contain the same number of lines.
+    pub fn are_edge_counts_equal(sets: Vec<&GameSet>, solution: &Solution) -> CheckResult {
+        let line_counts: Vec<usize> = sets
+            .iter()
+            .map(|set| solution.filter_to_set(set).len())
+            .collect();
+
+        CheckResult::new(
+            line_counts.windows(2).all(|pair| pair[0] == pair[1]),
+            Diagnostic::LineCounts(line_counts),
+        )
+    }
+
+    /// Checks that no path of lines outside the sets joins two of the sets with the same rule
+    /// and class. A line is outside the sets if none of them contains both of its nodes, so
+    /// overlapping sets may still be joined through the nodes they share.
+    ///
+    /// # Parameters
+    ///
+    /// - `sets`: Sets of the same rule and class in the puzzle.
+    /// - `solution`: Proposed solution in the puzzle to check against.
+    ///
+    /// # Returns
+    ///
+    /// Returns true if no two sets are joined outside them, otherwise false, along with the
+    /// number of pairs of sets which are.
+    pub fn are_mutually_disconnected(sets: Vec<&GameSet>, solution: &Solution) -> CheckResult {
+        let outside_solution = Solution::from_lines_unchecked(
+            solution
+                .iter()
+                .filter(|line| {
+                    !sets.iter().any(|set| {
+                        set.nodes.contains(&line.node_a_id())
+                            && set.nodes.contains(&line.node_b_id())
+                    })
+                })
+                .cloned(),
+        );
+
+        // Every network of outside lines joins each pair of sets it reaches
+        let mut joined_pairs: HashSet<(usize, usize)> = HashSet::new();
+        for network in outside_solution.components() {
+            let reached: Vec<usize> = (0..sets.len())
+                .filter(|idx| network.iter().any(|node| sets[*idx].nodes.contains(node)))
+                .collect();
+            for (i, set_i) in reached.iter().enumerate() {
+                for set_j in reached.iter().skip(i + 1) {
+                    joined_pairs.insert((*set_i, *set_j));
                 }
             }
+        }
 
-            if !homomorphism {
-                return false;
-            }
+        CheckResult::new(
+            joined_pairs.is_empty(),
+            Diagnostic::JoinedSets(joined_pairs.len()),
+        )
+    }
+
+    fn have_equal_line_counts(set_solutions: &[Solution]) -> bool {
+        set_solutions
+            .windows(2)
+            .all(|pair| pair[0].len() == pair[1].len())
+    }
+
+    /// A line between two grid positions, ordered so the same line is always equal.
+    type GridLine = (GridPosition, GridPosition);
+
+    /// Returns the lines as grid positions, rotated or reflected into whichever orientation sorts
+    /// first and moved to the origin, so lines forming congruent patterns give the same shape.
+    /// Returns None if there are no lines, which form no pattern to compare.
+    fn get_canonical_shape(solution: &Solution, puzzle: &Puzzle) -> Option<Vec<GridLine>> {
+        if solution.is_empty() {
+            return None;
         }
 
-        return true;
+        // Every orientation of the grid, for both ends of each line
+        let grid = puzzle.topology.grid();
+        let symmetries = |node: GameNodeId| grid.symmetries(node_to_grid_position(&node, puzzle));
+        let line_images: Vec<(Vec<GridPosition>, Vec<GridPosition>)> = solution
+            .iter()
+            .map(|line| (symmetries(line.node_a_id()), symmetries(line.node_b_id())))
+            .collect();
+        let orientation_count = line_images[0].0.len();
+
+        (0..orientation_count)
+            .map(|orientation| {
+                let lines: Vec<GridLine> = line_images
+                    .iter()
+                    .map(|(images_a, images_b)| (images_a[orientation], images_b[orientation]))
+                    .collect();
+                let min_x = lines.iter().map(|(a, b)| a.0.min(b.0)).min().unwrap_or(0);
+                let min_y = lines.iter().map(|(a, b)| a.1.min(b.1)).min().unwrap_or(0);
+
+                let mut shape: Vec<GridLine> = lines
+                    .into_iter()
+                    .map(|(a, b)| {
+                        let a = (a.0 - min_x, a.1 - min_y);
+                        let b = (b.0 - min_x, b.1 - min_y);
+                        (a.min(b), a.max(b))
+                    })
+                    .collect();
+                shape.sort_unstable();
+                shape
+            })
+            .min()
+    }
+
+    /// Combines whether the sets are homomorphic with the lines within each of them.
+    fn get_set_shapes_result(set_solutions: &[Solution], matching: bool) -> CheckResult {
+        CheckResult::new(
+            matching,
+            Diagnostic::SetShapes {
+                line_counts: set_solutions.iter().map(|set_sol| set_sol.len()).collect(),
+                matching,
+            },
+        )
     }
 
-    // TODO this should be run and cached for each set if it has homomorphism when puzzle is loaded.
-    fn generate_all_mappings(
-        set_i: &Vec<GameNodeId>,
-        set_j: &Vec<GameNodeId>,
-    ) -> Vec<HashMap<GameNodeId, GameNodeId>> {
-        let mut all_mappings = Vec::new();
-        let set_j_perms = set_j.iter().cloned().permutations(set_i.len());
-        for perm in set_j_perms {
-            let mut mapping: HashMap<GameNodeId, GameNodeId> = HashMap::new();
-            for (i, j) in set_i.iter().enumerate() {
-                mapping.insert(*j, perm[i]);
+    /// A bounded memo of homomorphism results, keyed by a pair of sets and the lines drawn
+    /// within each of them. Nothing is precomputed about the sets, so it only saves a search
+    /// when the same lines come back within a pair, e.g. as the player redraws a line or the
+    /// solver backtracks. It is emptied once it holds `MAX_CACHED_HOMOMORPHISMS` results.
+    #[derive(Clone, Debug, Default)]
+    pub struct HomomorphismCache {
+        results: HashMap<(u8, Solution, u8, Solution), bool>,
+        searches: usize,
+    }
+
+    impl HomomorphismCache {
+        pub fn new() -> Self {
+            HomomorphismCache::default()
+        }
+
+        /// Returns the number of pairs of sets searched, rather than read from the cache.
+        pub fn searches(&self) -> usize {
+            self.searches
+        }
+
+        /// Checks if all sets with the same rule and class are homomorphic, like
+        /// `are_homomorphic`, reusing results for sets whose lines were checked before.
+        pub fn are_homomorphic(&mut self, sets: &[&GameSet], solution: &Solution) -> CheckResult {
+            let set_solutions: Vec<Solution> =
+                sets.iter().map(|set| solution.filter_to_set(set)).collect();
+
+            let matching = (1..sets.len()).all(|j| {
+                let key = (
+                    sets[j - 1].id,
+                    set_solutions[j - 1].clone(),
+                    sets[j].id,
+                    set_solutions[j].clone(),
+                );
+                if let Some(result) = self.results.get(&key) {
+                    return *result;
+                }
+
+                self.searches += 1;
+                let result = is_homomorphic(sets[j - 1], &key.1, sets[j], &key.3);
+                if self.results.len() >= MAX_CACHED_HOMOMORPHISMS {
+                    self.results.clear();
+                }
+                self.results.insert(key, result);
+                result
+            });
+
+            get_set_shapes_result(&set_solutions, matching)
+        }
+
+        /// Checks if all sets with the same rule and class are isomorphic, like
+        /// `are_isomorphic`, reusing homomorphism results for sets whose lines were checked
+        /// before.
+        pub fn are_isomorphic(&mut self, sets: &[&GameSet], solution: &Solution) -> CheckResult {
+            let set_solutions: Vec<Solution> =
+                sets.iter().map(|set| solution.filter_to_set(set)).collect();
+            if !have_equal_line_counts(&set_solutions) {
+                return get_set_shapes_result(&set_solutions, false);
             }
-            all_mappings.push(mapping);
+            self.are_homomorphic(sets, solution)
         }
-        all_mappings
     }
 
-    fn is_homomorphism(
-        mapping: &HashMap<GameNodeId, GameNodeId>,
-        set_i_sol: &AdjacencyMatrix,
-        set_j_sol: &AdjacencyMatrix,
+    /// Checks if there is a one to one mapping from the nodes of set I to the nodes of set J,
+    /// which maps every line within set I onto a line within set J.
+    fn is_homomorphic(
+        set_i: &GameSet,
+        set_i_sol: &Solution,
+        set_j: &GameSet,
+        set_j_sol: &Solution,
     ) -> bool {
-        for (node, neighbors) in set_i_sol {
-            for &neighbor in neighbors {
-                let mapped_i = mapping.get(node).unwrap();
-                let mapped_j = mapping.get(&neighbor).unwrap();
-                if let Some(set_j_neighbors) = set_j_sol.get(mapped_i) {
-                    if !set_j_neighbors.contains(mapped_j) {
-                        return false;
-                    }
-                } else {
-                    return false;
+        // This should be static for a puzzle, since it's unsolvable if there are sets with different sizes.
+        if set_i.nodes.len() != set_j.nodes.len() {
+            return false;
+        }
+
+        // If either don't have any lines, return false.
+        if set_i_sol.is_empty() || set_j_sol.is_empty() {
+            return false;
+        }
+
+        let graph_i = set_i_sol.adjacency();
+        let graph_j = set_j_sol.adjacency();
+
+        // Map the nodes with the most lines first, as they have the fewest candidates.
+        let mut order = set_i.nodes.clone();
+        order.sort_by_key(|node| Reverse(get_degree(&graph_i, node)));
+
+        // Lines of a node are mapped onto distinct lines, so a node can only be mapped to a node
+        // of at least the same degree. Hence the k-th largest degree in I can't exceed the k-th
+        // largest degree in J.
+        let mut degrees_j: Vec<usize> = set_j
+            .nodes
+            .iter()
+            .map(|node| get_degree(&graph_j, node))
+            .collect();
+        degrees_j.sort_by_key(|degree| Reverse(*degree));
+        if order
+            .iter()
+            .zip(degrees_j.iter())
+            .any(|(node, degree_j)| get_degree(&graph_i, node) > *degree_j)
+        {
+            return false;
+        }
+
+        HomomorphismSearch {
+            order,
+            graph_i: &graph_i,
+            nodes_j: &set_j.nodes,
+            graph_j: &graph_j,
+            mapping: HashMap::new(),
+            mapped_j: HashSet::new(),
+        }
+        .extend(0)
+    }
+
+    fn get_degree(graph: &AdjacencyMatrix, node: &GameNodeId) -> usize {
+        graph.get(node).map_or(0, |neighbors| neighbors.len())
+    }
+
+    /// Backtracking search for a homomorphism, mapping the nodes of set I one at a time.
+    struct HomomorphismSearch<'a> {
+        /// Nodes of set I in the order they are mapped.
+        order: Vec<GameNodeId>,
+        graph_i: &'a AdjacencyMatrix,
+        nodes_j: &'a [GameNodeId],
+        graph_j: &'a AdjacencyMatrix,
+        /// Nodes of set I mapped so far to their node in set J.
+        mapping: HashMap<GameNodeId, GameNodeId>,
+        /// Nodes of set J already mapped to.
+        mapped_j: HashSet<GameNodeId>,
+    }
+
+    impl HomomorphismSearch<'_> {
+        /// Returns true if the mapping can be completed from the node at `idx` of the order.
+        fn extend(&mut self, idx: usize) -> bool {
+            let Some(&node_i) = self.order.get(idx) else {
+                return true;
+            };
+            let degree_i = get_degree(self.graph_i, &node_i);
+
+            for &node_j in self.nodes_j {
+                if self.mapped_j.contains(&node_j) || get_degree(self.graph_j, &node_j) < degree_i {
+                    continue;
+                }
+
+                // Every line to an already mapped node must map onto a line in J.
+                let neighbors_j = self.graph_j.get(&node_j);
+                let consistent = self
+                    .graph_i
+                    .get(&node_i)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|neighbor_i| self.mapping.get(neighbor_i))
+                    .all(|mapped| neighbors_j.is_some_and(|neighbors| neighbors.contains(mapped)));
+                if !consistent {
+                    continue;
                 }
+
+                self.mapping.insert(node_i, node_j);
+                self.mapped_j.insert(node_j);
+                if self.extend(idx + 1) {
+                    return true;
+                }
+                self.mapping.remove(&node_i);
+                self.mapped_j.remove(&node_j);
             }
+
+            false
         }
-        true
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        logic::connected_rule_checks::connected_rule_checks::are_homomorphic,
+        logic::{
+            connected_rule_checks::connected_rule_checks::{
+                are_congruent, are_edge_counts_equal, are_homomorphic, are_isomorphic,
+                are_mutually_disconnected, HomomorphismCache,
+            },
+            diagnostic::diagnostic::Diagnostic,
+        },
         structs::immutable::{
             connected_set_rule::connected_set_rule::{ConnectedSetRule, RuleClass},
             game_line::game_line::GameLine,
             game_set::game_set::GameSet,
+            puzzle::puzzle::Puzzle,
             solution::solution::Solution,
         },
     };
@@ -113,140 +421,318 @@ mod tests {
             id: 0,
             rules: Vec::new(),
             connected_rules: con_rules,
+            meta_rules: Vec::new(),
             bounded: false,
         }
     }
 
     #[test]
     fn test_homomorphic_sets_different_number_nodes_returns_false() {
+        let set_i = get_test_set(3, 0, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+        let set_j = get_test_set(4, 0, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+
+        let solution = Solution::new();
+
+        assert!(!are_homomorphic([&set_i, &set_j].to_vec(), &solution).satisfied);
+    }
+
+    #[test]
+    fn test_homomorphic_sets_empty_solution_returns_false() {
+        let set_i = get_test_set(3, 0, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+        let set_j = get_test_set(3, 0, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+
+        let solution = Solution::new();
+
+        assert!(!are_homomorphic([&set_i, &set_j].to_vec(), &solution).satisfied);
+    }
+
+    #[test]
+    fn test_homomorphic_sets_single_line_per_set_returns_true() {
+        let set_i = get_test_set(3, 0, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+        let set_j = get_test_set(3, 3, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(3, 4).unwrap()]);
+
+        assert!(are_homomorphic([&set_i, &set_j].to_vec(), &solution).satisfied);
+    }
+
+    #[test]
+    fn test_homomorphic_sets_overlapping_sets_horseshoe_returns_true() {
+        let set_i = get_test_set(4, 0, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+        let set_j = get_test_set(4, 2, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+            GameLine::new(3, 4).unwrap(),
+            GameLine::new(4, 5).unwrap(),
+        ]);
+
+        assert!(are_homomorphic([&set_i, &set_j].to_vec(), &solution).satisfied);
+    }
+
+    #[test]
+    fn test_homomorphic_sets_overlapping_sets_one_single_one_two_lines_returns_false() {
+        let set_i = get_test_set(4, 0, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+        let set_j = get_test_set(4, 2, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(4, 5).unwrap(),
+        ]);
+
+        assert!(!are_homomorphic([&set_i, &set_j].to_vec(), &solution).satisfied);
+    }
+
+    fn get_path(nodes: &[u16]) -> Vec<GameLine> {
+        nodes
+            .windows(2)
+            .map(|pair| GameLine::new(pair[0], pair[1]).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_homomorphic_sets_large_paths_returns_true() {
         let set_i = get_test_set(
-            3,
+            16,
             0,
-            [ConnectedSetRule::Homomorphic(RuleClass::Yellow)].to_vec(),
+            [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec(),
         );
         let set_j = get_test_set(
-            4,
-            0,
-            [ConnectedSetRule::Homomorphic(RuleClass::Yellow)].to_vec(),
+            16,
+            16,
+            [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec(),
         );
 
-        let solution = Solution::new();
+        // Path through set I in order, and through set J in a shuffled order
+        let order_j: Vec<u16> = (0..16).map(|idx| 16 + (idx * 5) % 16).collect();
+        let mut lines = get_path(&(0..16).collect::<Vec<u16>>());
+        lines.extend(get_path(&order_j));
+        let solution = Solution::from(lines);
 
-        assert!(!are_homomorphic([&set_i, &set_j].to_vec(), &solution));
+        assert!(are_homomorphic([&set_i, &set_j].to_vec(), &solution).satisfied);
     }
 
     #[test]
-    fn test_homomorphic_sets_empty_solution_returns_false() {
+    fn test_homomorphic_sets_large_path_to_split_paths_returns_false() {
         let set_i = get_test_set(
-            3,
+            16,
             0,
-            [ConnectedSetRule::Homomorphic(RuleClass::Yellow)].to_vec(),
+            [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec(),
         );
         let set_j = get_test_set(
-            3,
-            0,
-            [ConnectedSetRule::Homomorphic(RuleClass::Yellow)].to_vec(),
+            16,
+            16,
+            [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec(),
         );
 
-        let solution = Solution::new();
+        let mut lines = get_path(&(0..16).collect::<Vec<u16>>());
+        lines.extend(get_path(&(16..24).collect::<Vec<u16>>()));
+        lines.extend(get_path(&(24..32).collect::<Vec<u16>>()));
+        let solution = Solution::from(lines);
 
-        assert!(!are_homomorphic([&set_i, &set_j].to_vec(), &solution));
+        assert!(!are_homomorphic([&set_i, &set_j].to_vec(), &solution).satisfied);
     }
 
     #[test]
-    fn test_homomorphic_sets_single_line_per_set_returns_true() {
+    fn test_homomorphism_cache_matches_uncached_result() {
+        let set_i = get_test_set(4, 0, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+        let set_j = get_test_set(4, 2, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+        let horseshoe = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+            GameLine::new(3, 4).unwrap(),
+            GameLine::new(4, 5).unwrap(),
+        ]);
+        let split = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 3).unwrap(),
+            GameLine::new(4, 5).unwrap(),
+        ]);
+
+        let mut cache = HomomorphismCache::new();
+
+        // Check twice each, so the second check of each solution is read from the cache
+        for _ in 0..2 {
+            assert!(
+                cache
+                    .are_homomorphic(&[&set_i, &set_j], &horseshoe)
+                    .satisfied
+            );
+            assert!(!cache.are_homomorphic(&[&set_i, &set_j], &split).satisfied);
+        }
+    }
+
+    #[test]
+    fn test_homomorphism_cache_same_lines_returns_without_searching() {
+        let set_i = get_test_set(2, 0, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+        let set_j = get_test_set(2, 2, [ConnectedSetRule::Homomorphic(RuleClass(0))].to_vec());
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(2, 3).unwrap()]);
+        let mut cache = HomomorphismCache::new();
+
+        assert!(
+            cache
+                .are_homomorphic(&[&set_i, &set_j], &solution)
+                .satisfied
+        );
+        assert_eq!(cache.searches(), 1);
+
+        // Lines outside the sets don't change the lines within them, so the result is reused
+        let extended = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 4).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+        ]);
+        assert!(
+            cache
+                .are_homomorphic(&[&set_i, &set_j], &extended)
+                .satisfied
+        );
+        assert_eq!(cache.searches(), 1);
+    }
+
+    #[test]
+    fn test_isomorphic_sets_same_paths_returns_true() {
+        let set_i = get_test_set(3, 0, [ConnectedSetRule::Isomorphic(RuleClass(0))].to_vec());
+        let set_j = get_test_set(3, 3, [ConnectedSetRule::Isomorphic(RuleClass(0))].to_vec());
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(3, 4).unwrap(),
+            GameLine::new(3, 5).unwrap(),
+        ]);
+
+        assert!(are_isomorphic([&set_i, &set_j].to_vec(), &solution).satisfied);
+    }
+
+    #[test]
+    fn test_isomorphic_sets_path_and_cycle_returns_false() {
+        let set_i = get_test_set(3, 0, [ConnectedSetRule::Isomorphic(RuleClass(0))].to_vec());
+        let set_j = get_test_set(3, 3, [ConnectedSetRule::Isomorphic(RuleClass(0))].to_vec());
+
+        // The path maps into the cycle, but the cycle has a line the path lacks
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(3, 4).unwrap(),
+            GameLine::new(4, 5).unwrap(),
+            GameLine::new(3, 5).unwrap(),
+        ]);
+
+        assert!(are_homomorphic([&set_i, &set_j].to_vec(), &solution).satisfied);
+        assert!(!are_isomorphic([&set_i, &set_j].to_vec(), &solution).satisfied);
+    }
+
+    #[test]
+    fn test_congruent_sets_rotated_corner_returns_true() {
+        let puzzle = Puzzle::new(4, 4);
+        let mut set_i = get_test_set(0, 0, [ConnectedSetRule::Congruent(RuleClass(0))].to_vec());
+        set_i.nodes = [0, 1, 4].to_vec();
+        let mut set_j = get_test_set(0, 0, [ConnectedSetRule::Congruent(RuleClass(0))].to_vec());
+        set_j.nodes = [10, 14, 15].to_vec();
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(0, 4).unwrap(),
+            GameLine::new(10, 14).unwrap(),
+            GameLine::new(14, 15).unwrap(),
+        ]);
+
+        assert!(are_congruent([&set_i, &set_j].to_vec(), &solution, &puzzle).satisfied);
+    }
+
+    #[test]
+    fn test_congruent_sets_corner_and_straight_line_returns_false() {
+        let puzzle = Puzzle::new(4, 4);
+        let mut set_i = get_test_set(0, 0, [ConnectedSetRule::Congruent(RuleClass(0))].to_vec());
+        set_i.nodes = [0, 1, 4].to_vec();
+        let mut set_j = get_test_set(0, 0, [ConnectedSetRule::Congruent(RuleClass(0))].to_vec());
+        set_j.nodes = [2, 6, 10].to_vec();
+
+        // Both are paths of two lines, so they are homomorphic but not congruent
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(0, 4).unwrap(),
+            GameLine::new(2, 6).unwrap(),
+            GameLine::new(6, 10).unwrap(),
+        ]);
+
+        assert!(!are_congruent([&set_i, &set_j].to_vec(), &solution, &puzzle).satisfied);
+    }
+
+    #[test]
+    fn test_edge_counts_equal_different_counts_reports_line_counts() {
         let set_i = get_test_set(
             3,
             0,
-            [ConnectedSetRule::Homomorphic(RuleClass::Yellow)].to_vec(),
+            [ConnectedSetRule::EdgeCountEqual(RuleClass(0))].to_vec(),
         );
         let set_j = get_test_set(
             3,
             3,
-            [ConnectedSetRule::Homomorphic(RuleClass::Yellow)].to_vec(),
+            [ConnectedSetRule::EdgeCountEqual(RuleClass(0))].to_vec(),
         );
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 3,
-                node_b_id: 4,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(3, 4).unwrap(),
         ]);
 
-        assert!(are_homomorphic([&set_i, &set_j].to_vec(), &solution));
+        let result = are_edge_counts_equal([&set_i, &set_j].to_vec(), &solution);
+        assert!(!result.satisfied);
+        assert_eq!(result.diagnostic, Diagnostic::LineCounts([2, 1].to_vec()));
     }
 
     #[test]
-    fn test_homomorphic_sets_overlapping_sets_horseshoe_returns_true() {
+    fn test_mutually_disconnected_sets_joined_through_outside_node_returns_false() {
         let set_i = get_test_set(
-            4,
+            2,
             0,
-            [ConnectedSetRule::Homomorphic(RuleClass::Yellow)].to_vec(),
+            [ConnectedSetRule::MutuallyDisconnected(RuleClass(0))].to_vec(),
         );
         let set_j = get_test_set(
-            4,
             2,
-            [ConnectedSetRule::Homomorphic(RuleClass::Yellow)].to_vec(),
+            3,
+            [ConnectedSetRule::MutuallyDisconnected(RuleClass(0))].to_vec(),
         );
 
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 2,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 3,
-                node_b_id: 4,
-            },
-            GameLine {
-                node_a_id: 4,
-                node_b_id: 5,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(2, 3).unwrap(),
         ]);
 
-        assert!(are_homomorphic([&set_i, &set_j].to_vec(), &solution));
+        let result = are_mutually_disconnected([&set_i, &set_j].to_vec(), &solution);
+        assert!(!result.satisfied);
+        assert_eq!(result.diagnostic, Diagnostic::JoinedSets(1));
     }
 
     #[test]
-    fn test_homomorphic_sets_overlapping_sets_one_single_one_two_lines_returns_false() {
+    fn test_mutually_disconnected_overlapping_sets_joined_within_returns_true() {
         let set_i = get_test_set(
-            4,
+            3,
             0,
-            [ConnectedSetRule::Homomorphic(RuleClass::Yellow)].to_vec(),
+            [ConnectedSetRule::MutuallyDisconnected(RuleClass(0))].to_vec(),
         );
         let set_j = get_test_set(
-            4,
+            3,
             2,
-            [ConnectedSetRule::Homomorphic(RuleClass::Yellow)].to_vec(),
+            [ConnectedSetRule::MutuallyDisconnected(RuleClass(0))].to_vec(),
         );
 
+        // The sets share node 2, and every line is within one of them
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 1,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 3,
-            },
-            GameLine {
-                node_a_id: 4,
-                node_b_id: 5,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+            GameLine::new(3, 4).unwrap(),
         ]);
 
-        assert!(!are_homomorphic([&set_i, &set_j].to_vec(), &solution));
+        assert!(are_mutually_disconnected([&set_i, &set_j].to_vec(), &solution).satisfied);
     }
 }
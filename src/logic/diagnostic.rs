@@ -0,0 +1,168 @@
+pub mod diagnostic {
+    use std::fmt;
+
+    /// Explains the result of checking a condition or rule against the lines drawn, so players
+    /// can see why it is (un)satisfied.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum Diagnostic {
+        /// No lines are drawn where the check looks for them.
+        NoLines,
+        /// Depths of the ends of every branch reached from the node.
+        LeafDepths(Vec<u16>),
+        /// A branch from the node loops back on itself.
+        BranchCycle,
+        /// Number of lines connected to the node.
+        Degree(usize),
+        /// Whether the node lies on a cycle.
+        OnCycle(bool),
+        /// Number of networks the neighbors of the node are left in once it is removed.
+        NetworksWithout(usize),
+        /// Whether the two lines of the node run in opposite directions.
+        Straight(bool),
+        /// Number of lines joining two nodes within the set.
+        InnerLines(usize),
+        /// Number of separate groups the lines within the set join its nodes into.
+        InnerGroups(usize),
+        /// Whether the lines within the set form a cycle.
+        InnerCycle(bool),
+        /// Number of lines joining a node within the set to a node outside it.
+        OutwardConnections(usize),
+        /// Degree of every node with the condition.
+        Degrees(Vec<usize>),
+        /// Shortest distance between every pair of nodes with the condition, None if no path
+        /// joins them.
+        Distances(Vec<Option<usize>>),
+        /// Whether a single path passes through every node with the condition.
+        SinglePath(bool),
+        /// Number of lines within every set with the rule, and whether they all have the same
+        /// shape.
+        SetShapes {
+            line_counts: Vec<usize>,
+            matching: bool,
+        },
+        /// Number of lines within every set with the rule.
+        LineCounts(Vec<usize>),
+        /// Number of pairs of sets with the rule joined by lines outside them.
+        JoinedSets(usize),
+        /// Number of set rules holding, of the rules the meta rule combines.
+        SatisfiedRules(usize),
+        /// The rule only changes how other conditions are checked.
+        NoRequirement,
+    }
+
+    impl fmt::Display for Diagnostic {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Diagnostic::NoLines => write!(f, "No lines are drawn here"),
+                Diagnostic::LeafDepths(depths) => {
+                    write!(f, "Branches end at depths {}", join(depths.iter()))
+                }
+                Diagnostic::BranchCycle => write!(f, "A branch loops back on itself"),
+                Diagnostic::Degree(degree) => write!(f, "Has {} connected lines", degree),
+                Diagnostic::OnCycle(true) => write!(f, "Lies on a cycle"),
+                Diagnostic::OnCycle(false) => write!(f, "Doesn't lie on any cycle"),
+                Diagnostic::NetworksWithout(count) => {
+                    write!(f, "Removing it leaves {} networks", count)
+                }
+                Diagnostic::Straight(true) => write!(f, "Lines pass straight through"),
+                Diagnostic::Straight(false) => write!(f, "Lines turn at the node"),
+                Diagnostic::InnerLines(count) => {
+                    write!(f, "Found {} lines within the set", count)
+                }
+                Diagnostic::InnerGroups(count) => {
+                    write!(f, "Nodes of the set form {} separate groups", count)
+                }
+                Diagnostic::InnerCycle(true) => write!(f, "Lines within the set form a cycle"),
+                Diagnostic::InnerCycle(false) => write!(f, "No cycle within the set"),
+                Diagnostic::OutwardConnections(count) => {
+                    write!(f, "Found {} outward connections", count)
+                }
+                Diagnostic::Degrees(degrees) => {
+                    write!(f, "Connected lines per node: {}", join(degrees.iter()))
+                }
+                Diagnostic::Distances(distances) => write!(
+                    f,
+                    "Distances between nodes: {}",
+                    join(distances.iter().map(|distance| match distance {
+                        Some(distance) => distance.to_string(),
+                        None => "unreachable".to_string(),
+                    }))
+                ),
+                Diagnostic::SinglePath(true) => write!(f, "A single path passes through the nodes"),
+                Diagnostic::SinglePath(false) => {
+                    write!(f, "No single path passes through the nodes")
+                }
+                Diagnostic::SetShapes {
+                    line_counts,
+                    matching,
+                } => write!(
+                    f,
+                    "Lines per set: {}, {}",
+                    join(line_counts.iter()),
+                    if *matching {
+                        "all the same shape"
+                    } else {
+                        "not the same shape"
+                    }
+                ),
+                Diagnostic::LineCounts(line_counts) => {
+                    write!(f, "Lines per set: {}", join(line_counts.iter()))
+                }
+                Diagnostic::JoinedSets(count) => {
+                    write!(f, "Found {} pairs of sets joined outside them", count)
+                }
+                Diagnostic::SatisfiedRules(count) => {
+                    write!(f, "{} set rules are satisfied", count)
+                }
+                Diagnostic::NoRequirement => {
+                    write!(f, "Only lines within the set count for its conditions")
+                }
+            }
+        }
+    }
+
+    /// Joins the values into a comma separated list.
+    fn join<T: ToString>(values: impl Iterator<Item = T>) -> String {
+        values
+            .map(|value| value.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// The result of a single condition or rule check, along with why it is (un)satisfied.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct CheckResult {
+        pub satisfied: bool,
+        pub diagnostic: Diagnostic,
+    }
+
+    impl CheckResult {
+        pub fn new(satisfied: bool, diagnostic: Diagnostic) -> Self {
+            CheckResult {
+                satisfied,
+                diagnostic,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::diagnostic::diagnostic::Diagnostic;
+
+    #[test]
+    fn test_diagnostic_leaf_depths_lists_depths() {
+        assert_eq!(
+            Diagnostic::LeafDepths([1, 2].to_vec()).to_string(),
+            "Branches end at depths 1, 2"
+        );
+    }
+
+    #[test]
+    fn test_diagnostic_distances_unreachable_pair_is_named() {
+        assert_eq!(
+            Diagnostic::Distances([Some(2), None].to_vec()).to_string(),
+            "Distances between nodes: 2, unreachable"
+        );
+    }
+}
@@ -1,8 +1,11 @@
 pub mod condition_checks;
 pub mod connected_condition_checks;
 pub mod connected_rule_checks;
+pub mod diagnostic;
 pub mod puzzle;
+pub mod puzzle_code;
 pub mod puzzle_manager;
 pub mod rule_checks;
 pub mod save_data_manager;
+pub mod topology;
 pub mod util;
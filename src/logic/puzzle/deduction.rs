@@ -0,0 +1,1008 @@
+pub mod deduction {
+    use std::{collections::HashMap, fmt};
+
+    use uuid::Uuid;
+
+    use crate::{
+        logic::{
+            puzzle::{
+                solution_check::solution_check::is_solution_valid,
+                solver::solver::get_candidate_lines,
+            },
+            rule_checks::rule_checks::{is_acyclic, is_connected},
+            util::{get_connected_condition_groups, get_crossing_line, get_opposite_node_pairs},
+        },
+        structs::immutable::{
+            connected_node_condition::connected_node_condition::ConnectedNodeCondition,
+            game_line::game_line::GameLine, game_node::game_node::GameNodeId,
+            node_condition::node_condition::NodeCondition, puzzle::puzzle::Puzzle,
+            set_rule::set_rule::SetRule, solution::solution::Solution,
+        },
+    };
+
+    /// Extra score of each deduction per assumption it is nested in.
+    const DEPTH_WEIGHT: u32 = 5;
+    /// Extra score when the puzzle can't be solved without guessing.
+    const GUESS_PENALTY: u32 = 50;
+
+    /// Why a line was drawn or left out.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum DeductionReason {
+        /// A node only has one line left to connect it to the network.
+        NodeMustConnect(GameNodeId),
+        /// A Leaf node already has its line, so it can't take more.
+        LeafFull(GameNodeId),
+        /// An Internal or Cycle node needs at least two lines, and only two are left.
+        NeedsTwoLines(GameNodeId),
+        /// A node already has the exact number of lines it needs.
+        DegreeFull(GameNodeId),
+        /// A node needs every one of its remaining lines to reach its degree.
+        DegreeLastLines(GameNodeId),
+        /// A Straight node must continue its line in the opposite direction.
+        StraightOpposite(GameNodeId),
+        /// The line crosses a drawn line.
+        CrossesLine(GameLine),
+        /// A Disconnected set forbids lines between its nodes.
+        DisconnectedSet(u8),
+        /// A set already has the exact number of inner lines it needs.
+        EdgeCountFull(u8),
+        /// A set needs every one of its remaining inner lines to reach its line count.
+        EdgeCountLastLines(u8),
+        /// A Closed set forbids lines leaving it.
+        ClosedSet(u8),
+        /// A Leaf set already has its outward connection.
+        LeafSetFull(u8),
+        /// A Leaf set only has one outward connection left.
+        LeafSetLastLine(u8),
+        /// A node must reach the degree of another node with the same DegreeEqual condition.
+        DegreeEqualRaise(GameNodeId),
+        /// A node can't exceed the degree another node with the same DegreeEqual condition can
+        /// reach.
+        DegreeEqualCap(GameNodeId),
+        /// Assuming the opposite state of the line leads to a contradiction.
+        Contradiction,
+        /// No deduction applies, so the state of the line is guessed.
+        Guess,
+    }
+
+    impl fmt::Display for DeductionReason {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                DeductionReason::NodeMustConnect(node_id) => {
+                    write!(f, "node {} has only one line left to connect it", node_id)
+                }
+                DeductionReason::LeafFull(node_id) => {
+                    write!(f, "leaf node {} with one line cannot take more", node_id)
+                }
+                DeductionReason::NeedsTwoLines(node_id) => {
+                    write!(f, "node {} needs both of its last two lines", node_id)
+                }
+                DeductionReason::DegreeFull(node_id) => {
+                    write!(f, "node {} already has all of its lines", node_id)
+                }
+                DeductionReason::DegreeLastLines(node_id) => {
+                    write!(f, "node {} needs all of its remaining lines", node_id)
+                }
+                DeductionReason::StraightOpposite(node_id) => {
+                    write!(f, "straight node {} must continue its line", node_id)
+                }
+                DeductionReason::CrossesLine(line) => write!(
+                    f,
+                    "it crosses the line {}-{}",
+                    line.node_a_id(),
+                    line.node_b_id()
+                ),
+                DeductionReason::DisconnectedSet(set_id) => {
+                    write!(f, "disconnected set {} forbids inner lines", set_id)
+                }
+                DeductionReason::EdgeCountFull(set_id) => {
+                    write!(f, "set {} already has all of its lines", set_id)
+                }
+                DeductionReason::EdgeCountLastLines(set_id) => {
+                    write!(f, "set {} needs all of its remaining lines", set_id)
+                }
+                DeductionReason::ClosedSet(set_id) => {
+                    write!(f, "closed set {} forbids outward lines", set_id)
+                }
+                DeductionReason::LeafSetFull(set_id) => {
+                    write!(f, "leaf set {} already has its outward line", set_id)
+                }
+                DeductionReason::LeafSetLastLine(set_id) => {
+                    write!(f, "leaf set {} has only one outward line left", set_id)
+                }
+                DeductionReason::DegreeEqualRaise(node_id) => write!(
+                    f,
+                    "node {} must reach the degree of its connected nodes",
+                    node_id
+                ),
+                DeductionReason::DegreeEqualCap(node_id) => write!(
+                    f,
+                    "node {} can't exceed the degree its connected nodes can reach",
+                    node_id
+                ),
+                DeductionReason::Contradiction => {
+                    write!(f, "the opposite leads to a contradiction")
+                }
+                DeductionReason::Guess => write!(f, "guessed"),
+            }
+        }
+    }
+
+    /// A single line decided while solving.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct DeductionStep {
+        pub line: GameLine,
+        pub drawn: bool,
+        pub reason: DeductionReason,
+        /// Number of assumptions the deduction needed, 0 if it follows from the decided lines.
+        pub depth: usize,
+    }
+
+    impl fmt::Display for DeductionStep {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "{} {}-{}: {}",
+                if self.drawn { "Draw" } else { "Remove" },
+                self.line.node_a_id(),
+                self.line.node_b_id(),
+                self.reason
+            )
+        }
+    }
+
+    /// How hard a puzzle is to solve by deduction.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Difficulty {
+        pub deductions: usize,
+        /// Most assumptions any single deduction needed.
+        pub max_depth: usize,
+        pub guessed: bool,
+        /// Combined score, higher being harder, to order puzzles by.
+        pub score: u32,
+    }
+
+    /// The steps taken to solve a puzzle by deduction, and the solution they lead to.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct DeductionTrace {
+        pub steps: Vec<DeductionStep>,
+        /// The solution found, or None if the puzzle is unsolvable.
+        pub solution: Option<Solution>,
+    }
+
+    impl DeductionTrace {
+        pub fn difficulty(&self) -> Difficulty {
+            let guessed = self
+                .steps
+                .iter()
+                .any(|step| step.reason == DeductionReason::Guess);
+            let score = self
+                .steps
+                .iter()
+                .map(|step| 1 + DEPTH_WEIGHT * step.depth as u32)
+                .sum::<u32>()
+                + if guessed { GUESS_PENALTY } else { 0 };
+            Difficulty {
+                deductions: self.steps.len(),
+                max_depth: self.steps.iter().map(|step| step.depth).max().unwrap_or(0),
+                guessed,
+                score,
+            }
+        }
+    }
+
+    /// Solves a puzzle like a player would, repeatedly applying deductions to decide lines.
+    ///
+    /// When no deduction applies, each undecided line is assumed drawn or left out to see if
+    /// that leads to a contradiction. If that doesn't decide any line either, a line is guessed.
+    ///
+    /// # Parameters
+    ///
+    /// - `puzzle`: The puzzle to solve.
+    ///
+    /// # Returns
+    ///
+    /// Returns every step taken towards the solution, along with the solution if there is one.
+    pub fn solve_logically(puzzle: &Puzzle) -> DeductionTrace {
+        let deducer = Deducer::new(puzzle);
+        let mut steps: Vec<DeductionStep> = Vec::new();
+        let solution = deducer
+            .solve_from(deducer.get_fixed_states(), &mut steps)
+            .ok()
+            .map(|states| deducer.get_drawn(&states));
+        DeductionTrace { steps, solution }
+    }
+
+    /// Orders puzzles from easiest to hardest by the difficulty of solving them by deduction,
+    /// e.g. to lay out the campaign grid. Puzzles of equal score keep their order.
+    ///
+    /// # Parameters
+    ///
+    /// - `puzzles`: The puzzles to order.
+    ///
+    /// # Returns
+    ///
+    /// Returns the uuid and difficulty of every puzzle, easiest first.
+    pub fn order_by_difficulty(puzzles: &[Puzzle]) -> Vec<(Uuid, Difficulty)> {
+        let mut difficulties: Vec<(Uuid, Difficulty)> = puzzles
+            .iter()
+            .map(|puzzle| (puzzle.uuid, solve_logically(puzzle).difficulty()))
+            .collect();
+        difficulties.sort_by_key(|(_, difficulty)| difficulty.score);
+        difficulties
+    }
+
+    /// The decided lines can't be part of any solution.
+    struct Contradiction;
+
+    /// A line which can be decided, and why.
+    struct Deduction {
+        line_idx: usize,
+        drawn: bool,
+        reason: DeductionReason,
+    }
+
+    /// State of every candidate line, None while undecided.
+    type LineStates = Vec<Option<bool>>;
+
+    struct Deducer<'a> {
+        puzzle: &'a Puzzle,
+        lines: Vec<GameLine>,
+        line_idxs: HashMap<GameLine, usize>,
+        /// Indices of the lines touching each node.
+        node_lines: HashMap<GameNodeId, Vec<usize>>,
+    }
+
+    impl<'a> Deducer<'a> {
+        fn new(puzzle: &'a Puzzle) -> Self {
+            let lines = get_candidate_lines(puzzle);
+            let mut node_lines: HashMap<GameNodeId, Vec<usize>> = HashMap::new();
+            for (idx, line) in lines.iter().enumerate() {
+                node_lines.entry(line.node_a_id()).or_default().push(idx);
+                node_lines.entry(line.node_b_id()).or_default().push(idx);
+            }
+            Deducer {
+                puzzle,
+                line_idxs: lines
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, line)| (*line, idx))
+                    .collect(),
+                lines,
+                node_lines,
+            }
+        }
+
+        /// Returns the starting state of every line, where only the fixed lines are decided.
+        /// They are given by the puzzle, so aren't steps of the solution.
+        fn get_fixed_states(&self) -> LineStates {
+            self.lines
+                .iter()
+                .map(|line| self.puzzle.fixed_lines.contains(line).then_some(true))
+                .collect()
+        }
+
+        /// Returns the lines which may still be drawn, i.e. those which aren't left out.
+        fn get_possible(&self, states: &LineStates) -> Solution {
+            Solution::from_lines_unchecked(
+                self.lines
+                    .iter()
+                    .zip(states.iter())
+                    .filter(|(_, state)| **state != Some(false))
+                    .map(|(line, _)| *line),
+            )
+        }
+
+        fn get_drawn(&self, states: &LineStates) -> Solution {
+            Solution::from_lines_unchecked(
+                self.lines
+                    .iter()
+                    .zip(states.iter())
+                    .filter(|(_, state)| **state == Some(true))
+                    .map(|(line, _)| *line),
+            )
+        }
+
+        /// Solves the puzzle from the decided lines, recording the steps of the solution found.
+        fn solve_from(
+            &self,
+            mut states: LineStates,
+            steps: &mut Vec<DeductionStep>,
+        ) -> Result<LineStates, Contradiction> {
+            loop {
+                self.propagate(&mut states, steps, 0)?;
+                let Some(guess_idx) = states.iter().position(Option::is_none) else {
+                    return Ok(states);
+                };
+
+                if let Some((line_idx, drawn)) = self.find_contradiction(&states) {
+                    states[line_idx] = Some(drawn);
+                    steps.push(self.get_step(line_idx, drawn, DeductionReason::Contradiction, 1));
+                    continue;
+                }
+
+                // Only the steps of the guess that leads to a solution are kept
+                for drawn in [true, false] {
+                    let mut branch_states = states.clone();
+                    branch_states[guess_idx] = Some(drawn);
+                    let mut branch_steps = steps.clone();
+                    branch_steps.push(self.get_step(guess_idx, drawn, DeductionReason::Guess, 0));
+                    if let Ok(solved) = self.solve_from(branch_states, &mut branch_steps) {
+                        *steps = branch_steps;
+                        return Ok(solved);
+                    }
+                }
+                return Err(Contradiction);
+            }
+        }
+
+        fn get_step(
+            &self,
+            line_idx: usize,
+            drawn: bool,
+            reason: DeductionReason,
+            depth: usize,
+        ) -> DeductionStep {
+            DeductionStep {
+                line: self.lines[line_idx],
+                drawn,
+                reason,
+                depth,
+            }
+        }
+
+        /// Applies deductions until none apply, then verifies the lines once all are decided.
+        fn propagate(
+            &self,
+            states: &mut LineStates,
+            steps: &mut Vec<DeductionStep>,
+            depth: usize,
+        ) -> Result<(), Contradiction> {
+            while let Some(deduction) = self.find_deduction(states)? {
+                states[deduction.line_idx] = Some(deduction.drawn);
+                steps.push(self.get_step(
+                    deduction.line_idx,
+                    deduction.drawn,
+                    deduction.reason,
+                    depth,
+                ));
+            }
+
+            if states.iter().all(Option::is_some)
+                && !is_solution_valid(self.puzzle, &self.get_drawn(states))
+            {
+                return Err(Contradiction);
+            }
+            Ok(())
+        }
+
+        /// Returns an undecided line and the state it must have, because assuming the opposite
+        /// state leads to a contradiction.
+        fn find_contradiction(&self, states: &LineStates) -> Option<(usize, bool)> {
+            (0..states.len())
+                .filter(|idx| states[*idx].is_none())
+                .find_map(|idx| {
+                    [true, false].into_iter().find_map(|assumed| {
+                        let mut trial_states = states.clone();
+                        trial_states[idx] = Some(assumed);
+                        self.propagate(&mut trial_states, &mut Vec::new(), 1)
+                            .is_err()
+                            .then_some((idx, !assumed))
+                    })
+                })
+        }
+
+        /// Returns the first line which can be decided from the decided lines, or a
+        /// contradiction if the decided lines break a condition or rule.
+        fn find_deduction(&self, states: &LineStates) -> Result<Option<Deduction>, Contradiction> {
+            let deduction_finders = [
+                Self::find_node_deduction,
+                Self::find_crossing_deduction,
+                Self::find_set_deduction,
+                Self::find_degree_equal_deduction,
+            ];
+            for find in deduction_finders {
+                if let Some(deduction) = find(self, states)? {
+                    return Ok(Some(deduction));
+                }
+            }
+
+            if !self.is_connectable(states) {
+                return Err(Contradiction);
+            }
+            Ok(None)
+        }
+
+        /// Returns the number of drawn lines and the undecided lines of the given lines.
+        fn count_lines(&self, states: &LineStates, line_idxs: &[usize]) -> (usize, Vec<usize>) {
+            let drawn = line_idxs
+                .iter()
+                .filter(|idx| states[**idx] == Some(true))
+                .count();
+            let undecided = line_idxs
+                .iter()
+                .filter(|idx| states[**idx].is_none())
+                .cloned()
+                .collect();
+            (drawn, undecided)
+        }
+
+        fn get_node_lines(&self, node_id: &GameNodeId) -> &[usize] {
+            self.node_lines.get(node_id).map_or(&[], Vec::as_slice)
+        }
+
+        /// Deductions from the lines around each node, which must join the network and meet its
+        /// conditions. Conditions of nodes in scoped sets only count some lines, so they are
+        /// left to the final verification.
+        fn find_node_deduction(
+            &self,
+            states: &LineStates,
+        ) -> Result<Option<Deduction>, Contradiction> {
+            let must_connect = self.puzzle.nodes.len() > 1;
+            for node in self.puzzle.nodes.iter() {
+                let (drawn, undecided) = self.count_lines(states, self.get_node_lines(&node.id));
+                let possible = drawn + undecided.len();
+                if must_connect && possible == 0 {
+                    return Err(Contradiction);
+                }
+                if must_connect && drawn == 0 && undecided.len() == 1 {
+                    return Ok(Some(Deduction {
+                        line_idx: undecided[0],
+                        drawn: true,
+                        reason: DeductionReason::NodeMustConnect(node.id),
+                    }));
+                }
+
+                let is_scoped = self
+                    .puzzle
+                    .sets
+                    .iter()
+                    .any(|set| set.is_scoped() && set.nodes.contains(&node.id));
+                if is_scoped {
+                    continue;
+                }
+                for condition in node.conditions.iter() {
+                    match condition {
+                        NodeCondition::Leaf => {
+                            if drawn > 1 {
+                                return Err(Contradiction);
+                            }
+                            if drawn == 1 && !undecided.is_empty() {
+                                return Ok(Some(Deduction {
+                                    line_idx: undecided[0],
+                                    drawn: false,
+                                    reason: DeductionReason::LeafFull(node.id),
+                                }));
+                            }
+                        }
+                        NodeCondition::Internal
+                        | NodeCondition::Cycle
+                        | NodeCondition::CutVertex => {
+                            if possible < 2 {
+                                return Err(Contradiction);
+                            }
+                            if possible == 2 && !undecided.is_empty() {
+                                return Ok(Some(Deduction {
+                                    line_idx: undecided[0],
+                                    drawn: true,
+                                    reason: DeductionReason::NeedsTwoLines(node.id),
+                                }));
+                            }
+                        }
+                        NodeCondition::Degree(degree) => {
+                            let deduction = Self::find_exact_degree_deduction(
+                                node.id,
+                                drawn,
+                                &undecided,
+                                *degree as usize,
+                            )?;
+                            if deduction.is_some() {
+                                return Ok(deduction);
+                            }
+                        }
+                        NodeCondition::Straight => {
+                            let deduction =
+                                Self::find_exact_degree_deduction(node.id, drawn, &undecided, 2)?
+                                    .or(self.find_straight_deduction(states, node.id)?);
+                            if deduction.is_some() {
+                                return Ok(deduction);
+                            }
+                        }
+                        NodeCondition::BranchEqual => {}
+                    }
+                }
+            }
+            Ok(None)
+        }
+
+        /// A node needing an exact degree takes no more lines once it has them, and takes all
+        /// of its last lines if it needs every one.
+        fn find_exact_degree_deduction(
+            node_id: GameNodeId,
+            drawn: usize,
+            undecided: &[usize],
+            degree: usize,
+        ) -> Result<Option<Deduction>, Contradiction> {
+            if drawn > degree || drawn + undecided.len() < degree {
+                return Err(Contradiction);
+            }
+            let Some(line_idx) = undecided.first() else {
+                return Ok(None);
+            };
+            if drawn == degree {
+                return Ok(Some(Deduction {
+                    line_idx: *line_idx,
+                    drawn: false,
+                    reason: DeductionReason::DegreeFull(node_id),
+                }));
+            }
+            if drawn + undecided.len() == degree {
+                return Ok(Some(Deduction {
+                    line_idx: *line_idx,
+                    drawn: true,
+                    reason: DeductionReason::DegreeLastLines(node_id),
+                }));
+            }
+            Ok(None)
+        }
+
+        /// A Straight node with one drawn line must continue it in the opposite direction.
+        fn find_straight_deduction(
+            &self,
+            states: &LineStates,
+            node_id: GameNodeId,
+        ) -> Result<Option<Deduction>, Contradiction> {
+            let drawn_neighbors: Vec<GameNodeId> = self
+                .get_node_lines(&node_id)
+                .iter()
+                .filter(|idx| states[**idx] == Some(true))
+                .map(|idx| {
+                    let line = self.lines[*idx];
+                    if line.node_a_id() == node_id {
+                        line.node_b_id()
+                    } else {
+                        line.node_a_id()
+                    }
+                })
+                .collect();
+            let [neighbor] = drawn_neighbors.as_slice() else {
+                return Ok(None);
+            };
+
+            let opposite_idx = get_opposite_node_pairs(&node_id, self.puzzle)
+                .into_iter()
+                .find_map(|(node_a, node_b)| match *neighbor {
+                    n if n == node_a => Some(node_b),
+                    n if n == node_b => Some(node_a),
+                    _ => None,
+                })
+                .and_then(|opposite| GameLine::new(node_id, opposite).ok())
+                .and_then(|line| self.line_idxs.get(&line));
+            match opposite_idx.map(|idx| (*idx, states[*idx])) {
+                Some((line_idx, None)) => Ok(Some(Deduction {
+                    line_idx,
+                    drawn: true,
+                    reason: DeductionReason::StraightOpposite(node_id),
+                })),
+                Some((_, Some(true))) => Ok(None),
+                _ => Err(Contradiction),
+            }
+        }
+
+        /// Lines crossing a drawn line can't be drawn, unless the puzzle allows it.
+        fn find_crossing_deduction(
+            &self,
+            states: &LineStates,
+        ) -> Result<Option<Deduction>, Contradiction> {
+            if self.puzzle.allow_crossings {
+                return Ok(None);
+            }
+            for (idx, line) in self.lines.iter().enumerate() {
+                if states[idx] != Some(true) {
+                    continue;
+                }
+                let Some(crossing_idx) = get_crossing_line(line, self.puzzle)
+                    .and_then(|crossing| self.line_idxs.get(&crossing))
+                else {
+                    continue;
+                };
+                match states[*crossing_idx] {
+                    Some(true) => return Err(Contradiction),
+                    Some(false) => {}
+                    None => {
+                        return Ok(Some(Deduction {
+                            line_idx: *crossing_idx,
+                            drawn: false,
+                            reason: DeductionReason::CrossesLine(*line),
+                        }))
+                    }
+                }
+            }
+            Ok(None)
+        }
+
+        /// Deductions from set rules. Sets with meta rules don't need every rule to hold, so
+        /// they are left to the final verification.
+        fn find_set_deduction(
+            &self,
+            states: &LineStates,
+        ) -> Result<Option<Deduction>, Contradiction> {
+            for set in self.puzzle.sets.iter() {
+                if !set.meta_rules.is_empty() {
+                    continue;
+                }
+                let inner_lines: Vec<usize> = (0..self.lines.len())
+                    .filter(|idx| {
+                        set.nodes.contains(&self.lines[*idx].node_a_id())
+                            && set.nodes.contains(&self.lines[*idx].node_b_id())
+                    })
+                    .collect();
+                let outward_lines: Vec<usize> = (0..self.lines.len())
+                    .filter(|idx| {
+                        set.nodes.contains(&self.lines[*idx].node_a_id())
+                            != set.nodes.contains(&self.lines[*idx].node_b_id())
+                    })
+                    .collect();
+
+                for rule in set.rules.iter() {
+                    match rule {
+                        SetRule::Disconnected => {
+                            let (drawn, undecided) = self.count_lines(states, &inner_lines);
+                            if drawn > 0 {
+                                return Err(Contradiction);
+                            }
+                            if let Some(line_idx) = undecided.first() {
+                                return Ok(Some(Deduction {
+                                    line_idx: *line_idx,
+                                    drawn: false,
+                                    reason: DeductionReason::DisconnectedSet(set.id),
+                                }));
+                            }
+                        }
+                        SetRule::Leaf => {
+                            let (drawn, undecided) = self.count_lines(states, &outward_lines);
+                            if drawn > 1 || drawn + undecided.len() == 0 {
+                                return Err(Contradiction);
+                            }
+                            if drawn == 1 && !undecided.is_empty() {
+                                return Ok(Some(Deduction {
+                                    line_idx: undecided[0],
+                                    drawn: false,
+                                    reason: DeductionReason::LeafSetFull(set.id),
+                                }));
+                            }
+                            if drawn == 0 && undecided.len() == 1 {
+                                return Ok(Some(Deduction {
+                                    line_idx: undecided[0],
+                                    drawn: true,
+                                    reason: DeductionReason::LeafSetLastLine(set.id),
+                                }));
+                            }
+                        }
+                        SetRule::EdgeCount(count) => {
+                            let (drawn, undecided) = self.count_lines(states, &inner_lines);
+                            let count = *count as usize;
+                            if drawn > count || drawn + undecided.len() < count {
+                                return Err(Contradiction);
+                            }
+                            if drawn == count && !undecided.is_empty() {
+                                return Ok(Some(Deduction {
+                                    line_idx: undecided[0],
+                                    drawn: false,
+                                    reason: DeductionReason::EdgeCountFull(set.id),
+                                }));
+                            }
+                            if drawn + undecided.len() == count && !undecided.is_empty() {
+                                return Ok(Some(Deduction {
+                                    line_idx: undecided[0],
+                                    drawn: true,
+                                    reason: DeductionReason::EdgeCountLastLines(set.id),
+                                }));
+                            }
+                        }
+                        SetRule::Closed => {
+                            let (drawn, undecided) = self.count_lines(states, &outward_lines);
+                            if drawn > 0 {
+                                return Err(Contradiction);
+                            }
+                            if let Some(line_idx) = undecided.first() {
+                                return Ok(Some(Deduction {
+                                    line_idx: *line_idx,
+                                    drawn: false,
+                                    reason: DeductionReason::ClosedSet(set.id),
+                                }));
+                            }
+                        }
+                        // Only ruled out once the lines can no longer join the set, or once they
+                        // form a cycle within it
+                        SetRule::Connected => {
+                            if !is_connected(set, &self.get_possible(states)).satisfied {
+                                return Err(Contradiction);
+                            }
+                        }
+                        SetRule::Acyclic => {
+                            if !is_acyclic(set, &self.get_drawn(states)).satisfied {
+                                return Err(Contradiction);
+                            }
+                        }
+                        SetRule::Scope => {}
+                    }
+                }
+            }
+            Ok(None)
+        }
+
+        /// Nodes sharing a DegreeEqual condition must all end up between the highest drawn
+        /// degree and the lowest reachable degree of the group.
+        fn find_degree_equal_deduction(
+            &self,
+            states: &LineStates,
+        ) -> Result<Option<Deduction>, Contradiction> {
+            for (con_cdtn, nodes) in get_connected_condition_groups(&self.puzzle.nodes) {
+                if !matches!(con_cdtn, ConnectedNodeCondition::DegreeEqual(_)) {
+                    continue;
+                }
+                let counts: Vec<(GameNodeId, usize, Vec<usize>)> = nodes
+                    .iter()
+                    .map(|node| {
+                        let (drawn, undecided) =
+                            self.count_lines(states, self.get_node_lines(&node.id));
+                        (node.id, drawn, undecided)
+                    })
+                    .collect();
+                let max_drawn = counts.iter().map(|(_, drawn, _)| *drawn).max();
+                let min_possible = counts
+                    .iter()
+                    .map(|(_, drawn, undecided)| drawn + undecided.len())
+                    .min();
+                let (Some(max_drawn), Some(min_possible)) = (max_drawn, min_possible) else {
+                    continue;
+                };
+                if min_possible == 0 || max_drawn > min_possible {
+                    return Err(Contradiction);
+                }
+
+                for (node_id, drawn, undecided) in counts.iter() {
+                    let Some(line_idx) = undecided.first() else {
+                        continue;
+                    };
+                    if drawn + undecided.len() == max_drawn {
+                        return Ok(Some(Deduction {
+                            line_idx: *line_idx,
+                            drawn: true,
+                            reason: DeductionReason::DegreeEqualRaise(*node_id),
+                        }));
+                    }
+                    if *drawn == min_possible {
+                        return Ok(Some(Deduction {
+                            line_idx: *line_idx,
+                            drawn: false,
+                            reason: DeductionReason::DegreeEqualCap(*node_id),
+                        }));
+                    }
+                }
+            }
+            Ok(None)
+        }
+
+        /// Returns true if every node can still be joined into one network by the lines which
+        /// aren't left out.
+        fn is_connectable(&self, states: &LineStates) -> bool {
+            match self.get_possible(states).components().as_slice() {
+                [network] => network.len() == self.puzzle.nodes.len(),
+                _ => self.puzzle.nodes.len() <= 1,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{
+        logic::puzzle::{
+            deduction::deduction::{order_by_difficulty, solve_logically, DeductionReason},
+            uniqueness::uniqueness::{check_uniqueness, Uniqueness},
+        },
+        puzzle_manager::PuzzleManager,
+        structs::immutable::{
+            game_line::game_line::GameLine, game_set::game_set::GameSet,
+            node_condition::node_condition::NodeCondition, puzzle::puzzle::Puzzle,
+            set_rule::set_rule::SetRule,
+        },
+    };
+
+    fn get_test_puzzle(width: u8, height: u8, conditions: Vec<Vec<NodeCondition>>) -> Puzzle {
+        let mut puzzle = Puzzle::new(width, height);
+        for node in puzzle.nodes.iter_mut() {
+            node.conditions = conditions
+                .get(node.id as usize)
+                .cloned()
+                .unwrap_or_default();
+        }
+        puzzle
+    }
+
+    #[test]
+    fn test_solve_logically_three_leaves_returns_star_without_guessing() {
+        let puzzle = get_test_puzzle(
+            2,
+            2,
+            [
+                Vec::new(),
+                [NodeCondition::Leaf].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+            ]
+            .to_vec(),
+        );
+
+        let trace = solve_logically(&puzzle);
+
+        let solution = trace.solution.clone().unwrap();
+        assert!(solution
+            .iter()
+            .all(|line| line.node_a_id() == 0 || line.node_b_id() == 0));
+        assert!(!trace.difficulty().guessed);
+    }
+
+    #[test]
+    fn test_solve_logically_all_leaves_returns_no_solution() {
+        let puzzle = get_test_puzzle(2, 2, vec![[NodeCondition::Leaf].to_vec(); 4]);
+
+        assert_eq!(solve_logically(&puzzle).solution, None);
+    }
+
+    #[test]
+    fn test_solve_logically_fixed_line_is_drawn_without_a_step() {
+        let fixed = GameLine::new(0, 3).unwrap();
+        let mut puzzle = get_test_puzzle(
+            2,
+            2,
+            [
+                Vec::new(),
+                [NodeCondition::Leaf].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+            ]
+            .to_vec(),
+        );
+        puzzle.fixed_lines = [fixed].to_vec();
+
+        let trace = solve_logically(&puzzle);
+
+        assert!(trace.solution.unwrap().contains(&fixed));
+        assert!(trace.steps.iter().all(|step| step.line != fixed));
+    }
+
+    #[test]
+    fn test_solve_logically_disconnected_set_forbids_inner_line() {
+        let mut puzzle = get_test_puzzle(2, 2, Vec::new());
+        puzzle.sets.push(GameSet {
+            id: 0,
+            nodes: [0, 1].to_vec(),
+            rules: [SetRule::Disconnected].to_vec(),
+            connected_rules: Vec::new(),
+            meta_rules: Vec::new(),
+            bounded: false,
+        });
+
+        let trace = solve_logically(&puzzle);
+
+        assert!(!trace
+            .solution
+            .unwrap()
+            .contains(&GameLine::new(0, 1).unwrap()));
+        assert_eq!(
+            trace.steps.first().map(|step| (step.line, step.reason)),
+            Some((
+                GameLine::new(0, 1).unwrap(),
+                DeductionReason::DisconnectedSet(0)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_difficulty_guessing_scores_higher_than_deducing() {
+        let deduced = get_test_puzzle(
+            2,
+            2,
+            [
+                Vec::new(),
+                [NodeCondition::Leaf].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+            ]
+            .to_vec(),
+        );
+        let guessed = get_test_puzzle(2, 2, Vec::new());
+
+        let deduced_difficulty = solve_logically(&deduced).difficulty();
+        let guessed_difficulty = solve_logically(&guessed).difficulty();
+
+        assert!(guessed_difficulty.guessed);
+        assert!(guessed_difficulty.score > deduced_difficulty.score);
+    }
+
+    #[test]
+    fn test_order_by_difficulty_returns_easiest_first() {
+        let guessed = get_test_puzzle(2, 2, Vec::new());
+        let deduced = get_test_puzzle(
+            2,
+            2,
+            [
+                Vec::new(),
+                [NodeCondition::Leaf].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+            ]
+            .to_vec(),
+        );
+
+        let order = order_by_difficulty(&[guessed.clone(), deduced.clone()]);
+
+        assert_eq!(
+            order.iter().map(|(uuid, _)| *uuid).collect::<Vec<Uuid>>(),
+            [deduced.uuid, guessed.uuid].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_solve_logically_campaign_puzzles_returns_unique_solution() {
+        let mut puzzle_manager = PuzzleManager::new();
+        puzzle_manager.populate_campaign();
+
+        for uuid in puzzle_manager.get_puzzle_uuids() {
+            let puzzle = puzzle_manager.load_puzzle(&uuid).unwrap();
+            if let Uniqueness::Unique(solution) = check_uniqueness(&puzzle) {
+                assert_eq!(solve_logically(&puzzle).solution, Some(solution));
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_logically_straight_node_continues_line_returns_straight_path() {
+        let puzzle = get_test_puzzle(
+            3,
+            1,
+            [Vec::new(), [NodeCondition::Straight].to_vec()].to_vec(),
+        );
+
+        let trace = solve_logically(&puzzle);
+
+        assert_eq!(
+            trace.solution.clone().unwrap().lines(),
+            [GameLine::new(0, 1).unwrap(), GameLine::new(1, 2).unwrap()]
+        );
+        assert!(!trace.difficulty().guessed);
+    }
+
+    #[test]
+    fn test_solve_logically_degree_full_forbids_remaining_lines() {
+        let puzzle = get_test_puzzle(2, 2, [[NodeCondition::Degree(1)].to_vec()].to_vec());
+
+        let trace = solve_logically(&puzzle);
+
+        assert!(trace
+            .steps
+            .iter()
+            .any(|step| step.reason == DeductionReason::DegreeFull(0) && !step.drawn));
+        assert_eq!(trace.solution.unwrap().degree(&0), 1);
+    }
+
+    #[test]
+    fn test_solve_logically_closed_set_within_network_returns_no_solution() {
+        let mut puzzle = get_test_puzzle(3, 1, Vec::new());
+        puzzle.sets.push(GameSet {
+            id: 0,
+            nodes: [0, 1].to_vec(),
+            rules: [SetRule::Closed].to_vec(),
+            connected_rules: Vec::new(),
+            meta_rules: Vec::new(),
+            bounded: false,
+        });
+
+        assert_eq!(solve_logically(&puzzle).solution, None);
+    }
+}
@@ -0,0 +1,583 @@
+pub mod evaluation {
+    use std::collections::{HashMap, HashSet};
+
+    use crate::{
+        logic::{
+            connected_rule_checks::connected_rule_checks::HomomorphismCache,
+            diagnostic::diagnostic::{CheckResult, Diagnostic},
+            util::{
+                get_bounded_solutions, get_connected_condition_groups, get_connected_rule_groups,
+                has_crossing_lines,
+            },
+        },
+        structs::immutable::{
+            connected_node_condition::connected_node_condition::ConnectedNodeCondition,
+            connected_set_rule::connected_set_rule::ConnectedSetRule,
+            game_line::game_line::GameLine,
+            game_node::game_node::{GameNode, GameNodeId},
+            game_set::game_set::GameSet,
+            puzzle::puzzle::Puzzle,
+            solution::solution::Solution,
+        },
+    };
+
+    /// Stable identifier of a checked element of a puzzle. Conditions and rules are identified by
+    /// their index within the node or set they belong to.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub enum EvaluationId {
+        /// The node is part of the single network of lines.
+        Node(GameNodeId),
+        NodeCondition(GameNodeId, usize),
+        ConnectedNodeCondition(GameNodeId, usize),
+        /// All rules of the set hold, combined by its meta rules if it has any.
+        Set(u8),
+        SetRule(u8, usize),
+        ConnectedSetRule(u8, usize),
+        MetaSetRule(u8, usize),
+    }
+
+    /// The satisfied state of every node, condition and rule of a puzzle for a solution, along
+    /// with why each condition and rule is (un)satisfied.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct EvaluationReport {
+        results: HashMap<EvaluationId, bool>,
+        diagnostics: HashMap<EvaluationId, Diagnostic>,
+        connected_condition_groups: HashMap<ConnectedNodeCondition, bool>,
+        connected_rule_groups: HashMap<ConnectedSetRule, bool>,
+        has_crossing_lines: bool,
+    }
+
+    impl EvaluationReport {
+        /// Returns the satisfied state of the element, or None if the puzzle has no such element.
+        pub fn get(&self, id: &EvaluationId) -> Option<bool> {
+            self.results.get(id).copied()
+        }
+
+        /// Returns true if the element exists and is satisfied.
+        pub fn is_satisfied(&self, id: &EvaluationId) -> bool {
+            self.get(id).unwrap_or(false)
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = (&EvaluationId, &bool)> {
+            self.results.iter()
+        }
+
+        /// Returns why the condition or rule is (un)satisfied, or None if the element isn't a
+        /// condition or rule of the puzzle.
+        pub fn get_diagnostic(&self, id: &EvaluationId) -> Option<&Diagnostic> {
+            self.diagnostics.get(id)
+        }
+
+        /// Returns the satisfied state shared by all nodes with the connected condition.
+        pub fn get_connected_condition_group(
+            &self,
+            con_cdtn: &ConnectedNodeCondition,
+        ) -> Option<bool> {
+            self.connected_condition_groups.get(con_cdtn).copied()
+        }
+
+        /// Returns the satisfied state shared by all sets with the connected rule.
+        pub fn get_connected_rule_group(&self, con_rule: &ConnectedSetRule) -> Option<bool> {
+            self.connected_rule_groups.get(con_rule).copied()
+        }
+
+        /// Returns true if the solution has crossing lines the puzzle doesn't allow.
+        pub fn has_crossing_lines(&self) -> bool {
+            self.has_crossing_lines
+        }
+
+        /// Returns true if the solution solves the puzzle. Individual set and meta rules aren't
+        /// checked directly, as `EvaluationId::Set` already combines them.
+        pub fn is_solved(&self) -> bool {
+            !self.has_crossing_lines
+                && self.results.iter().all(|(id, satisfied)| {
+                    *satisfied
+                        || matches!(
+                            id,
+                            EvaluationId::SetRule(..) | EvaluationId::MetaSetRule(..)
+                        )
+                })
+        }
+    }
+
+    impl EvaluationReport {
+        /// Records the result of checking a condition or rule.
+        fn insert_check(&mut self, id: EvaluationId, check: CheckResult) {
+            self.results.insert(id, check.satisfied);
+            self.diagnostics.insert(id, check.diagnostic);
+        }
+
+        /// Updates whether each node is part of the single network, from the networks of the
+        /// solution so they are only found once.
+        fn evaluate_networks(&mut self, puzzle: &Puzzle, networks: &[Vec<GameNodeId>]) {
+            for node in puzzle.nodes.iter() {
+                let satisfied = match networks {
+                    [network] => network.binary_search(&node.id).is_ok(),
+                    _ => false,
+                };
+                self.results.insert(EvaluationId::Node(node.id), satisfied);
+            }
+        }
+
+        /// Updates the conditions of a node, which must hold in every bounded set the node is in.
+        /// The diagnostic is taken from the first bounded set the condition fails in, if any.
+        fn evaluate_node_conditions(
+            &mut self,
+            puzzle: &Puzzle,
+            node: &GameNode,
+            solution: &Solution,
+        ) {
+            let bounded_solutions =
+                get_bounded_solutions(&node.id, puzzle.sets.iter().collect(), solution);
+            for (idx, condition) in node.conditions.iter().enumerate() {
+                let checks: Vec<CheckResult> = bounded_solutions
+                    .iter()
+                    .map(|bounded_solution| condition.check(node, bounded_solution, puzzle))
+                    .collect();
+                let satisfied = checks.iter().all(|check| check.satisfied);
+                let diagnostic = checks
+                    .into_iter()
+                    .find(|check| check.satisfied == satisfied)
+                    .map(|check| check.diagnostic)
+                    .unwrap_or(Diagnostic::NoLines);
+                self.insert_check(
+                    EvaluationId::NodeCondition(node.id, idx),
+                    CheckResult::new(satisfied, diagnostic),
+                );
+            }
+        }
+
+        /// Updates a connected condition group and every node condition belonging to it.
+        fn evaluate_connected_condition(
+            &mut self,
+            con_cdtn: ConnectedNodeCondition,
+            nodes: Vec<&GameNode>,
+            solution: &Solution,
+        ) {
+            let check = con_cdtn.check(nodes.clone(), solution);
+            self.connected_condition_groups
+                .insert(con_cdtn, check.satisfied);
+            for node in nodes {
+                for (idx, node_con_cdtn) in node.connected_conditions.iter().enumerate() {
+                    if *node_con_cdtn == con_cdtn {
+                        self.insert_check(
+                            EvaluationId::ConnectedNodeCondition(node.id, idx),
+                            check.clone(),
+                        );
+                    }
+                }
+            }
+        }
+
+        /// Updates the set along with its rules and meta rules.
+        fn evaluate_set(&mut self, set: &GameSet, solution: &Solution) {
+            self.results
+                .insert(EvaluationId::Set(set.id), set.are_rules_satisfied(solution));
+            for (idx, rule) in set.rules.iter().enumerate() {
+                self.insert_check(
+                    EvaluationId::SetRule(set.id, idx),
+                    rule.check(set, solution),
+                );
+            }
+            for (idx, meta_rule) in set.meta_rules.iter().enumerate() {
+                self.insert_check(
+                    EvaluationId::MetaSetRule(set.id, idx),
+                    meta_rule.check(set, solution),
+                );
+            }
+        }
+
+        /// Updates a connected rule group and every set rule belonging to it.
+        fn evaluate_connected_rule(
+            &mut self,
+            puzzle: &Puzzle,
+            con_rule: ConnectedSetRule,
+            sets: Vec<&GameSet>,
+            solution: &Solution,
+            homomorphisms: &mut HomomorphismCache,
+        ) {
+            let check = con_rule.check_with_cache(sets.clone(), solution, puzzle, homomorphisms);
+            self.connected_rule_groups.insert(con_rule, check.satisfied);
+            for set in sets {
+                for (idx, set_con_rule) in set.connected_rules.iter().enumerate() {
+                    if *set_con_rule == con_rule {
+                        self.insert_check(
+                            EvaluationId::ConnectedSetRule(set.id, idx),
+                            check.clone(),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Evaluates every node, condition and rule of the puzzle against a solution.
+    ///
+    /// # Parameters
+    ///
+    /// - `puzzle`: The puzzle to evaluate.
+    /// - `solution`: The lines drawn in the puzzle.
+    ///
+    /// # Returns
+    ///
+    /// Returns the report of all satisfied states, keyed by stable ids.
+    pub fn evaluate(puzzle: &Puzzle, solution: &Solution) -> EvaluationReport {
+        evaluate_with_cache(puzzle, solution, &mut HomomorphismCache::new())
+    }
+
+    /// Evaluates the puzzle like `evaluate`, reusing connected rule results in the cache.
+    fn evaluate_with_cache(
+        puzzle: &Puzzle,
+        solution: &Solution,
+        homomorphisms: &mut HomomorphismCache,
+    ) -> EvaluationReport {
+        let mut report = EvaluationReport {
+            has_crossing_lines: !puzzle.allow_crossings && has_crossing_lines(solution, puzzle),
+            ..Default::default()
+        };
+
+        report.evaluate_networks(puzzle, &solution.components());
+        for node in puzzle.nodes.iter() {
+            report.evaluate_node_conditions(puzzle, node, solution);
+        }
+        for (con_cdtn, nodes) in get_connected_condition_groups(&puzzle.nodes).into_iter() {
+            report.evaluate_connected_condition(con_cdtn, nodes, solution);
+        }
+        for set in puzzle.sets.iter() {
+            report.evaluate_set(set, solution);
+        }
+        for (con_rule, sets) in get_connected_rule_groups(&puzzle.sets).into_iter() {
+            report.evaluate_connected_rule(puzzle, con_rule, sets, solution, homomorphisms);
+        }
+
+        report
+    }
+
+    /// Keeps the evaluation of a puzzle up to date while lines are drawn one at a time. A changed
+    /// line can only affect nodes in the networks it joins or splits, groups with a node in those
+    /// networks, and sets containing either end of the line, so only those are checked again.
+    pub struct IncrementalEvaluator {
+        puzzle: Puzzle,
+        solution: Solution,
+        report: EvaluationReport,
+        /// Results of connected rules, created once the puzzle is loaded.
+        homomorphisms: HomomorphismCache,
+    }
+
+    impl IncrementalEvaluator {
+        /// Creates an evaluator for a puzzle, fully evaluating the lines already drawn.
+        ///
+        /// # Parameters
+        ///
+        /// - `puzzle`: The puzzle being played.
+        /// - `solution`: The lines already drawn in the puzzle.
+        pub fn new(puzzle: Puzzle, solution: Solution) -> Self {
+            let mut homomorphisms = HomomorphismCache::new();
+            let report = evaluate_with_cache(&puzzle, &solution, &mut homomorphisms);
+            IncrementalEvaluator {
+                puzzle,
+                solution,
+                report,
+                homomorphisms,
+            }
+        }
+
+        pub fn puzzle(&self) -> &Puzzle {
+            &self.puzzle
+        }
+
+        pub fn solution(&self) -> &Solution {
+            &self.solution
+        }
+
+        pub fn report(&self) -> &EvaluationReport {
+            &self.report
+        }
+
+        /// Draws a line and re-evaluates what it affects, returning false if it was already drawn
+        /// or is forbidden.
+        pub fn add_line(&mut self, line: GameLine) -> bool {
+            if self.puzzle.forbidden_lines.contains(&line) {
+                return false;
+            }
+            let touched_nodes = self.get_touched_nodes(&line);
+            if !self.solution.insert(line) {
+                return false;
+            }
+            self.update(&line, touched_nodes);
+            true
+        }
+
+        /// Removes a line and re-evaluates what it affected, returning false if it wasn't drawn
+        /// or is fixed.
+        pub fn remove_line(&mut self, line: &GameLine) -> bool {
+            if self.puzzle.fixed_lines.contains(line) {
+                return false;
+            }
+            let touched_nodes = self.get_touched_nodes(line);
+            if !self.solution.remove(line) {
+                return false;
+            }
+            self.update(line, touched_nodes);
+            true
+        }
+
+        /// Removes all lines except the fixed lines, evaluating the puzzle from scratch.
+        pub fn clear_lines(&mut self) {
+            self.solution = Solution::from_lines_unchecked(self.puzzle.fixed_lines.iter().cloned());
+            self.report =
+                evaluate_with_cache(&self.puzzle, &self.solution, &mut self.homomorphisms);
+        }
+
+        /// Returns both ends of the line and all nodes in the networks containing them.
+        fn get_touched_nodes(&self, line: &GameLine) -> HashSet<GameNodeId> {
+            let mut touched_nodes = HashSet::from([line.node_a_id(), line.node_b_id()]);
+            for network in self.solution.components() {
+                if network.contains(&line.node_a_id()) || network.contains(&line.node_b_id()) {
+                    touched_nodes.extend(network);
+                }
+            }
+            touched_nodes
+        }
+
+        /// Re-evaluates everything a changed line may affect, given the nodes it touched before
+        /// the change.
+        fn update(&mut self, line: &GameLine, mut touched_nodes: HashSet<GameNodeId>) {
+            let puzzle = &self.puzzle;
+            let solution = &self.solution;
+            let report = &mut self.report;
+            let homomorphisms = &mut self.homomorphisms;
+
+            let networks = solution.components();
+            for network in networks.iter() {
+                if network.contains(&line.node_a_id()) || network.contains(&line.node_b_id()) {
+                    touched_nodes.extend(network);
+                }
+            }
+
+            report.has_crossing_lines =
+                !puzzle.allow_crossings && has_crossing_lines(solution, puzzle);
+            report.evaluate_networks(puzzle, &networks);
+
+            for node in puzzle.nodes.iter() {
+                if touched_nodes.contains(&node.id) {
+                    report.evaluate_node_conditions(puzzle, node, solution);
+                }
+            }
+            for (con_cdtn, nodes) in get_connected_condition_groups(&puzzle.nodes).into_iter() {
+                if nodes.iter().any(|node| touched_nodes.contains(&node.id)) {
+                    report.evaluate_connected_condition(con_cdtn, nodes, solution);
+                }
+            }
+
+            // Set rules only consider lines with at least one end in the set, as do connected rules
+            // other than those looking for paths outside their sets
+            let is_touched_set = |set: &GameSet| {
+                set.nodes.contains(&line.node_a_id()) || set.nodes.contains(&line.node_b_id())
+            };
+            for set in puzzle.sets.iter() {
+                if is_touched_set(set) {
+                    report.evaluate_set(set, solution);
+                }
+            }
+            for (con_rule, sets) in get_connected_rule_groups(&puzzle.sets).into_iter() {
+                if !con_rule.is_within_sets() || sets.iter().any(|set| is_touched_set(set)) {
+                    report.evaluate_connected_rule(puzzle, con_rule, sets, solution, homomorphisms);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::structs::immutable::{
+        connected_node_condition::connected_node_condition::{
+            ConditionClass, ConnectedNodeCondition,
+        },
+        game_line::game_line::GameLine,
+        game_node::game_node::GameNode,
+        game_set::game_set::GameSet,
+        meta_set_rule::meta_set_rule::MetaSetRule,
+        node_condition::node_condition::NodeCondition,
+        puzzle::puzzle::Puzzle,
+        set_rule::set_rule::SetRule,
+        solution::solution::Solution,
+    };
+
+    use crate::{
+        logic::{
+            diagnostic::diagnostic::Diagnostic,
+            puzzle::{
+                evaluation::evaluation::{evaluate, EvaluationId, IncrementalEvaluator},
+                solver::solver::get_candidate_lines,
+            },
+        },
+        puzzle_manager::PuzzleManager,
+    };
+
+    fn get_test_puzzle() -> Puzzle {
+        let degree_equal = ConnectedNodeCondition::DegreeEqual(ConditionClass(0));
+        Puzzle {
+            nodes: [
+                GameNode {
+                    id: 0,
+                    conditions: [NodeCondition::Leaf].to_vec(),
+                    connected_conditions: [degree_equal].to_vec(),
+                },
+                GameNode {
+                    id: 1,
+                    conditions: [NodeCondition::Leaf, NodeCondition::Internal].to_vec(),
+                    connected_conditions: Vec::new(),
+                },
+                GameNode {
+                    id: 2,
+                    conditions: Vec::new(),
+                    connected_conditions: [degree_equal].to_vec(),
+                },
+            ]
+            .to_vec(),
+            sets: [GameSet {
+                id: 4,
+                nodes: [1, 2].to_vec(),
+                rules: [SetRule::Disconnected, SetRule::Leaf].to_vec(),
+                connected_rules: Vec::new(),
+                meta_rules: [MetaSetRule::Xor].to_vec(),
+                bounded: false,
+            }]
+            .to_vec(),
+            ..Puzzle::new(3, 1)
+        }
+    }
+
+    #[test]
+    fn test_evaluate_keys_results_by_node_and_index() {
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(1, 2).unwrap()]);
+
+        let report = evaluate(&get_test_puzzle(), &solution);
+
+        assert_eq!(report.get(&EvaluationId::Node(2)), Some(true));
+        assert_eq!(report.get(&EvaluationId::NodeCondition(0, 0)), Some(true));
+        assert_eq!(report.get(&EvaluationId::NodeCondition(1, 0)), Some(false));
+        assert_eq!(report.get(&EvaluationId::NodeCondition(1, 1)), Some(true));
+        assert_eq!(report.get(&EvaluationId::NodeCondition(2, 0)), None);
+        assert_eq!(
+            report.get(&EvaluationId::ConnectedNodeCondition(0, 0)),
+            Some(true)
+        );
+        assert_eq!(
+            report.get(&EvaluationId::ConnectedNodeCondition(2, 0)),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_set_combines_rules_by_meta_rules() {
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(1, 2).unwrap()]);
+
+        let report = evaluate(&get_test_puzzle(), &solution);
+
+        // Disconnected fails as 1 and 2 are connected, Leaf holds with the single line to 0
+        assert_eq!(report.get(&EvaluationId::SetRule(4, 0)), Some(false));
+        assert_eq!(report.get(&EvaluationId::SetRule(4, 1)), Some(true));
+        assert_eq!(report.get(&EvaluationId::MetaSetRule(4, 0)), Some(true));
+        assert_eq!(report.get(&EvaluationId::Set(4)), Some(true));
+    }
+
+    #[test]
+    fn test_evaluate_reports_diagnostics_of_conditions_and_rules() {
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(1, 2).unwrap()]);
+
+        let report = evaluate(&get_test_puzzle(), &solution);
+
+        assert_eq!(
+            report.get_diagnostic(&EvaluationId::NodeCondition(1, 0)),
+            Some(&Diagnostic::Degree(2))
+        );
+        assert_eq!(
+            report.get_diagnostic(&EvaluationId::ConnectedNodeCondition(2, 0)),
+            Some(&Diagnostic::Degrees([1, 1].to_vec()))
+        );
+        assert_eq!(
+            report.get_diagnostic(&EvaluationId::SetRule(4, 0)),
+            Some(&Diagnostic::InnerLines(1))
+        );
+        assert_eq!(report.get_diagnostic(&EvaluationId::Node(1)), None);
+    }
+
+    #[test]
+    fn test_evaluate_unsatisfied_condition_returns_unsolved() {
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(1, 2).unwrap()]);
+
+        assert!(!evaluate(&get_test_puzzle(), &solution).is_solved());
+    }
+
+    #[test]
+    fn test_evaluate_all_satisfied_returns_solved() {
+        let mut puzzle = get_test_puzzle();
+        puzzle.nodes[1].conditions = [NodeCondition::Internal].to_vec();
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(1, 2).unwrap()]);
+
+        assert!(evaluate(&puzzle, &solution).is_solved());
+    }
+
+    #[test]
+    fn test_incremental_evaluator_matches_full_evaluation() {
+        let mut puzzle_manager = PuzzleManager::new();
+        puzzle_manager.populate_campaign();
+
+        for uuid in puzzle_manager.get_puzzle_uuids() {
+            let puzzle = puzzle_manager.load_puzzle(&uuid).unwrap();
+            let lines = get_candidate_lines(&puzzle);
+            let mut evaluator = IncrementalEvaluator::new(puzzle.clone(), Solution::new());
+
+            // Draw every other line, then remove them in a different order, joining and
+            // splitting networks along the way
+            for line in lines.iter().step_by(2) {
+                assert!(evaluator.add_line(*line));
+                assert_eq!(
+                    *evaluator.report(),
+                    evaluate(&puzzle, evaluator.solution()),
+                    "{} after adding {:?}",
+                    uuid,
+                    line
+                );
+            }
+            for line in lines.iter().step_by(2).rev().step_by(3) {
+                assert!(evaluator.remove_line(line));
+                assert_eq!(
+                    *evaluator.report(),
+                    evaluate(&puzzle, evaluator.solution()),
+                    "{} after removing {:?}",
+                    uuid,
+                    line
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_incremental_evaluator_existing_line_returns_false() {
+        let line = GameLine::new(0, 1).unwrap();
+        let mut evaluator = IncrementalEvaluator::new(get_test_puzzle(), Solution::from([line]));
+
+        assert!(!evaluator.add_line(line));
+        assert!(evaluator.remove_line(&line));
+        assert!(!evaluator.remove_line(&line));
+    }
+
+    #[test]
+    fn test_incremental_evaluator_fixed_and_forbidden_lines_are_kept() {
+        let fixed = GameLine::new(0, 1).unwrap();
+        let forbidden = GameLine::new(1, 2).unwrap();
+        let mut puzzle = get_test_puzzle();
+        puzzle.fixed_lines = [fixed].to_vec();
+        puzzle.forbidden_lines = [forbidden].to_vec();
+        let mut evaluator = IncrementalEvaluator::new(puzzle, Solution::from([fixed]));
+
+        assert!(!evaluator.add_line(forbidden));
+        assert!(!evaluator.remove_line(&fixed));
+        evaluator.clear_lines();
+        assert_eq!(evaluator.solution(), &Solution::from([fixed]));
+    }
+}
@@ -0,0 +1,670 @@
+pub mod generator {
+    use std::{collections::HashMap, error::Error, fmt};
+
+    use uuid::Builder;
+
+    use crate::{
+        logic::{
+            puzzle::{
+                solver::solver::get_candidate_lines,
+                uniqueness::uniqueness::{check_uniqueness, Uniqueness},
+            },
+            util::get_crossing_line,
+        },
+        structs::immutable::{
+            connected_node_condition::connected_node_condition::{
+                ConditionClass, ConnectedNodeCondition,
+            },
+            connected_set_rule::connected_set_rule::{ConnectedSetRule, RuleClass},
+            game_line::game_line::GameLine,
+            game_node::game_node::{GameNode, GameNodeId},
+            game_set::game_set::GameSet,
+            node_condition::node_condition::NodeCondition,
+            puzzle::puzzle::Puzzle,
+            set_rule::set_rule::SetRule,
+            solution::solution::Solution,
+        },
+    };
+
+    /// Target networks tried before giving up, as the clues of a network may not single it out.
+    const MAX_GENERATION_ATTEMPTS: usize = 20;
+
+    const CONDITION_CLASSES: [ConditionClass; 3] =
+        [ConditionClass(0), ConditionClass(1), ConditionClass(2)];
+    const RULE_CLASSES: [RuleClass; 3] = [RuleClass(0), RuleClass(1), RuleClass(2)];
+
+    /// Creates a connected set rule of the given class.
+    type RuleConstructor = fn(RuleClass) -> ConnectedSetRule;
+
+    /// Creates a connected node condition of the given class.
+    type ConditionConstructor = fn(ConditionClass) -> ConnectedNodeCondition;
+
+    /// A kind of clue the generator may place in a puzzle.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum ClueType {
+        BranchEqual,
+        Leaf,
+        Internal,
+        Cycle,
+        /// The Degree condition, with the degree the node has in the target network.
+        Degree,
+        CutVertex,
+        Straight,
+        /// The Disconnected set rule.
+        SetDisconnected,
+        /// The Leaf set rule.
+        SetLeaf,
+        /// The Connected set rule.
+        SetConnected,
+        /// The Acyclic set rule.
+        SetAcyclic,
+        /// The EdgeCount set rule, with the number of lines the set has in the target network.
+        SetEdgeCount,
+        /// The Closed set rule.
+        SetClosed,
+        DegreeEqual,
+        DistanceEqual,
+        DegreeDistinct,
+        DistanceDistinct,
+        SameComponentPath,
+        Homomorphic,
+        Isomorphic,
+        Congruent,
+        EdgeCountEqual,
+        MutuallyDisconnected,
+    }
+
+    impl ClueType {
+        fn needs_sets(&self) -> bool {
+            matches!(
+                self,
+                ClueType::SetDisconnected
+                    | ClueType::SetLeaf
+                    | ClueType::SetConnected
+                    | ClueType::SetAcyclic
+                    | ClueType::SetEdgeCount
+                    | ClueType::SetClosed
+                    | ClueType::Homomorphic
+                    | ClueType::Isomorphic
+                    | ClueType::Congruent
+                    | ClueType::EdgeCountEqual
+                    | ClueType::MutuallyDisconnected
+            )
+        }
+    }
+
+    /// Settings of a generated puzzle.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct GeneratorOptions {
+        pub width: u8,
+        pub height: u8,
+        /// The same seed and settings always generate the same puzzle.
+        pub seed: u64,
+        /// Kinds of clues the puzzle may contain, e.g. only a newly introduced condition.
+        pub allowed_clues: Vec<ClueType>,
+        pub allow_crossings: bool,
+    }
+
+    /// Reasons a puzzle couldn't be generated.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum GenerationError {
+        /// The grid has fewer than two nodes, so no lines can be drawn.
+        BoardTooSmall,
+        /// No clue types are allowed.
+        NoCluesAllowed,
+        /// None of the target networks tried could be singled out by the allowed clues.
+        NoUniquePuzzle { attempts: usize },
+    }
+
+    impl fmt::Display for GenerationError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                GenerationError::BoardTooSmall => {
+                    write!(f, "board needs at least two nodes")
+                }
+                GenerationError::NoCluesAllowed => write!(f, "no clue types are allowed"),
+                GenerationError::NoUniquePuzzle { attempts } => write!(
+                    f,
+                    "no puzzle with a unique solution found in {} attempts",
+                    attempts
+                ),
+            }
+        }
+    }
+
+    impl Error for GenerationError {}
+
+    /// A clue placed on a node or set, identified by the id of the node or set.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    enum Clue {
+        NodeCondition(GameNodeId, NodeCondition),
+        ConnectedNodeCondition(GameNodeId, ConnectedNodeCondition),
+        SetRule(u8, SetRule),
+        ConnectedSetRule(u8, ConnectedSetRule),
+    }
+
+    /// Small seeded random number generator (SplitMix64), so puzzles can be generated again from
+    /// their seed.
+    struct SeededRng {
+        state: u64,
+    }
+
+    impl SeededRng {
+        fn new(seed: u64) -> Self {
+            SeededRng { state: seed }
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = self.state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        }
+
+        /// Returns a number from 0 up to but excluding `bound`, which must be positive.
+        fn below(&mut self, bound: usize) -> usize {
+            (self.next_u64() % bound as u64) as usize
+        }
+
+        fn shuffle<T>(&mut self, items: &mut [T]) {
+            for idx in (1..items.len()).rev() {
+                items.swap(idx, self.below(idx + 1));
+            }
+        }
+    }
+
+    /// Generates a puzzle with a unique solution from a seed.
+    ///
+    /// A random network of lines connecting every node is picked as the solution, then every
+    /// allowed clue which holds for it is placed. Clues are then removed one at a time in random
+    /// order, as long as the solver still finds the network to be the only solution.
+    ///
+    /// # Parameters
+    ///
+    /// - `options`: Size, seed and allowed clues of the puzzle.
+    ///
+    /// # Returns
+    ///
+    /// Returns the puzzle, or an error if no puzzle with a unique solution could be made.
+    pub fn generate_puzzle(options: &GeneratorOptions) -> Result<Puzzle, GenerationError> {
+        if (options.width as u16) * (options.height as u16) < 2 {
+            return Err(GenerationError::BoardTooSmall);
+        }
+        if options.allowed_clues.is_empty() {
+            return Err(GenerationError::NoCluesAllowed);
+        }
+
+        let mut rng = SeededRng::new(options.seed);
+        let mut uuid_bytes = [0u8; 16];
+        uuid_bytes[..8].copy_from_slice(&rng.next_u64().to_le_bytes());
+        uuid_bytes[8..].copy_from_slice(&rng.next_u64().to_le_bytes());
+        let base = Puzzle {
+            uuid: Builder::from_random_bytes(uuid_bytes).into_uuid(),
+            allow_crossings: options.allow_crossings,
+            ..Puzzle::new(options.width, options.height)
+        };
+
+        for _ in 0..MAX_GENERATION_ATTEMPTS {
+            let target = get_target_network(&base, &mut rng);
+            let sets = if options.allowed_clues.iter().any(ClueType::needs_sets) {
+                get_random_sets(&base, &mut rng)
+            } else {
+                Vec::new()
+            };
+            let mut clues = get_clues(&base, &sets, &target, &options.allowed_clues, &mut rng);
+            if !is_unique(&build_puzzle(&base, &sets, &clues)) {
+                continue;
+            }
+
+            let mut order: Vec<usize> = (0..clues.len()).collect();
+            rng.shuffle(&mut order);
+            let mut removed = vec![false; clues.len()];
+            for idx in order {
+                removed[idx] = true;
+                let remaining = get_remaining_clues(&clues, &removed);
+                if !is_unique(&build_puzzle(&base, &sets, &remaining)) {
+                    removed[idx] = false;
+                }
+            }
+            clues = get_remaining_clues(&clues, &removed);
+
+            return Ok(build_puzzle(&base, &sets, &clues));
+        }
+
+        Err(GenerationError::NoUniquePuzzle {
+            attempts: MAX_GENERATION_ATTEMPTS,
+        })
+    }
+
+    fn is_unique(puzzle: &Puzzle) -> bool {
+        matches!(check_uniqueness(puzzle), Uniqueness::Unique(_))
+    }
+
+    fn get_remaining_clues(clues: &[Clue], removed: &[bool]) -> Vec<Clue> {
+        clues
+            .iter()
+            .zip(removed.iter())
+            .filter(|(_, removed)| !**removed)
+            .map(|(clue, _)| clue.clone())
+            .collect()
+    }
+
+    /// Returns a random network connecting every node: a random spanning tree, with a few extra
+    /// lines closing cycles. Lines crossing a drawn line are skipped unless the puzzle allows it.
+    fn get_target_network(puzzle: &Puzzle, rng: &mut SeededRng) -> Solution {
+        let mut lines = get_candidate_lines(puzzle);
+        rng.shuffle(&mut lines);
+
+        // Union find over node ids, to only join lines between separate networks into the tree
+        let mut parents: Vec<usize> = (0..puzzle.nodes.len()).collect();
+        fn find_root(parents: &mut [usize], node: usize) -> usize {
+            let mut root = node;
+            while parents[root] != root {
+                parents[root] = parents[parents[root]];
+                root = parents[root];
+            }
+            root
+        }
+
+        let mut network = Solution::new();
+        let mut spare_lines: Vec<GameLine> = Vec::new();
+        for line in lines {
+            if is_crossing(&line, &network, puzzle) {
+                continue;
+            }
+            let root_a = find_root(&mut parents, line.node_a_id() as usize);
+            let root_b = find_root(&mut parents, line.node_b_id() as usize);
+            if root_a == root_b {
+                spare_lines.push(line);
+            } else {
+                parents[root_a] = root_b;
+                network.insert(line);
+            }
+        }
+
+        let extra_lines = rng.below(puzzle.nodes.len() / 3 + 1);
+        for line in spare_lines.into_iter().take(extra_lines) {
+            if !is_crossing(&line, &network, puzzle) {
+                network.insert(line);
+            }
+        }
+
+        network
+    }
+
+    fn is_crossing(line: &GameLine, network: &Solution, puzzle: &Puzzle) -> bool {
+        !puzzle.allow_crossings
+            && get_crossing_line(line, puzzle).is_some_and(|crossing| network.contains(&crossing))
+    }
+
+    /// Returns a few non-overlapping rectangular sets of up to 2x2 nodes.
+    fn get_random_sets(puzzle: &Puzzle, rng: &mut SeededRng) -> Vec<GameSet> {
+        let mut sets: Vec<GameSet> = Vec::new();
+        let target_count = puzzle.nodes.len() / 4 + 1;
+        for _ in 0..target_count * 4 {
+            if sets.len() >= target_count {
+                break;
+            }
+
+            let set_width = (1 + rng.below(2)).min(puzzle.width as usize);
+            let set_height = (1 + rng.below(2)).min(puzzle.height as usize);
+            let x = rng.below(puzzle.width as usize - set_width + 1);
+            let y = rng.below(puzzle.height as usize - set_height + 1);
+            let nodes: Vec<GameNodeId> = (x..x + set_width)
+                .flat_map(|node_x| {
+                    (y..y + set_height)
+                        .map(move |node_y| (node_x * puzzle.height as usize + node_y) as u16)
+                })
+                .collect();
+
+            if nodes.len() < 2
+                || sets
+                    .iter()
+                    .any(|set| set.nodes.iter().any(|node| nodes.contains(node)))
+            {
+                continue;
+            }
+            sets.push(GameSet {
+                id: sets.len() as u8,
+                nodes,
+                rules: Vec::new(),
+                connected_rules: Vec::new(),
+                meta_rules: Vec::new(),
+                bounded: false,
+            });
+        }
+        sets
+    }
+
+    /// Returns every allowed clue which holds for the target network.
+    fn get_clues(
+        puzzle: &Puzzle,
+        sets: &[GameSet],
+        target: &Solution,
+        allowed_clues: &[ClueType],
+        rng: &mut SeededRng,
+    ) -> Vec<Clue> {
+        let is_allowed = |clue_type: ClueType| allowed_clues.contains(&clue_type);
+        let mut clues: Vec<Clue> = Vec::new();
+
+        for node in puzzle.nodes.iter() {
+            let node_conditions = [
+                (ClueType::BranchEqual, NodeCondition::BranchEqual),
+                (ClueType::Leaf, NodeCondition::Leaf),
+                (ClueType::Internal, NodeCondition::Internal),
+                (ClueType::Cycle, NodeCondition::Cycle),
+                (
+                    ClueType::Degree,
+                    NodeCondition::Degree(target.degree(&node.id) as u8),
+                ),
+                (ClueType::CutVertex, NodeCondition::CutVertex),
+                (ClueType::Straight, NodeCondition::Straight),
+            ];
+            for (clue_type, condition) in node_conditions.iter() {
+                if is_allowed(*clue_type) && condition.is_satisfied(node, target, puzzle) {
+                    clues.push(Clue::NodeCondition(node.id, condition.clone()));
+                }
+            }
+        }
+
+        for set in sets.iter() {
+            let set_rules = [
+                (ClueType::SetDisconnected, SetRule::Disconnected),
+                (ClueType::SetLeaf, SetRule::Leaf),
+                (ClueType::SetConnected, SetRule::Connected),
+                (ClueType::SetAcyclic, SetRule::Acyclic),
+                (
+                    ClueType::SetEdgeCount,
+                    SetRule::EdgeCount(target.filter_to_set(set).len() as u8),
+                ),
+                (ClueType::SetClosed, SetRule::Closed),
+            ];
+            for (clue_type, rule) in set_rules.iter() {
+                if is_allowed(*clue_type) && rule.is_satisfied(set, target) {
+                    clues.push(Clue::SetRule(set.id, *rule));
+                }
+            }
+        }
+
+        let connected_rules: [(ClueType, RuleConstructor); 5] = [
+            (ClueType::Homomorphic, ConnectedSetRule::Homomorphic),
+            (ClueType::Isomorphic, ConnectedSetRule::Isomorphic),
+            (ClueType::Congruent, ConnectedSetRule::Congruent),
+            (ClueType::EdgeCountEqual, ConnectedSetRule::EdgeCountEqual),
+            (
+                ClueType::MutuallyDisconnected,
+                ConnectedSetRule::MutuallyDisconnected,
+            ),
+        ];
+        for (clue_type, make_rule) in connected_rules {
+            if is_allowed(clue_type) {
+                clues.extend(get_connected_rule_clues(puzzle, sets, target, make_rule));
+            }
+        }
+
+        if is_allowed(ClueType::DegreeEqual) {
+            // Nodes of equal degree, a few of which share a class
+            let mut condition_classes = CONDITION_CLASSES.iter();
+            let mut nodes_by_degree: HashMap<usize, Vec<GameNodeId>> = HashMap::new();
+            for node in puzzle.nodes.iter() {
+                nodes_by_degree
+                    .entry(target.degree(&node.id))
+                    .or_default()
+                    .push(node.id);
+            }
+            let mut degrees: Vec<usize> = nodes_by_degree.keys().cloned().collect();
+            degrees.sort();
+            rng.shuffle(&mut degrees);
+            for degree in degrees {
+                let mut nodes = nodes_by_degree[&degree].clone();
+                if nodes.len() < 2 {
+                    continue;
+                }
+                let Some(class) = condition_classes.next() else {
+                    break;
+                };
+                rng.shuffle(&mut nodes);
+                for node_id in nodes.into_iter().take(3) {
+                    clues.push(Clue::ConnectedNodeCondition(
+                        node_id,
+                        ConnectedNodeCondition::DegreeEqual(*class),
+                    ));
+                }
+            }
+        }
+
+        if is_allowed(ClueType::DistanceEqual) {
+            // Any two nodes of the single network have some equal distance to each other
+            let mut condition_classes = CONDITION_CLASSES.iter();
+            let mut node_ids: Vec<GameNodeId> = puzzle.nodes.iter().map(|node| node.id).collect();
+            rng.shuffle(&mut node_ids);
+            for pair in node_ids.chunks_exact(2) {
+                let Some(class) = condition_classes.next() else {
+                    break;
+                };
+                for node_id in pair {
+                    clues.push(Clue::ConnectedNodeCondition(
+                        *node_id,
+                        ConnectedNodeCondition::DistanceEqual(*class),
+                    ));
+                }
+            }
+        }
+
+        let connected_conditions: [(ClueType, ConditionConstructor); 3] = [
+            (
+                ClueType::DegreeDistinct,
+                ConnectedNodeCondition::DegreeDistinct,
+            ),
+            (
+                ClueType::DistanceDistinct,
+                ConnectedNodeCondition::DistanceDistinct,
+            ),
+            (
+                ClueType::SameComponentPath,
+                ConnectedNodeCondition::SameComponentPath,
+            ),
+        ];
+        for (clue_type, make_condition) in connected_conditions {
+            if is_allowed(clue_type) {
+                clues.extend(get_connected_condition_clues(
+                    puzzle,
+                    target,
+                    rng,
+                    make_condition,
+                ));
+            }
+        }
+
+        clues
+    }
+
+    /// Picks random groups of three nodes whose lines satisfy the connected condition, giving
+    /// each group its own class.
+    fn get_connected_condition_clues(
+        puzzle: &Puzzle,
+        target: &Solution,
+        rng: &mut SeededRng,
+        make_condition: ConditionConstructor,
+    ) -> Vec<Clue> {
+        let condition = make_condition(CONDITION_CLASSES[0]);
+        let mut nodes: Vec<&GameNode> = puzzle.nodes.iter().collect();
+        rng.shuffle(&mut nodes);
+
+        nodes
+            .chunks_exact(3)
+            .filter(|group| condition.is_satisfied(group.to_vec(), target))
+            .zip(CONDITION_CLASSES.iter())
+            .flat_map(|(group, class)| {
+                group
+                    .iter()
+                    .map(|node| Clue::ConnectedNodeCondition(node.id, make_condition(*class)))
+            })
+            .collect()
+    }
+
+    /// Groups the sets whose lines satisfy the connected rule together, giving each group of
+    /// two or more sets its own class.
+    fn get_connected_rule_clues(
+        puzzle: &Puzzle,
+        sets: &[GameSet],
+        target: &Solution,
+        make_rule: RuleConstructor,
+    ) -> Vec<Clue> {
+        let rule = make_rule(RULE_CLASSES[0]);
+        let mut groups: Vec<Vec<&GameSet>> = Vec::new();
+        for set in sets.iter() {
+            match groups.iter_mut().find(|group| {
+                let mut candidate = group.to_vec();
+                candidate.push(set);
+                rule.is_satisfied(candidate, target, puzzle)
+            }) {
+                Some(group) => group.push(set),
+                None => groups.push([set].to_vec()),
+            }
+        }
+
+        groups
+            .into_iter()
+            .filter(|group| group.len() > 1)
+            .zip(RULE_CLASSES.iter())
+            .flat_map(|(group, class)| {
+                group
+                    .into_iter()
+                    .map(|set| Clue::ConnectedSetRule(set.id, make_rule(*class)))
+            })
+            .collect()
+    }
+
+    /// Places the clues on the nodes and sets of the puzzle, leaving out sets without any rules.
+    fn build_puzzle(base: &Puzzle, sets: &[GameSet], clues: &[Clue]) -> Puzzle {
+        let mut puzzle = base.clone();
+        let mut sets = sets.to_vec();
+        for clue in clues.iter() {
+            match clue {
+                Clue::NodeCondition(node_id, condition) => {
+                    puzzle.nodes[*node_id as usize]
+                        .conditions
+                        .push(condition.clone());
+                }
+                Clue::ConnectedNodeCondition(node_id, con_cdtn) => {
+                    puzzle.nodes[*node_id as usize]
+                        .connected_conditions
+                        .push(*con_cdtn);
+                }
+                Clue::SetRule(set_id, rule) => sets[*set_id as usize].rules.push(*rule),
+                Clue::ConnectedSetRule(set_id, con_rule) => {
+                    sets[*set_id as usize].connected_rules.push(*con_rule);
+                }
+            }
+        }
+        puzzle.sets = sets
+            .into_iter()
+            .filter(|set| !set.rules.is_empty() || !set.connected_rules.is_empty())
+            .collect();
+        puzzle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::puzzle::{
+        generator::generator::{generate_puzzle, ClueType, GenerationError, GeneratorOptions},
+        uniqueness::uniqueness::{check_uniqueness, Uniqueness},
+        validation::validation::validate_puzzle,
+    };
+
+    fn get_test_options(seed: u64, allowed_clues: Vec<ClueType>) -> GeneratorOptions {
+        GeneratorOptions {
+            width: 3,
+            height: 2,
+            seed,
+            allowed_clues,
+            allow_crossings: false,
+        }
+    }
+
+    fn get_all_clue_types() -> Vec<ClueType> {
+        [
+            ClueType::BranchEqual,
+            ClueType::Leaf,
+            ClueType::Internal,
+            ClueType::Cycle,
+            ClueType::Degree,
+            ClueType::CutVertex,
+            ClueType::Straight,
+            ClueType::SetDisconnected,
+            ClueType::SetLeaf,
+            ClueType::SetConnected,
+            ClueType::SetAcyclic,
+            ClueType::SetEdgeCount,
+            ClueType::SetClosed,
+            ClueType::DegreeEqual,
+            ClueType::DistanceEqual,
+            ClueType::DegreeDistinct,
+            ClueType::DistanceDistinct,
+            ClueType::SameComponentPath,
+            ClueType::Homomorphic,
+            ClueType::Isomorphic,
+            ClueType::Congruent,
+            ClueType::EdgeCountEqual,
+            ClueType::MutuallyDisconnected,
+        ]
+        .to_vec()
+    }
+
+    #[test]
+    fn test_generate_puzzle_returns_valid_puzzle_with_unique_solution() {
+        for seed in 0..3 {
+            let puzzle = generate_puzzle(&get_test_options(seed, get_all_clue_types())).unwrap();
+
+            assert_eq!(validate_puzzle(&puzzle), Ok(()));
+            assert!(matches!(check_uniqueness(&puzzle), Uniqueness::Unique(_)));
+        }
+    }
+
+    #[test]
+    fn test_generate_puzzle_same_seed_returns_same_puzzle() {
+        let options = get_test_options(7, get_all_clue_types());
+
+        let puzzle_a = generate_puzzle(&options).unwrap();
+        let puzzle_b = generate_puzzle(&options).unwrap();
+
+        assert_eq!(puzzle_a.uuid, puzzle_b.uuid);
+        assert_eq!(format!("{:?}", puzzle_a), format!("{:?}", puzzle_b));
+    }
+
+    #[test]
+    fn test_generate_puzzle_only_places_allowed_clues() {
+        let puzzle = generate_puzzle(&get_test_options(
+            0,
+            [
+                ClueType::BranchEqual,
+                ClueType::Leaf,
+                ClueType::Internal,
+                ClueType::Cycle,
+            ]
+            .to_vec(),
+        ))
+        .unwrap();
+
+        assert!(puzzle.sets.is_empty());
+        assert!(puzzle
+            .nodes
+            .iter()
+            .all(|node| node.connected_conditions.is_empty()));
+    }
+
+    #[test]
+    fn test_generate_puzzle_single_node_returns_error() {
+        let mut options = get_test_options(0, get_all_clue_types());
+        options.width = 1;
+        options.height = 1;
+
+        assert_eq!(
+            generate_puzzle(&options).unwrap_err(),
+            GenerationError::BoardTooSmall
+        );
+    }
+}
@@ -0,0 +1,223 @@
+pub mod hint {
+    use crate::{
+        logic::puzzle::{
+            solution_check::solution_check::is_solution_valid,
+            solver::solver::{get_candidate_lines, solve_from, solve_with_limit},
+        },
+        structs::immutable::{
+            game_line::game_line::GameLine, puzzle::puzzle::Puzzle, solution::solution::Solution,
+        },
+    };
+
+    /// Most solutions compared with drawn lines which no solution contains all of, when every
+    /// drawn line is in some solution.
+    const HINT_SOLUTION_LIMIT: usize = 16;
+
+    /// A single line the player should change to get closer to a solution.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Hint {
+        /// Every valid completion of the drawn lines contains this line.
+        Draw(GameLine),
+        /// No valid completion of the drawn lines contains this undrawn line.
+        Avoid(GameLine),
+        /// This drawn line keeps the drawn lines from being completed.
+        Remove(GameLine),
+    }
+
+    /// Finds a line whose state is forced, given the lines the player has drawn.
+    ///
+    /// The completions of the drawn lines are the solutions containing all of them. If there are
+    /// none, the drawn lines contain a mistake and a drawn line is hinted for removal. Otherwise
+    /// a line every completion contains is hinted to be drawn, or failing that, a line no
+    /// completion contains is hinted to be avoided. Lines are only hinted once the solver proves
+    /// it, by finding no completion which disagrees with the hint.
+    ///
+    /// # Parameters
+    ///
+    /// - `puzzle`: The puzzle being played.
+    /// - `drawn`: The lines the player has drawn.
+    ///
+    /// # Returns
+    ///
+    /// Returns the hint, or None if the puzzle is solved or unsolvable, or no line is forced.
+    pub fn find_hint(puzzle: &Puzzle, drawn: &Solution) -> Option<Hint> {
+        let Some(completion) = solve_from(puzzle, drawn, 1).pop() else {
+            return get_removal_hint(puzzle, drawn);
+        };
+        if is_solution_valid(puzzle, drawn) {
+            return None;
+        }
+
+        let is_undecided =
+            |line: &GameLine| !drawn.contains(line) && !puzzle.fixed_lines.contains(line);
+        let mut avoiding = puzzle.clone();
+        let forced_line = completion.iter().find(|line| {
+            if !is_undecided(line) {
+                return false;
+            }
+            avoiding.forbidden_lines.push(**line);
+            let is_forced = solve_from(&avoiding, drawn, 1).is_empty();
+            avoiding.forbidden_lines.pop();
+            is_forced
+        });
+        if let Some(line) = forced_line {
+            return Some(Hint::Draw(*line));
+        }
+
+        get_candidate_lines(puzzle)
+            .into_iter()
+            .filter(|line| is_undecided(line) && !completion.contains(line))
+            .find(|line| !has_completion_with(puzzle, drawn, line))
+            .map(Hint::Avoid)
+    }
+
+    /// Returns a drawn line to remove when no solution contains every drawn line. A drawn line
+    /// which no solution contains is preferred. Otherwise solving for every solution could take
+    /// too long, so a drawn line is picked which the closest of the first few solutions lacks.
+    fn get_removal_hint(puzzle: &Puzzle, drawn: &Solution) -> Option<Hint> {
+        if let Some(line) = drawn
+            .iter()
+            .filter(|line| !puzzle.fixed_lines.contains(line))
+            .find(|line| !has_completion_with(puzzle, &Solution::new(), line))
+        {
+            return Some(Hint::Remove(*line));
+        }
+        get_closest_missing_line(&solve_with_limit(puzzle, HINT_SOLUTION_LIMIT), drawn)
+            .map(Hint::Remove)
+    }
+
+    /// Returns true if some completion of the drawn lines also contains the line.
+    fn has_completion_with(puzzle: &Puzzle, drawn: &Solution, line: &GameLine) -> bool {
+        let mut lines = drawn.clone();
+        lines.insert(*line);
+        !solve_from(puzzle, &lines, 1).is_empty()
+    }
+
+    /// Returns a drawn line which the solution sharing the most drawn lines lacks, taking the
+    /// first such solution so hints are stable.
+    ///
+    /// # Parameters
+    ///
+    /// - `solutions`: Solutions to the puzzle to compare the drawn lines with.
+    /// - `drawn`: The lines the player has drawn.
+    ///
+    /// # Returns
+    ///
+    /// Returns the first drawn line the closest solution lacks, or None if there are no
+    /// solutions or the closest contains every drawn line.
+    pub fn get_closest_missing_line(solutions: &[Solution], drawn: &Solution) -> Option<GameLine> {
+        let closest = solutions.iter().reduce(|closest, solution| {
+            if count_shared_lines(solution, drawn) > count_shared_lines(closest, drawn) {
+                solution
+            } else {
+                closest
+            }
+        })?;
+        drawn.iter().find(|line| !closest.contains(line)).copied()
+    }
+
+    /// Returns the number of drawn lines the solution contains.
+    fn count_shared_lines(solution: &Solution, drawn: &Solution) -> usize {
+        drawn.iter().filter(|line| solution.contains(line)).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        logic::puzzle::hint::hint::{find_hint, get_closest_missing_line, Hint},
+        structs::immutable::{
+            game_line::game_line::GameLine, node_condition::node_condition::NodeCondition,
+            puzzle::puzzle::Puzzle, solution::solution::Solution,
+        },
+    };
+
+    fn get_test_line(node_a_id: u16, node_b_id: u16) -> GameLine {
+        GameLine::new(node_a_id, node_b_id).unwrap()
+    }
+
+    /// Returns a 2x2 puzzle whose nodes other than node 3 are leaves, solved only by the star
+    /// around node 3.
+    fn get_test_puzzle() -> Puzzle {
+        let mut puzzle = Puzzle::new(2, 2);
+        for node in puzzle.nodes.iter_mut().filter(|node| node.id != 3) {
+            node.conditions = [NodeCondition::Leaf].to_vec();
+        }
+        puzzle
+    }
+
+    #[test]
+    fn test_find_hint_no_lines_returns_line_in_every_completion() {
+        assert_eq!(
+            find_hint(&get_test_puzzle(), &Solution::new()),
+            Some(Hint::Draw(get_test_line(0, 3)))
+        );
+    }
+
+    #[test]
+    fn test_find_hint_partial_returns_line_forced_by_drawn_lines() {
+        let drawn = Solution::from([get_test_line(0, 3), get_test_line(1, 3)]);
+
+        assert_eq!(
+            find_hint(&get_test_puzzle(), &drawn),
+            Some(Hint::Draw(get_test_line(2, 3)))
+        );
+    }
+
+    #[test]
+    fn test_find_hint_solved_returns_none() {
+        let drawn = Solution::from([
+            get_test_line(0, 3),
+            get_test_line(1, 3),
+            get_test_line(2, 3),
+        ]);
+
+        assert_eq!(find_hint(&get_test_puzzle(), &drawn), None);
+    }
+
+    #[test]
+    fn test_find_hint_many_completions_without_forced_line_returns_none() {
+        // Lines shared by the first completions found aren't forced, as others lack them
+        assert_eq!(find_hint(&Puzzle::new(2, 2), &Solution::new()), None);
+    }
+
+    #[test]
+    fn test_find_hint_line_in_no_completion_returns_avoid() {
+        // The other diagonal crosses the drawn one, which the puzzle doesn't allow
+        let drawn = Solution::from([get_test_line(0, 3)]);
+
+        assert_eq!(
+            find_hint(&Puzzle::new(2, 2), &drawn),
+            Some(Hint::Avoid(get_test_line(1, 2)))
+        );
+    }
+
+    #[test]
+    fn test_find_hint_wrong_line_returns_remove() {
+        let drawn = Solution::from([get_test_line(0, 3), get_test_line(0, 1)]);
+
+        assert_eq!(
+            find_hint(&get_test_puzzle(), &drawn),
+            Some(Hint::Remove(get_test_line(0, 1)))
+        );
+    }
+
+    #[test]
+    fn test_get_closest_missing_line_lines_from_different_solutions_returns_line() {
+        let solutions = [
+            Solution::from([get_test_line(0, 1), get_test_line(1, 2)]),
+            Solution::from([get_test_line(2, 3), get_test_line(3, 4)]),
+        ];
+        let drawn = Solution::from([get_test_line(0, 1), get_test_line(2, 3)]);
+
+        assert_eq!(
+            get_closest_missing_line(&solutions, &drawn),
+            Some(get_test_line(2, 3))
+        );
+    }
+
+    #[test]
+    fn test_get_closest_missing_line_no_solutions_returns_none() {
+        assert_eq!(get_closest_missing_line(&[], &Solution::new()), None);
+    }
+}
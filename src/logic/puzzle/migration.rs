@@ -0,0 +1,214 @@
+pub mod migration {
+    use std::{error::Error, fmt};
+
+    use serde_json::{Map, Value};
+
+    use crate::structs::immutable::{
+        connected_node_condition::connected_node_condition::CONDITION_CLASS_NAMES,
+        connected_set_rule::connected_set_rule::RULE_CLASS_NAMES, puzzle::puzzle::Puzzle,
+    };
+
+    /// Upgrades a puzzle document from one format version to the next.
+    type Migration = fn(&mut Map<String, Value>) -> Result<(), MigrationError>;
+
+    /// Migrations in order, where the migration at index n upgrades documents of version n.
+    const MIGRATIONS: [Migration; Puzzle::FORMAT_VERSION as usize] = [migrate_v0_to_v1];
+
+    /// Reasons a puzzle document couldn't be upgraded to the current format.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum MigrationError {
+        /// The document isn't a json object.
+        NotAnObject,
+        /// The format version isn't a non-negative integer.
+        InvalidVersion(Value),
+        /// The document was written by a newer version of the game.
+        UnsupportedVersion(u64),
+        /// The document doesn't have the shape its format version expects.
+        Malformed(String),
+    }
+
+    impl fmt::Display for MigrationError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                MigrationError::NotAnObject => write!(f, "puzzle is not a json object"),
+                MigrationError::InvalidVersion(version) => {
+                    write!(f, "invalid format version {}", version)
+                }
+                MigrationError::UnsupportedVersion(version) => write!(
+                    f,
+                    "format version {} is newer than the supported version {}",
+                    version,
+                    Puzzle::FORMAT_VERSION
+                ),
+                MigrationError::Malformed(reason) => write!(f, "malformed puzzle: {}", reason),
+            }
+        }
+    }
+
+    impl Error for MigrationError {}
+
+    /// Upgrades a puzzle document of any earlier format version to the current one, applying
+    /// each migration from its version onwards. Documents without a version predate versioning
+    /// and count as version 0.
+    ///
+    /// # Parameters
+    /// - `document`: Puzzle json as written by any version of the game.
+    ///
+    /// # Returns
+    /// The document in the current format, ready to be deserialized as a `Puzzle`.
+    pub fn migrate_puzzle(mut document: Value) -> Result<Value, MigrationError> {
+        let object = document
+            .as_object_mut()
+            .ok_or(MigrationError::NotAnObject)?;
+        let version = match object.get("format_version") {
+            None => 0,
+            Some(version) => version
+                .as_u64()
+                .ok_or_else(|| MigrationError::InvalidVersion(version.clone()))?,
+        };
+        if version > Puzzle::FORMAT_VERSION as u64 {
+            return Err(MigrationError::UnsupportedVersion(version));
+        }
+
+        for migration in &MIGRATIONS[version as usize..] {
+            migration(object)?;
+        }
+        object.insert("format_version".to_string(), Puzzle::FORMAT_VERSION.into());
+        Ok(document)
+    }
+
+    /// Version 1 stores condition and rule classes by id rather than by the names of the
+    /// original classes.
+    fn migrate_v0_to_v1(object: &mut Map<String, Value>) -> Result<(), MigrationError> {
+        for node in get_array_mut(object, "nodes")? {
+            let node = node.as_object_mut().ok_or_else(|| malformed("node"))?;
+            rename_classes(node, "connected_conditions", &CONDITION_CLASS_NAMES)?;
+        }
+        for set in get_array_mut(object, "sets")? {
+            let set = set.as_object_mut().ok_or_else(|| malformed("set"))?;
+            rename_classes(set, "connected_rules", &RULE_CLASS_NAMES)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the class names of the connected conditions or rules under the key with their
+    /// ids, where a name's id is its position in the names.
+    fn rename_classes(
+        object: &mut Map<String, Value>,
+        key: &str,
+        names: &[&str],
+    ) -> Result<(), MigrationError> {
+        for connected in get_array_mut(object, key)? {
+            let classes = connected.as_object_mut().ok_or_else(|| malformed(key))?;
+            for class in classes.values_mut() {
+                if let Some(name) = class.as_str() {
+                    let id = names
+                        .iter()
+                        .position(|legacy_name| *legacy_name == name)
+                        .ok_or_else(|| malformed(&format!("unknown class {}", name)))?;
+                    *class = id.into();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the array under the key, or an empty one if the document leaves it out.
+    fn get_array_mut<'a>(
+        object: &'a mut Map<String, Value>,
+        key: &str,
+    ) -> Result<&'a mut [Value], MigrationError> {
+        match object.get_mut(key) {
+            None => Ok(&mut []),
+            Some(Value::Array(values)) => Ok(values),
+            Some(_) => Err(malformed(key)),
+        }
+    }
+
+    fn malformed(what: &str) -> MigrationError {
+        MigrationError::Malformed(format!("invalid {}", what))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use serde_json::json;
+    use walkdir::WalkDir;
+
+    use crate::{
+        logic::puzzle::migration::migration::{migrate_puzzle, MigrationError},
+        puzzle_manager::read_puzzle,
+        structs::immutable::puzzle::puzzle::Puzzle,
+    };
+
+    #[test]
+    fn test_migrate_puzzle_unversioned_returns_class_ids() {
+        let document = json!({
+            "nodes": [{ "id": 0, "connected_conditions": [{ "DegreeEqual": "Green" }] }],
+            "sets": [{
+                "id": 0,
+                "connected_rules": [{ "Homomorphic": "Orange" }, { "Isomorphic": 4 }]
+            }]
+        });
+
+        let migrated = migrate_puzzle(document).unwrap();
+
+        assert_eq!(migrated["format_version"], Puzzle::FORMAT_VERSION);
+        assert_eq!(
+            migrated["nodes"][0]["connected_conditions"],
+            json!([{ "DegreeEqual": 2 }])
+        );
+        assert_eq!(
+            migrated["sets"][0]["connected_rules"],
+            json!([{ "Homomorphic": 1 }, { "Isomorphic": 4 }])
+        );
+    }
+
+    #[test]
+    fn test_migrate_puzzle_unknown_class_returns_error() {
+        let document =
+            json!({ "nodes": [{ "connected_conditions": [{ "DegreeEqual": "Cyan" }] }] });
+
+        assert!(matches!(
+            migrate_puzzle(document),
+            Err(MigrationError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn test_migrate_puzzle_newer_version_returns_error() {
+        let version = Puzzle::FORMAT_VERSION as u64 + 1;
+
+        assert_eq!(
+            migrate_puzzle(json!({ "format_version": version })),
+            Err(MigrationError::UnsupportedVersion(version))
+        );
+    }
+
+    #[test]
+    fn test_all_campaign_puzzles_round_trip() {
+        let mut count = 0;
+        for entry in WalkDir::new(Path::new("assets/campaign/puzzles/"))
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+        {
+            let puzzle = read_puzzle(entry.path()).unwrap();
+            let document = serde_json::to_value(&puzzle).unwrap();
+            let reloaded: Puzzle =
+                serde_json::from_value(migrate_puzzle(document).unwrap()).unwrap();
+
+            assert_eq!(reloaded.format_version, Puzzle::FORMAT_VERSION);
+            assert_eq!(
+                serde_json::to_value(&reloaded).unwrap(),
+                serde_json::to_value(&puzzle).unwrap(),
+                "{} changed after a round trip",
+                entry.path().display()
+            );
+            count += 1;
+        }
+        assert!(count > 0);
+    }
+}
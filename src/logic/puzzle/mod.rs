@@ -1,2 +1,10 @@
+pub mod deduction;
+pub mod evaluation;
+pub mod generator;
+pub mod hint;
+pub mod migration;
 pub mod solution_check;
+pub mod solver;
 pub mod tile_placement;
+pub mod uniqueness;
+pub mod validation;
@@ -1,6 +1,10 @@
 pub mod solution_check {
-    use crate::structs::active::{
-        active_node::active_node::ActiveNode, active_set::active_set::ActiveSet,
+    use crate::{
+        logic::puzzle::evaluation::evaluation::evaluate,
+        structs::{
+            active::{active_node::active_node::ActiveNode, active_set::active_set::ActiveSet},
+            immutable::{puzzle::puzzle::Puzzle, solution::solution::Solution},
+        },
     };
 
     /// Checks if a puzzle is solved based on its active state.
@@ -21,8 +25,16 @@ pub mod solution_check {
             }
         }
         for active_set in active_sets {
-            for active_set_rule in &active_set.active_set_rules {
-                if !active_set_rule.satisfied {
+            // Meta rules decide how the set rules combine, so they replace the set rules if present
+            if active_set.active_meta_set_rules.is_empty() {
+                for active_set_rule in &active_set.active_set_rules {
+                    if !active_set_rule.satisfied {
+                        return false;
+                    }
+                }
+            }
+            for active_meta_set_rule in &active_set.active_meta_set_rules {
+                if !active_meta_set_rule.satisfied {
                     return false;
                 }
             }
@@ -34,4 +46,102 @@ pub mod solution_check {
         }
         true
     }
+
+    /// Checks if a solution solves a puzzle using only its immutable state, so it can be used
+    /// without a puzzle being displayed.
+    pub fn is_solution_valid(puzzle: &Puzzle, solution: &Solution) -> bool {
+        evaluate(puzzle, solution).is_solved()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        logic::puzzle::solution_check::solution_check::is_solution_valid,
+        structs::immutable::{
+            game_line::game_line::GameLine, game_set::game_set::GameSet,
+            node_condition::node_condition::NodeCondition, puzzle::puzzle::Puzzle,
+            set_rule::set_rule::SetRule, solution::solution::Solution,
+        },
+    };
+
+    fn get_test_puzzle(allow_crossings: bool) -> Puzzle {
+        Puzzle {
+            allow_crossings,
+            ..Puzzle::new(2, 2)
+        }
+    }
+
+    fn get_crossing_solution() -> Solution {
+        Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(0, 3).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+        ])
+    }
+
+    #[test]
+    fn test_is_solution_valid_crossing_lines_returns_false() {
+        let puzzle = get_test_puzzle(false);
+
+        assert!(!is_solution_valid(&puzzle, &get_crossing_solution()));
+    }
+
+    #[test]
+    fn test_is_solution_valid_crossing_lines_allowed_returns_true() {
+        let puzzle = get_test_puzzle(true);
+
+        assert!(is_solution_valid(&puzzle, &get_crossing_solution()));
+    }
+
+    #[test]
+    fn test_is_solution_valid_non_crossing_diagonals_returns_true() {
+        let puzzle = get_test_puzzle(false);
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(0, 3).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+        ]);
+
+        assert!(is_solution_valid(&puzzle, &solution));
+    }
+
+    fn get_test_scoped_puzzle(rules: Vec<SetRule>) -> Puzzle {
+        let mut puzzle = get_test_puzzle(false);
+        puzzle.nodes[0].conditions = [NodeCondition::Leaf].to_vec();
+        puzzle.sets = [GameSet {
+            id: 0,
+            nodes: [0, 1].to_vec(),
+            rules,
+            connected_rules: Vec::new(),
+            meta_rules: Vec::new(),
+            bounded: false,
+        }]
+        .to_vec();
+        puzzle
+    }
+
+    fn get_scoped_solution() -> Solution {
+        // Node 0 has two lines, but only one of them stays within the set
+        Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(0, 2).unwrap(),
+            GameLine::new(2, 3).unwrap(),
+        ])
+    }
+
+    #[test]
+    fn test_is_solution_valid_scoped_condition_returns_true() {
+        let puzzle = get_test_scoped_puzzle([SetRule::Scope].to_vec());
+
+        assert!(is_solution_valid(&puzzle, &get_scoped_solution()));
+    }
+
+    #[test]
+    fn test_is_solution_valid_unscoped_condition_returns_false() {
+        let puzzle = get_test_scoped_puzzle(Vec::new());
+
+        assert!(!is_solution_valid(&puzzle, &get_scoped_solution()));
+    }
 }
@@ -0,0 +1,860 @@
+pub mod solver {
+    use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
+
+    use crate::{
+        logic::{
+            connected_condition_checks::connected_condition_checks::has_single_path,
+            connected_rule_checks::connected_rule_checks::HomomorphismCache,
+            puzzle::solution_check::solution_check::is_solution_valid,
+            rule_checks::rule_checks::{is_acyclic, is_connected},
+            util::{
+                get_adjacent_nodes, get_bounded_solutions, get_connected_condition_groups,
+                get_connected_rule_groups, get_crossing_line, get_opposite_node_pairs,
+            },
+        },
+        structs::immutable::{
+            connected_node_condition::connected_node_condition::ConnectedNodeCondition,
+            connected_set_rule::connected_set_rule::ConnectedSetRule,
+            game_line::game_line::GameLine,
+            game_node::game_node::{GameNode, GameNodeId},
+            game_set::game_set::GameSet,
+            meta_set_rule::meta_set_rule::MetaSetRule,
+            node_condition::node_condition::NodeCondition,
+            puzzle::puzzle::Puzzle,
+            set_rule::set_rule::SetRule,
+            solution::solution::{AdjacencyMatrix, Solution},
+        },
+    };
+
+    /// Returns every solution to the puzzle.
+    pub fn solve(puzzle: &Puzzle) -> Vec<Solution> {
+        solve_with_limit(puzzle, usize::MAX)
+    }
+
+    /// Returns solutions to the puzzle, stopping once `limit` solutions have been found.
+    ///
+    /// # Parameters
+    ///
+    /// - `puzzle`: The puzzle to solve.
+    /// - `limit`: The maximum number of solutions to return.
+    ///
+    /// # Returns
+    ///
+    /// Up to `limit` solutions, each with its lines ordered by node ids.
+    pub fn solve_with_limit(puzzle: &Puzzle, limit: usize) -> Vec<Solution> {
+        solve_from(puzzle, &Solution::new(), limit)
+    }
+
+    /// Returns completions of the drawn lines, i.e. solutions to the puzzle containing every
+    /// one of them, stopping once `limit` completions have been found.
+    ///
+    /// # Parameters
+    ///
+    /// - `puzzle`: The puzzle to solve.
+    /// - `drawn`: The lines every completion must contain.
+    /// - `limit`: The maximum number of completions to return.
+    ///
+    /// # Returns
+    ///
+    /// Up to `limit` completions, each with its lines ordered by node ids.
+    pub fn solve_from(puzzle: &Puzzle, drawn: &Solution, limit: usize) -> Vec<Solution> {
+        let mut solutions: Vec<Solution> = Vec::new();
+        if limit == 0 {
+            return solutions;
+        }
+
+        // Fixed and drawn lines are there from the start, leaving only the other lines to decide
+        let mut partial =
+            Solution::from_lines_unchecked(puzzle.fixed_lines.iter().chain(drawn).cloned());
+        let mut search = Search::new(puzzle, &partial);
+        if search.is_feasible(0, &partial) {
+            search.search(0, &mut partial, &mut solutions, limit);
+        }
+        solutions
+    }
+
+    /// Returns every line which may be drawn in the puzzle (between adjacent nodes, and not
+    /// forbidden), ordered by the smallest node id, then the largest node id.
+    pub fn get_candidate_lines(puzzle: &Puzzle) -> Vec<GameLine> {
+        let node_ids: HashSet<GameNodeId> = puzzle.nodes.iter().map(|node| node.id).collect();
+
+        let mut lines: Vec<GameLine> = Vec::new();
+        let mut ordered_ids: Vec<GameNodeId> = node_ids.iter().cloned().collect();
+        ordered_ids.sort();
+        for node_id in ordered_ids {
+            let mut neighbors: Vec<GameNodeId> = get_adjacent_nodes(&node_id, puzzle)
+                .into_iter()
+                .filter(|neighbor| *neighbor > node_id && node_ids.contains(neighbor))
+                .collect();
+            neighbors.sort();
+            lines.extend(
+                neighbors
+                    .into_iter()
+                    .filter_map(|neighbor| GameLine::new(node_id, neighbor).ok())
+                    .filter(|line| !puzzle.forbidden_lines.contains(line)),
+            );
+        }
+        lines
+    }
+
+    fn get_degree(adj_matrix: &AdjacencyMatrix, node_id: &GameNodeId) -> usize {
+        adj_matrix
+            .get(node_id)
+            .map_or(0, |neighbors| neighbors.len())
+    }
+
+    /// Returns the shortest distance from the start node to every node reachable from it.
+    fn get_distances(
+        adj_matrix: &AdjacencyMatrix,
+        start: GameNodeId,
+    ) -> HashMap<GameNodeId, usize> {
+        let mut distances: HashMap<GameNodeId, usize> = HashMap::from([(start, 0)]);
+        let mut queue: VecDeque<GameNodeId> = VecDeque::from([start]);
+        while let Some(curr_node) = queue.pop_front() {
+            let distance = distances[&curr_node];
+            for &neighbor in adj_matrix.get(&curr_node).into_iter().flatten() {
+                if let Entry::Vacant(entry) = distances.entry(neighbor) {
+                    entry.insert(distance + 1);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Returns true if the network containing the node has a cycle.
+    fn is_in_cyclic_network(adj_matrix: &AdjacencyMatrix, node_id: &GameNodeId) -> bool {
+        let distances = get_distances(adj_matrix, *node_id);
+        let line_count: usize = distances
+            .keys()
+            .map(|id| get_degree(adj_matrix, id))
+            .sum::<usize>()
+            / 2;
+        line_count >= distances.len()
+    }
+
+    /// Returns true if the node lies on a cycle, meaning one of its neighbors can still be
+    /// reached after removing the line between them.
+    fn is_on_cycle(adj_matrix: &AdjacencyMatrix, node_id: &GameNodeId) -> bool {
+        adj_matrix
+            .get(node_id)
+            .into_iter()
+            .flatten()
+            .any(|&neighbor| {
+                let mut visited: HashSet<GameNodeId> = HashSet::from([*node_id, neighbor]);
+                let mut queue: VecDeque<GameNodeId> = VecDeque::from([neighbor]);
+                while let Some(curr_node) = queue.pop_front() {
+                    for &next in adj_matrix.get(&curr_node).into_iter().flatten() {
+                        if next == *node_id && curr_node != neighbor {
+                            return true;
+                        }
+                        if visited.insert(next) {
+                            queue.push_back(next);
+                        }
+                    }
+                }
+                false
+            })
+    }
+
+    /// Returns false if the condition can't hold for any solution containing every drawn line
+    /// and contained by the optimistic solution (drawn plus undecided lines).
+    fn is_condition_feasible(
+        condition: &NodeCondition,
+        node: &GameNode,
+        drawn: &AdjacencyMatrix,
+        optimistic: &AdjacencyMatrix,
+        puzzle: &Puzzle,
+    ) -> bool {
+        match condition {
+            NodeCondition::BranchEqual => {
+                get_degree(optimistic, &node.id) > 0 && !is_in_cyclic_network(drawn, &node.id)
+            }
+            NodeCondition::Leaf => {
+                get_degree(drawn, &node.id) <= 1 && get_degree(optimistic, &node.id) >= 1
+            }
+            NodeCondition::Internal => get_degree(optimistic, &node.id) > 1,
+            NodeCondition::Cycle => is_on_cycle(optimistic, &node.id),
+            NodeCondition::Degree(degree) => {
+                get_degree(drawn, &node.id) <= *degree as usize
+                    && get_degree(optimistic, &node.id) >= *degree as usize
+            }
+            NodeCondition::CutVertex => get_degree(optimistic, &node.id) > 1,
+            NodeCondition::Straight => {
+                // Some opposite pair must still be drawable and hold every drawn line
+                let drawn_neighbors = drawn.get(&node.id).cloned().unwrap_or_default();
+                let optimistic_neighbors = optimistic.get(&node.id).cloned().unwrap_or_default();
+                get_opposite_node_pairs(&node.id, puzzle)
+                    .iter()
+                    .any(|(node_a, node_b)| {
+                        optimistic_neighbors.contains(node_a)
+                            && optimistic_neighbors.contains(node_b)
+                            && drawn_neighbors
+                                .iter()
+                                .all(|neighbor| neighbor == node_a || neighbor == node_b)
+                    })
+            }
+        }
+    }
+
+    /// Returns false if the rule can't hold for any solution between the drawn and optimistic
+    /// solutions.
+    fn is_rule_feasible(
+        rule: &SetRule,
+        set: &GameSet,
+        drawn: &Solution,
+        optimistic: &Solution,
+    ) -> bool {
+        let count_external = |solution: &Solution| {
+            solution
+                .iter()
+                .filter(|line| {
+                    set.nodes.contains(&line.node_a_id()) != set.nodes.contains(&line.node_b_id())
+                })
+                .count()
+        };
+        match rule {
+            SetRule::Disconnected => !drawn.iter().any(|line| {
+                set.nodes.contains(&line.node_a_id()) && set.nodes.contains(&line.node_b_id())
+            }),
+            SetRule::Leaf => count_external(drawn) <= 1 && count_external(optimistic) >= 1,
+            SetRule::Scope => true,
+            // Lines only join groups from drawn to optimistic, and cycles once drawn stay
+            SetRule::Connected => is_connected(set, optimistic).satisfied,
+            SetRule::Acyclic => is_acyclic(set, drawn).satisfied,
+            SetRule::EdgeCount(count) => {
+                drawn.filter_to_set(set).len() <= *count as usize
+                    && optimistic.filter_to_set(set).len() >= *count as usize
+            }
+            SetRule::Closed => count_external(drawn) == 0,
+        }
+    }
+
+    /// Returns false if the connected condition can't hold over its nodes for any solution
+    /// between the drawn and optimistic solutions.
+    fn is_connected_condition_feasible(
+        con_cdtn: &ConnectedNodeCondition,
+        nodes: &[&GameNode],
+        drawn: &AdjacencyMatrix,
+        optimistic: &AdjacencyMatrix,
+    ) -> bool {
+        match con_cdtn {
+            ConnectedNodeCondition::DegreeEqual(_) => {
+                // Degrees only grow from drawn to optimistic, so a common degree must fit both.
+                let max_drawn = nodes.iter().map(|node| get_degree(drawn, &node.id)).max();
+                let min_optimistic = nodes
+                    .iter()
+                    .map(|node| get_degree(optimistic, &node.id))
+                    .min();
+                match (max_drawn, min_optimistic) {
+                    (Some(max_drawn), Some(min_optimistic)) => {
+                        min_optimistic > 0 && max_drawn <= min_optimistic
+                    }
+                    _ => true,
+                }
+            }
+            ConnectedNodeCondition::DistanceEqual(_) => {
+                if nodes.len() == 1 {
+                    return get_degree(optimistic, &nodes[0].id) > 0;
+                }
+                // Distances only shrink from drawn to optimistic, so a common distance must be
+                // at least every optimistic distance and at most every drawn distance.
+                let mut max_optimistic: usize = 0;
+                let mut min_drawn: usize = usize::MAX;
+                for node_u in nodes {
+                    let optimistic_distances = get_distances(optimistic, node_u.id);
+                    let drawn_distances = get_distances(drawn, node_u.id);
+                    for node_v in nodes.iter().filter(|node_v| node_v.id != node_u.id) {
+                        match optimistic_distances.get(&node_v.id) {
+                            Some(distance) => max_optimistic = max_optimistic.max(*distance),
+                            None => return false,
+                        }
+                        if let Some(distance) = drawn_distances.get(&node_v.id) {
+                            min_drawn = min_drawn.min(*distance);
+                        }
+                    }
+                }
+                max_optimistic <= min_drawn
+            }
+            ConnectedNodeCondition::DegreeDistinct(_) => {
+                // Every node needs its own degree between its drawn and optimistic degree.
+                can_be_distinct(
+                    nodes
+                        .iter()
+                        .map(|node| {
+                            (
+                                get_degree(drawn, &node.id).max(1),
+                                get_degree(optimistic, &node.id),
+                            )
+                        })
+                        .collect(),
+                )
+            }
+            ConnectedNodeCondition::DistanceDistinct(_) => {
+                if nodes.len() == 1 {
+                    return get_degree(optimistic, &nodes[0].id) > 0;
+                }
+                // Every pair needs its own distance between its optimistic and drawn distance.
+                let mut ranges: Vec<(usize, usize)> = Vec::new();
+                for (idx, node_u) in nodes.iter().enumerate() {
+                    let optimistic_distances = get_distances(optimistic, node_u.id);
+                    let drawn_distances = get_distances(drawn, node_u.id);
+                    for node_v in nodes.iter().skip(idx + 1) {
+                        let Some(min) = optimistic_distances.get(&node_v.id) else {
+                            return false;
+                        };
+                        let max = drawn_distances.get(&node_v.id).unwrap_or(&usize::MAX);
+                        ranges.push((*min, *max));
+                    }
+                }
+                can_be_distinct(ranges)
+            }
+            // Paths only appear as lines are drawn, so it's enough for the optimistic lines
+            ConnectedNodeCondition::SameComponentPath(_) => has_single_path(nodes, optimistic),
+        }
+    }
+
+    /// Returns true if a different value can be picked from each inclusive range.
+    fn can_be_distinct(mut ranges: Vec<(usize, usize)>) -> bool {
+        // Ranges ending first take the smallest value left to them
+        ranges.sort_by_key(|(_min, max)| *max);
+        let mut taken: HashSet<usize> = HashSet::new();
+        ranges.iter().all(|(min, max)| {
+            let mut value = *min;
+            while taken.contains(&value) {
+                value += 1;
+            }
+            value <= *max && taken.insert(value)
+        })
+    }
+
+    /// Depth first search over the candidate lines of a puzzle, deciding for each line whether it
+    /// is drawn or not. After every decision the search is pruned if some check can no longer
+    /// hold, given the drawn lines and the lines still undecided. Connected rules are checked
+    /// once every line within their sets is decided, and complete solutions are verified in full.
+    struct Search<'a> {
+        puzzle: &'a Puzzle,
+        /// Candidate lines other than those drawn from the start, with lines within sets of
+        /// connected rules first so those rules are decided early.
+        lines: Vec<GameLine>,
+        /// Connected rules which become decidable once the line at the same index is decided.
+        rules_after_line: Vec<Vec<ConnectedSetRule>>,
+        /// Results of connected rules, as the same lines within sets are checked many times.
+        homomorphisms: HomomorphismCache,
+    }
+
+    impl<'a> Search<'a> {
+        fn new(puzzle: &'a Puzzle, start: &Solution) -> Self {
+            let rule_groups = get_connected_rule_groups(&puzzle.sets);
+            let is_within_rule_set = |line: &GameLine| {
+                rule_groups.values().flatten().any(|set| {
+                    set.nodes.contains(&line.node_a_id()) && set.nodes.contains(&line.node_b_id())
+                })
+            };
+
+            let (mut lines, other_lines): (Vec<GameLine>, Vec<GameLine>) =
+                get_candidate_lines(puzzle)
+                    .into_iter()
+                    .filter(|line| !start.contains(line))
+                    .partition(is_within_rule_set);
+            lines.extend(other_lines);
+
+            let mut rules_after_line: Vec<Vec<ConnectedSetRule>> = vec![Vec::new(); lines.len()];
+            for (con_rule, sets) in rule_groups.iter() {
+                let closing_line = lines.iter().rposition(|line| {
+                    sets.iter().any(|set| {
+                        set.nodes.contains(&line.node_a_id())
+                            && set.nodes.contains(&line.node_b_id())
+                    })
+                });
+                // Rules without any lines within their sets, or depending on lines outside them,
+                // are left to the final verification.
+                if let Some(idx) = closing_line.filter(|_| con_rule.is_within_sets()) {
+                    rules_after_line[idx].push(*con_rule);
+                }
+            }
+
+            Search {
+                puzzle,
+                lines,
+                rules_after_line,
+                homomorphisms: HomomorphismCache::new(),
+            }
+        }
+
+        fn search(
+            &mut self,
+            idx: usize,
+            partial: &mut Solution,
+            solutions: &mut Vec<Solution>,
+            limit: usize,
+        ) {
+            if solutions.len() >= limit {
+                return;
+            }
+
+            if idx == self.lines.len() {
+                if is_solution_valid(self.puzzle, partial) {
+                    solutions.push(partial.clone());
+                }
+                return;
+            }
+
+            // Try drawing the line first (unless it would cross a drawn line), then leaving it out.
+            let line = self.lines[idx];
+            partial.insert(line);
+            if !self.is_crossing(&line, partial) && self.passes_checks(idx, partial) {
+                self.search(idx + 1, partial, solutions, limit);
+            }
+            partial.remove(&line);
+
+            if self.passes_checks(idx, partial) {
+                self.search(idx + 1, partial, solutions, limit);
+            }
+        }
+
+        /// Returns true if the line crosses a drawn line, when the puzzle doesn't allow it.
+        fn is_crossing(&self, line: &GameLine, partial: &Solution) -> bool {
+            !self.puzzle.allow_crossings
+                && get_crossing_line(line, self.puzzle)
+                    .is_some_and(|crossing| partial.contains(&crossing))
+        }
+
+        /// Runs every check after deciding the line at `idx`.
+        fn passes_checks(&mut self, idx: usize, partial: &Solution) -> bool {
+            let puzzle = self.puzzle;
+            let homomorphisms = &mut self.homomorphisms;
+            self.rules_after_line[idx].iter().all(|con_rule| {
+                let sets = puzzle
+                    .sets
+                    .iter()
+                    .filter(|set| set.connected_rules.contains(con_rule))
+                    .collect();
+                con_rule
+                    .check_with_cache(sets, partial, puzzle, homomorphisms)
+                    .satisfied
+            }) && self.is_feasible(idx + 1, partial)
+        }
+
+        /// Checks that the puzzle may still be solved by drawing some of the lines from
+        /// `next_idx` onwards.
+        ///
+        /// # Parameters
+        ///
+        /// - `next_idx`: Index of the first undecided line.
+        /// - `partial`: Lines drawn so far.
+        ///
+        /// # Returns
+        ///
+        /// Returns false if no solution can be reached, otherwise true.
+        fn is_feasible(&self, next_idx: usize, partial: &Solution) -> bool {
+            let optimistic = Solution::from_lines_unchecked(
+                partial.iter().chain(self.lines[next_idx..].iter()).cloned(),
+            );
+
+            let optimistic_matrix = optimistic.adjacency();
+            if !self.puzzle.nodes.is_empty() {
+                let reachable = get_distances(&optimistic_matrix, self.puzzle.nodes[0].id);
+                if reachable.len() != self.puzzle.nodes.len() {
+                    return false;
+                }
+            }
+
+            let drawn_matrix = partial.adjacency();
+            self.puzzle
+                .nodes
+                .iter()
+                .filter(|node| !node.conditions.is_empty())
+                .all(|node| {
+                    let sets: Vec<&GameSet> = self.puzzle.sets.iter().collect();
+                    get_bounded_solutions(&node.id, sets.clone(), partial)
+                        .iter()
+                        .zip(get_bounded_solutions(&node.id, sets, &optimistic).iter())
+                        .all(|(bounded_drawn, bounded_optimistic)| {
+                            let bounded_drawn = bounded_drawn.adjacency();
+                            let bounded_optimistic = bounded_optimistic.adjacency();
+                            node.conditions.iter().all(|condition| {
+                                is_condition_feasible(
+                                    condition,
+                                    node,
+                                    &bounded_drawn,
+                                    &bounded_optimistic,
+                                    self.puzzle,
+                                )
+                            })
+                        })
+                })
+                && self.puzzle.sets.iter().all(|set| {
+                    let mut feasible = set
+                        .rules
+                        .iter()
+                        .map(|rule| is_rule_feasible(rule, set, partial, &optimistic));
+                    // With Xor any single rule may be the one that holds
+                    if set.meta_rules.contains(&MetaSetRule::Xor) {
+                        feasible.any(|rule_feasible| rule_feasible)
+                    } else {
+                        feasible.all(|rule_feasible| rule_feasible)
+                    }
+                })
+                && get_connected_condition_groups(&self.puzzle.nodes)
+                    .iter()
+                    .all(|(con_cdtn, nodes)| {
+                        is_connected_condition_feasible(
+                            con_cdtn,
+                            nodes,
+                            &drawn_matrix,
+                            &optimistic_matrix,
+                        )
+                    })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        logic::{
+            puzzle::{
+                solution_check::solution_check::is_solution_valid,
+                solver::solver::{get_candidate_lines, solve, solve_from, solve_with_limit},
+            },
+            topology::topology::Topology,
+        },
+        structs::immutable::{
+            connected_node_condition::connected_node_condition::{
+                ConditionClass, ConnectedNodeCondition,
+            },
+            game_line::game_line::GameLine,
+            game_set::game_set::GameSet,
+            node_condition::node_condition::NodeCondition,
+            puzzle::puzzle::Puzzle,
+            set_rule::set_rule::SetRule,
+            solution::solution::Solution,
+        },
+    };
+
+    fn get_test_puzzle(width: u8, height: u8, conditions: Vec<Vec<NodeCondition>>) -> Puzzle {
+        let mut puzzle = Puzzle::new(width, height);
+        for node in puzzle.nodes.iter_mut() {
+            node.conditions = conditions
+                .get(node.id as usize)
+                .cloned()
+                .unwrap_or_default();
+        }
+        puzzle
+    }
+
+    #[test]
+    fn test_candidate_lines_two_by_two_returns_all_six_lines() {
+        let puzzle = get_test_puzzle(2, 2, Vec::new());
+
+        assert_eq!(get_candidate_lines(&puzzle).len(), 6);
+    }
+
+    #[test]
+    fn test_candidate_lines_three_by_three_returns_twenty_lines() {
+        let puzzle = get_test_puzzle(3, 3, Vec::new());
+
+        assert_eq!(get_candidate_lines(&puzzle).len(), 20);
+    }
+
+    #[test]
+    fn test_candidate_lines_three_by_three_center_hole_returns_twelve_lines() {
+        let mut puzzle = get_test_puzzle(3, 3, Vec::new());
+        puzzle.nodes.retain(|node| node.id != 4);
+
+        let lines = get_candidate_lines(&puzzle);
+
+        assert_eq!(lines.len(), 12);
+        assert!(lines
+            .iter()
+            .all(|line| line.node_a_id() != 4 && line.node_b_id() != 4));
+    }
+
+    #[test]
+    fn test_candidate_lines_three_by_three_square_4_returns_twelve_lines() {
+        let mut puzzle = get_test_puzzle(3, 3, Vec::new());
+        puzzle.topology = Topology::Square4;
+
+        let lines = get_candidate_lines(&puzzle);
+
+        assert_eq!(lines.len(), 12);
+        assert!(!lines.contains(&GameLine::new(0, 4).unwrap()));
+    }
+
+    #[test]
+    fn test_candidate_lines_three_by_three_hex_returns_sixteen_lines() {
+        let mut puzzle = get_test_puzzle(3, 3, Vec::new());
+        puzzle.topology = Topology::Hex;
+
+        let lines = get_candidate_lines(&puzzle);
+
+        // Even columns reach down into the odd column beside them, odd columns reach up
+        assert_eq!(lines.len(), 16);
+        assert!(lines.contains(&GameLine::new(1, 3).unwrap()));
+        assert!(lines.contains(&GameLine::new(3, 7).unwrap()));
+        assert!(!lines.contains(&GameLine::new(0, 4).unwrap()));
+    }
+
+    #[test]
+    fn test_candidate_lines_three_by_three_toroidal_joins_every_node() {
+        let mut puzzle = get_test_puzzle(3, 3, Vec::new());
+        puzzle.toroidal = true;
+
+        let lines = get_candidate_lines(&puzzle);
+
+        // Every node wraps around to reach the other eight
+        assert_eq!(lines.len(), 36);
+        assert!(lines.contains(&GameLine::new(0, 6).unwrap()));
+        assert!(lines.contains(&GameLine::new(0, 8).unwrap()));
+    }
+
+    #[test]
+    fn test_solve_toroidal_row_of_degree_two_returns_wrapping_cycle() {
+        let mut puzzle = get_test_puzzle(3, 1, vec![[NodeCondition::Degree(2)].to_vec(); 3]);
+        puzzle.toroidal = true;
+
+        let solutions = solve(&puzzle);
+
+        assert_eq!(solutions.len(), 1);
+        assert!(solutions[0].contains(&GameLine::new(0, 2).unwrap()));
+    }
+
+    #[test]
+    fn test_solve_fixed_and_forbidden_lines_returns_solutions_honouring_both() {
+        let fixed = GameLine::new(0, 3).unwrap();
+        let forbidden = GameLine::new(0, 1).unwrap();
+        let mut puzzle = get_test_puzzle(2, 2, Vec::new());
+        puzzle.fixed_lines = [fixed].to_vec();
+        puzzle.forbidden_lines = [forbidden].to_vec();
+
+        let solutions = solve(&puzzle);
+
+        assert!(!solutions.is_empty());
+        assert!(solutions
+            .iter()
+            .all(|solution| solution.contains(&fixed) && !solution.contains(&forbidden)));
+    }
+
+    #[test]
+    fn test_solve_forbidden_only_line_returns_no_solutions() {
+        let mut puzzle = get_test_puzzle(2, 1, Vec::new());
+        puzzle.forbidden_lines = [GameLine::new(0, 1).unwrap()].to_vec();
+
+        assert!(solve(&puzzle).is_empty());
+    }
+
+    #[test]
+    fn test_solve_two_nodes_returns_single_line() {
+        let puzzle = get_test_puzzle(2, 1, Vec::new());
+
+        let solutions = solve(&puzzle);
+
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0], Solution::from([GameLine::new(0, 1).unwrap()]));
+    }
+
+    #[test]
+    fn test_solve_single_node_returns_no_solutions() {
+        let puzzle = get_test_puzzle(1, 1, Vec::new());
+
+        assert!(solve(&puzzle).is_empty());
+    }
+
+    #[test]
+    fn test_solve_two_by_two_no_conditions_returns_all_connected_graphs() {
+        let mut puzzle = get_test_puzzle(2, 2, Vec::new());
+        puzzle.allow_crossings = true;
+
+        // There are 38 connected spanning subgraphs of the complete graph on 4 nodes.
+        assert_eq!(solve(&puzzle).len(), 38);
+    }
+
+    #[test]
+    fn test_solve_two_by_two_no_crossings_returns_graphs_without_both_diagonals() {
+        let puzzle = get_test_puzzle(2, 2, Vec::new());
+
+        let solutions = solve(&puzzle);
+
+        // 15 of the 38 connected graphs contain both diagonals 0-3 and 1-2.
+        assert_eq!(solutions.len(), 23);
+        assert!(solutions.iter().all(|solution| {
+            !solution.contains(&GameLine::new(0, 3).unwrap())
+                || !solution.contains(&GameLine::new(1, 2).unwrap())
+        }));
+    }
+
+    #[test]
+    fn test_solve_two_by_two_corner_hole_returns_connected_triangle_graphs() {
+        let mut puzzle = get_test_puzzle(2, 2, Vec::new());
+        puzzle.nodes.retain(|node| node.id != 3);
+
+        // Diagonal 1-2 has no crossing line, as 0-3 would end in the hole.
+        assert_eq!(solve(&puzzle).len(), 4);
+    }
+
+    #[test]
+    fn test_solve_all_leaves_returns_no_solutions() {
+        let puzzle = get_test_puzzle(
+            2,
+            2,
+            [
+                [NodeCondition::Leaf].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+            ]
+            .to_vec(),
+        );
+
+        assert!(solve(&puzzle).is_empty());
+    }
+
+    #[test]
+    fn test_solve_three_leaves_returns_stars() {
+        let puzzle = get_test_puzzle(
+            2,
+            2,
+            [
+                Vec::new(),
+                [NodeCondition::Leaf].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+            ]
+            .to_vec(),
+        );
+
+        let solutions = solve(&puzzle);
+
+        assert_eq!(solutions.len(), 1);
+        assert!(solutions[0]
+            .iter()
+            .all(|line| line.node_a_id() == 0 || line.node_b_id() == 0));
+    }
+
+    #[test]
+    fn test_solve_disconnected_set_on_path_returns_no_solutions() {
+        let mut puzzle = get_test_puzzle(3, 1, Vec::new());
+        puzzle.sets.push(GameSet {
+            id: 0,
+            nodes: [0, 1].to_vec(),
+            rules: [SetRule::Disconnected].to_vec(),
+            connected_rules: Vec::new(),
+            meta_rules: Vec::new(),
+            bounded: false,
+        });
+
+        // The only lines are 0-1 and 1-2, so the set can't be disconnected and connected.
+        assert!(solve(&puzzle).is_empty());
+    }
+
+    #[test]
+    fn test_solve_with_limit_stops_at_limit() {
+        let puzzle = get_test_puzzle(3, 3, Vec::new());
+
+        assert_eq!(solve_with_limit(&puzzle, 2).len(), 2);
+    }
+
+    #[test]
+    fn test_solve_from_drawn_line_returns_solutions_containing_it() {
+        let puzzle = get_test_puzzle(2, 2, Vec::new());
+        let drawn = Solution::from([GameLine::new(0, 3).unwrap()]);
+
+        let completions = solve_from(&puzzle, &drawn, usize::MAX);
+
+        let expected: Vec<Solution> = solve(&puzzle)
+            .into_iter()
+            .filter(|solution| solution.contains(&GameLine::new(0, 3).unwrap()))
+            .collect();
+        assert_eq!(completions.len(), expected.len());
+        assert!(completions
+            .iter()
+            .all(|solution| expected.contains(solution)));
+    }
+
+    #[test]
+    fn test_solve_every_solution_is_valid() {
+        let puzzle = get_test_puzzle(
+            3,
+            2,
+            [
+                [NodeCondition::Internal].to_vec(),
+                Vec::new(),
+                [NodeCondition::BranchEqual].to_vec(),
+                [NodeCondition::Leaf].to_vec(),
+            ]
+            .to_vec(),
+        );
+
+        let solutions = solve(&puzzle);
+
+        assert!(!solutions.is_empty());
+        assert!(solutions
+            .iter()
+            .all(|solution| is_solution_valid(&puzzle, solution)));
+    }
+
+    #[test]
+    fn test_solve_node_conditions_match_checking_every_graph() {
+        let mut unconstrained = get_test_puzzle(2, 2, Vec::new());
+        unconstrained.allow_crossings = true;
+        let graphs = solve(&unconstrained);
+
+        for condition in [
+            NodeCondition::Leaf,
+            NodeCondition::Internal,
+            NodeCondition::BranchEqual,
+            NodeCondition::Cycle,
+        ] {
+            let mut puzzle = unconstrained.clone();
+            for node in puzzle.nodes.iter_mut().take(2) {
+                node.conditions.push(condition.clone());
+            }
+
+            let expected = graphs
+                .iter()
+                .filter(|graph| is_solution_valid(&puzzle, graph))
+                .count();
+
+            assert_eq!(solve(&puzzle).len(), expected);
+        }
+    }
+
+    #[test]
+    fn test_solve_degree_distinct_on_every_node_returns_no_solutions() {
+        let mut puzzle = get_test_puzzle(2, 2, Vec::new());
+        puzzle.allow_crossings = true;
+        for node in puzzle.nodes.iter_mut() {
+            node.connected_conditions
+                .push(ConnectedNodeCondition::DegreeDistinct(ConditionClass(0)));
+        }
+
+        // Four nodes can't have four different degrees between 1 and 3.
+        assert!(solve(&puzzle).is_empty());
+    }
+
+    #[test]
+    fn test_solve_distinct_conditions_match_checking_every_graph() {
+        let mut unconstrained = get_test_puzzle(2, 2, Vec::new());
+        unconstrained.allow_crossings = true;
+        let graphs = solve(&unconstrained);
+
+        for con_cdtn in [
+            ConnectedNodeCondition::DegreeDistinct(ConditionClass(0)),
+            ConnectedNodeCondition::DistanceDistinct(ConditionClass(0)),
+            ConnectedNodeCondition::SameComponentPath(ConditionClass(0)),
+        ] {
+            let mut puzzle = unconstrained.clone();
+            for node in puzzle.nodes.iter_mut().take(3) {
+                node.connected_conditions.push(con_cdtn);
+            }
+
+            let expected = graphs
+                .iter()
+                .filter(|graph| is_solution_valid(&puzzle, graph))
+                .count();
+
+            assert_eq!(solve(&puzzle).len(), expected);
+        }
+    }
+}
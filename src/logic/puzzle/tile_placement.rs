@@ -20,31 +20,17 @@ pub mod tile_placement {
 
 #[cfg(test)]
 mod tests {
-    use itertools::Itertools;
-    use uuid::Uuid;
-
     use crate::{
         logic::puzzle::tile_placement::tile_placement::get_set_upper_left_node,
         structs::immutable::{
-            game_node::game_node::{GameNode, GameNodeId},
-            game_set::game_set::GameSet,
-            puzzle::puzzle::Puzzle,
+            game_node::game_node::GameNodeId, game_set::game_set::GameSet, puzzle::puzzle::Puzzle,
         },
     };
 
     fn get_test_puzzle(sets: Vec<GameSet>) -> Puzzle {
         Puzzle {
-            uuid: Uuid::new_v4(),
-            width: 4,
-            height: 4,
-            nodes: (0..15)
-                .map(|id| GameNode {
-                    id: id,
-                    conditions: Vec::new(),
-                    connected_conditions: Vec::new(),
-                })
-                .collect_vec(),
-            sets: sets,
+            sets,
+            ..Puzzle::new(4, 4)
         }
     }
 
@@ -54,6 +40,7 @@ mod tests {
             nodes: node_ids,
             rules: Vec::new(),
             connected_rules: Vec::new(),
+            meta_rules: Vec::new(),
             bounded: false,
         }
     }
@@ -0,0 +1,199 @@
+pub mod uniqueness {
+    use std::fmt;
+
+    use itertools::Itertools;
+
+    use crate::{
+        logic::puzzle::solver::solver::solve_with_limit,
+        structs::immutable::{
+            game_line::game_line::GameLine, puzzle::puzzle::Puzzle, solution::solution::Solution,
+        },
+    };
+
+    /// How many solutions a puzzle has, along with the solutions found.
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Uniqueness {
+        /// The puzzle cannot be solved.
+        Unsolvable,
+        /// The puzzle has exactly one solution.
+        Unique(Solution),
+        /// The puzzle has more than one solution, two of which are given.
+        Ambiguous(Solution, Solution),
+    }
+
+    /// Determines if a puzzle has zero, one or many solutions.
+    pub fn check_uniqueness(puzzle: &Puzzle) -> Uniqueness {
+        let mut solutions = solve_with_limit(puzzle, 2).into_iter();
+        match (solutions.next(), solutions.next()) {
+            (None, _) => Uniqueness::Unsolvable,
+            (Some(solution), None) => Uniqueness::Unique(solution),
+            (Some(solution_a), Some(solution_b)) => Uniqueness::Ambiguous(solution_a, solution_b),
+        }
+    }
+
+    fn format_lines(lines: &[&GameLine]) -> String {
+        lines
+            .iter()
+            .map(|line| format!("{}-{}", line.node_a_id(), line.node_b_id()))
+            .join(", ")
+    }
+
+    fn format_solution(solution: &Solution) -> String {
+        format_lines(&solution.iter().collect::<Vec<_>>())
+    }
+
+    impl fmt::Display for Uniqueness {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Uniqueness::Unsolvable => write!(f, "no solutions"),
+                Uniqueness::Unique(solution) => {
+                    write!(f, "unique solution [{}]", format_solution(solution))
+                }
+                Uniqueness::Ambiguous(solution_a, solution_b) => {
+                    let only_a: Vec<&GameLine> = solution_a
+                        .iter()
+                        .filter(|line| !solution_b.contains(line))
+                        .collect();
+                    let only_b: Vec<&GameLine> = solution_b
+                        .iter()
+                        .filter(|line| !solution_a.contains(line))
+                        .collect();
+                    write!(
+                        f,
+                        "multiple solutions, e.g. [{}] and [{}] (differing lines [{}] vs [{}])",
+                        format_solution(solution_a),
+                        format_solution(solution_b),
+                        format_lines(&only_a),
+                        format_lines(&only_b),
+                    )
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{
+        logic::puzzle::uniqueness::uniqueness::{check_uniqueness, Uniqueness},
+        puzzle_manager::PuzzleManager,
+        structs::immutable::{
+            game_line::game_line::GameLine, node_condition::node_condition::NodeCondition,
+            puzzle::puzzle::Puzzle, solution::solution::Solution,
+        },
+    };
+
+    fn get_test_puzzle(width: u8, height: u8, conditions: Vec<Vec<NodeCondition>>) -> Puzzle {
+        let mut puzzle = Puzzle::new(width, height);
+        for node in puzzle.nodes.iter_mut() {
+            node.conditions = conditions
+                .get(node.id as usize)
+                .cloned()
+                .unwrap_or_default();
+        }
+        puzzle
+    }
+
+    #[test]
+    fn test_uniqueness_single_node_returns_unsolvable() {
+        let puzzle = get_test_puzzle(1, 1, Vec::new());
+
+        assert_eq!(check_uniqueness(&puzzle), Uniqueness::Unsolvable);
+    }
+
+    #[test]
+    fn test_uniqueness_two_nodes_returns_unique() {
+        let puzzle = get_test_puzzle(2, 1, Vec::new());
+
+        assert_eq!(
+            check_uniqueness(&puzzle),
+            Uniqueness::Unique(Solution::from([GameLine::new(0, 1).unwrap()]))
+        );
+    }
+
+    #[test]
+    fn test_uniqueness_no_conditions_returns_ambiguous_differing_solutions() {
+        let puzzle = get_test_puzzle(2, 2, Vec::new());
+
+        match check_uniqueness(&puzzle) {
+            Uniqueness::Ambiguous(solution_a, solution_b) => assert_ne!(solution_a, solution_b),
+            uniqueness => panic!("Expected ambiguous puzzle, got {}", uniqueness),
+        }
+    }
+
+    /// Campaign puzzles known to have more than one solution, by file name. New puzzles should
+    /// have a unique solution rather than be added here, and puzzles made unique are taken off.
+    const AMBIGUOUS_CAMPAIGN_PUZZLES: [&str; 31] = [
+        "bounded_branch_equal.json",
+        "bounded_branch_equal_internal.json",
+        "bounded_branch_equal_leaf.json",
+        "bounded_internal_1.json",
+        "bounded_leaf_2.json",
+        "branch_equal_2.json",
+        "branch_equal_3.json",
+        "branch_equal_internal.json",
+        "branch_equal_internal_leaf_1.json",
+        "branch_equal_internal_leaf_2.json",
+        "branch_equal_leaf.json",
+        "challenge_1.json",
+        "condition_bridge_1.json",
+        "condition_bridge_2.json",
+        "condition_bridge_3.json",
+        "condition_bridge_4.json",
+        "cycle.json",
+        "cycle_internal.json",
+        "cycle_leaf.json",
+        "degree_equal_3.json",
+        "degree_equal_internal.json",
+        "distance_equal_1.json",
+        "distance_equal_4.json",
+        "distance_equal_leaf.json",
+        "internal_1.json",
+        "internal_2.json",
+        "intro_3.json",
+        "intro_4.json",
+        "leaf_2.json",
+        "puzzle1.json",
+        "puzzle2.json",
+    ];
+
+    #[test]
+    fn test_uniqueness_all_campaign_puzzles() {
+        let mut puzzle_manager = PuzzleManager::new();
+        puzzle_manager.populate_campaign();
+
+        let mut report: Vec<String> = Vec::new();
+        let mut failures: Vec<String> = Vec::new();
+        for uuid in puzzle_manager.get_puzzle_uuids() {
+            let path = puzzle_manager.get_puzzle_path(&uuid).unwrap();
+            let puzzle = puzzle_manager
+                .load_puzzle(&uuid)
+                .unwrap_or_else(|| panic!("Failed to load puzzle {}", path));
+            let file_name = Path::new(path).file_name().unwrap().to_string_lossy();
+            let known_ambiguous = AMBIGUOUS_CAMPAIGN_PUZZLES.contains(&file_name.as_ref());
+
+            let uniqueness = check_uniqueness(&puzzle);
+            // Run with --nocapture to see how each allowed ambiguous puzzle's solutions differ
+            println!("{}: {}", path, uniqueness);
+            report.push(format!("{}: {}", path, uniqueness));
+            match uniqueness {
+                Uniqueness::Unique(_) if known_ambiguous => failures.push(format!(
+                    "{}: {}, so it is no longer known to be ambiguous",
+                    path, uniqueness
+                )),
+                Uniqueness::Ambiguous(_, _) if known_ambiguous => {}
+                Uniqueness::Unique(_) => {}
+                _ => failures.push(format!("{}: {}", path, uniqueness)),
+            }
+        }
+
+        assert!(
+            failures.is_empty(),
+            "Campaign puzzles without the expected solutions:\n{}\n\nAll campaign puzzles:\n{}",
+            failures.join("\n"),
+            report.join("\n")
+        );
+    }
+}
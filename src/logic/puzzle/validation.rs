@@ -0,0 +1,370 @@
+pub mod validation {
+    use std::{collections::HashSet, error::Error, fmt};
+
+    use crate::{
+        logic::{topology::topology::Topology, util::has_crossing_lines},
+        structs::immutable::{
+            game_line::game_line::LineError, game_node::game_node::GameNodeId,
+            puzzle::puzzle::Puzzle, solution::solution::Solution,
+        },
+    };
+
+    /// Reasons a deserialized puzzle can't be played.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum PuzzleValidationError {
+        /// A node id lies outside of the width * height grid.
+        NodeOutOfBounds {
+            node_id: GameNodeId,
+            node_count: u16,
+        },
+        /// More than one node has the same id.
+        DuplicateNode(GameNodeId),
+        /// The puzzle has no nodes, leaving out every position of its grid.
+        NoNodes,
+        /// A toroidal hex board has an odd width, so its shifted columns can't line up when
+        /// wrapping around.
+        OddToroidalHexWidth(u8),
+        /// More than one set has the same id.
+        DuplicateSet(u8),
+        /// A set contains a node id which isn't in the puzzle.
+        UnknownSetNode { set_id: u8, node_id: GameNodeId },
+        /// A set contains no nodes.
+        EmptySet(u8),
+        /// A fixed line can't be drawn, e.g. as it isn't between adjacent nodes or is forbidden.
+        InvalidFixedLine(LineError),
+        /// A forbidden line isn't between adjacent nodes of the puzzle.
+        InvalidForbiddenLine(LineError),
+        /// Fixed lines cross each other, when the puzzle doesn't allow crossings.
+        CrossingFixedLines,
+    }
+
+    impl fmt::Display for PuzzleValidationError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                PuzzleValidationError::NodeOutOfBounds {
+                    node_id,
+                    node_count,
+                } => write!(
+                    f,
+                    "node {} is out of bounds, puzzle only has {} nodes",
+                    node_id, node_count
+                ),
+                PuzzleValidationError::DuplicateNode(node_id) => {
+                    write!(f, "node {} is defined more than once", node_id)
+                }
+                PuzzleValidationError::NoNodes => write!(f, "puzzle has no nodes"),
+                PuzzleValidationError::OddToroidalHexWidth(width) => write!(
+                    f,
+                    "toroidal hex boards need an even width to wrap around, not {}",
+                    width
+                ),
+                PuzzleValidationError::DuplicateSet(set_id) => {
+                    write!(f, "set {} is defined more than once", set_id)
+                }
+                PuzzleValidationError::UnknownSetNode { set_id, node_id } => {
+                    write!(f, "set {} contains unknown node {}", set_id, node_id)
+                }
+                PuzzleValidationError::EmptySet(set_id) => {
+                    write!(f, "set {} has no nodes", set_id)
+                }
+                PuzzleValidationError::InvalidFixedLine(error) => {
+                    write!(f, "invalid fixed line: {}", error)
+                }
+                PuzzleValidationError::InvalidForbiddenLine(error) => {
+                    write!(f, "invalid forbidden line: {}", error)
+                }
+                PuzzleValidationError::CrossingFixedLines => {
+                    write!(f, "fixed lines cross each other")
+                }
+            }
+        }
+    }
+
+    impl Error for PuzzleValidationError {}
+
+    /// Checks that the nodes, sets and fixed or forbidden lines of a puzzle are consistent with
+    /// its grid.
+    ///
+    /// # Parameters
+    ///
+    /// - `puzzle`: The deserialized puzzle to validate.
+    ///
+    /// # Returns
+    ///
+    /// Returns the first problem found with the puzzle, otherwise Ok.
+    pub fn validate_puzzle(puzzle: &Puzzle) -> Result<(), PuzzleValidationError> {
+        let node_count = puzzle.width as u16 * puzzle.height as u16;
+
+        let mut node_ids: HashSet<GameNodeId> = HashSet::new();
+        for node in puzzle.nodes.iter() {
+            if node.id >= node_count {
+                return Err(PuzzleValidationError::NodeOutOfBounds {
+                    node_id: node.id,
+                    node_count,
+                });
+            }
+            if !node_ids.insert(node.id) {
+                return Err(PuzzleValidationError::DuplicateNode(node.id));
+            }
+        }
+        // Positions without a node are holes in the board, as long as some node remains
+        if node_ids.is_empty() {
+            return Err(PuzzleValidationError::NoNodes);
+        }
+        if puzzle.toroidal && puzzle.topology == Topology::Hex && puzzle.width % 2 == 1 {
+            return Err(PuzzleValidationError::OddToroidalHexWidth(puzzle.width));
+        }
+
+        let mut set_ids: HashSet<u8> = HashSet::new();
+        for set in puzzle.sets.iter() {
+            if !set_ids.insert(set.id) {
+                return Err(PuzzleValidationError::DuplicateSet(set.id));
+            }
+            if set.nodes.is_empty() {
+                return Err(PuzzleValidationError::EmptySet(set.id));
+            }
+            if let Some(node_id) = set.nodes.iter().find(|node_id| !node_ids.contains(node_id)) {
+                return Err(PuzzleValidationError::UnknownSetNode {
+                    set_id: set.id,
+                    node_id: *node_id,
+                });
+            }
+        }
+
+        for line in puzzle.forbidden_lines.iter() {
+            puzzle
+                .check_line(line)
+                .map_err(PuzzleValidationError::InvalidForbiddenLine)?;
+        }
+        let fixed_lines = Solution::for_puzzle(puzzle, puzzle.fixed_lines.iter().cloned())
+            .map_err(PuzzleValidationError::InvalidFixedLine)?;
+        if !puzzle.allow_crossings && has_crossing_lines(&fixed_lines, puzzle) {
+            return Err(PuzzleValidationError::CrossingFixedLines);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        logic::{
+            puzzle::validation::validation::{validate_puzzle, PuzzleValidationError},
+            topology::topology::Topology,
+        },
+        puzzle_manager::PuzzleManager,
+        structs::immutable::{
+            game_line::game_line::{GameLine, LineError},
+            game_node::game_node::{GameNode, GameNodeId},
+            game_set::game_set::GameSet,
+            puzzle::puzzle::Puzzle,
+        },
+    };
+
+    fn get_test_node(id: GameNodeId) -> GameNode {
+        GameNode {
+            id,
+            conditions: Vec::new(),
+            connected_conditions: Vec::new(),
+        }
+    }
+
+    fn get_test_set(id: u8, nodes: Vec<GameNodeId>) -> GameSet {
+        GameSet {
+            id,
+            nodes,
+            rules: Vec::new(),
+            connected_rules: Vec::new(),
+            meta_rules: Vec::new(),
+            bounded: false,
+        }
+    }
+
+    fn get_test_puzzle(node_ids: Vec<GameNodeId>, sets: Vec<GameSet>) -> Puzzle {
+        Puzzle {
+            nodes: node_ids.into_iter().map(get_test_node).collect(),
+            sets,
+            ..Puzzle::new(2, 2)
+        }
+    }
+
+    #[test]
+    fn test_validate_puzzle_valid_returns_ok() {
+        let puzzle = get_test_puzzle(
+            [0, 1, 2, 3].to_vec(),
+            [
+                get_test_set(0, [0, 1].to_vec()),
+                get_test_set(1, [2, 3].to_vec()),
+            ]
+            .to_vec(),
+        );
+
+        assert_eq!(validate_puzzle(&puzzle), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_puzzle_node_out_of_bounds_returns_error() {
+        let puzzle = get_test_puzzle([0, 1, 2, 3, 4].to_vec(), Vec::new());
+
+        assert_eq!(
+            validate_puzzle(&puzzle),
+            Err(PuzzleValidationError::NodeOutOfBounds {
+                node_id: 4,
+                node_count: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_puzzle_duplicate_node_returns_error() {
+        let puzzle = get_test_puzzle([0, 1, 1, 2, 3].to_vec(), Vec::new());
+
+        assert_eq!(
+            validate_puzzle(&puzzle),
+            Err(PuzzleValidationError::DuplicateNode(1))
+        );
+    }
+
+    #[test]
+    fn test_validate_puzzle_hole_returns_ok() {
+        let puzzle = get_test_puzzle([0, 1, 3].to_vec(), Vec::new());
+
+        assert_eq!(validate_puzzle(&puzzle), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_puzzle_no_nodes_returns_error() {
+        let puzzle = get_test_puzzle(Vec::new(), Vec::new());
+
+        assert_eq!(
+            validate_puzzle(&puzzle),
+            Err(PuzzleValidationError::NoNodes)
+        );
+    }
+
+    #[test]
+    fn test_validate_puzzle_fixed_line_not_adjacent_returns_error() {
+        let mut puzzle = get_test_puzzle([0, 1, 2, 3].to_vec(), Vec::new());
+        puzzle.topology = Topology::Square4;
+        puzzle.fixed_lines = [GameLine::new(0, 3).unwrap()].to_vec();
+
+        assert_eq!(
+            validate_puzzle(&puzzle),
+            Err(PuzzleValidationError::InvalidFixedLine(
+                LineError::NotAdjacent(0, 3)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_puzzle_fixed_and_forbidden_line_returns_error() {
+        let mut puzzle = get_test_puzzle([0, 1, 2, 3].to_vec(), Vec::new());
+        puzzle.fixed_lines = [GameLine::new(0, 1).unwrap()].to_vec();
+        puzzle.forbidden_lines = [GameLine::new(0, 1).unwrap()].to_vec();
+
+        assert_eq!(
+            validate_puzzle(&puzzle),
+            Err(PuzzleValidationError::InvalidFixedLine(
+                LineError::Forbidden(0, 1)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_puzzle_forbidden_line_in_hole_returns_error() {
+        let mut puzzle = get_test_puzzle([0, 1, 2].to_vec(), Vec::new());
+        puzzle.forbidden_lines = [GameLine::new(2, 3).unwrap()].to_vec();
+
+        assert_eq!(
+            validate_puzzle(&puzzle),
+            Err(PuzzleValidationError::InvalidForbiddenLine(
+                LineError::UnknownNode(3)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_puzzle_crossing_fixed_lines_returns_error() {
+        let mut puzzle = get_test_puzzle([0, 1, 2, 3].to_vec(), Vec::new());
+        puzzle.fixed_lines = [GameLine::new(0, 3).unwrap(), GameLine::new(1, 2).unwrap()].to_vec();
+
+        assert_eq!(
+            validate_puzzle(&puzzle),
+            Err(PuzzleValidationError::CrossingFixedLines)
+        );
+    }
+
+    #[test]
+    fn test_validate_puzzle_toroidal_hex_odd_width_returns_error() {
+        let mut puzzle = get_test_puzzle([0, 1].to_vec(), Vec::new());
+        puzzle.width = 1;
+        puzzle.topology = Topology::Hex;
+        puzzle.toroidal = true;
+
+        assert_eq!(
+            validate_puzzle(&puzzle),
+            Err(PuzzleValidationError::OddToroidalHexWidth(1))
+        );
+    }
+
+    #[test]
+    fn test_validate_puzzle_duplicate_set_returns_error() {
+        let puzzle = get_test_puzzle(
+            [0, 1, 2, 3].to_vec(),
+            [get_test_set(0, [0].to_vec()), get_test_set(0, [1].to_vec())].to_vec(),
+        );
+
+        assert_eq!(
+            validate_puzzle(&puzzle),
+            Err(PuzzleValidationError::DuplicateSet(0))
+        );
+    }
+
+    #[test]
+    fn test_validate_puzzle_unknown_set_node_returns_error() {
+        let puzzle = get_test_puzzle(
+            [0, 1, 2, 3].to_vec(),
+            [get_test_set(0, [0, 7].to_vec())].to_vec(),
+        );
+
+        assert_eq!(
+            validate_puzzle(&puzzle),
+            Err(PuzzleValidationError::UnknownSetNode {
+                set_id: 0,
+                node_id: 7
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_puzzle_empty_set_returns_error() {
+        let puzzle = get_test_puzzle(
+            [0, 1, 2, 3].to_vec(),
+            [get_test_set(0, Vec::new())].to_vec(),
+        );
+
+        assert_eq!(
+            validate_puzzle(&puzzle),
+            Err(PuzzleValidationError::EmptySet(0))
+        );
+    }
+
+    #[test]
+    fn test_validate_all_campaign_puzzles() {
+        let mut puzzle_manager = PuzzleManager::new();
+        puzzle_manager.populate_campaign();
+
+        let invalid_puzzles: Vec<String> = puzzle_manager
+            .get_invalid_puzzles()
+            .iter()
+            .map(|invalid_puzzle| invalid_puzzle.to_string())
+            .collect();
+        assert!(
+            invalid_puzzles.is_empty(),
+            "Invalid puzzles: {:?}",
+            invalid_puzzles
+        );
+        assert!(!puzzle_manager.get_puzzle_uuids().is_empty());
+    }
+}
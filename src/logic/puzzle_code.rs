@@ -0,0 +1,310 @@
+pub mod puzzle_code {
+    use std::{
+        error::Error,
+        fmt,
+        io::{self, Read, Write},
+    };
+
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+    use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+    use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+
+    use crate::{
+        logic::{
+            puzzle::{
+                migration::migration::{migrate_puzzle, MigrationError},
+                validation::validation::{validate_puzzle, PuzzleValidationError},
+            },
+            topology::topology::Topology,
+        },
+        structs::immutable::{
+            game_line::game_line::{GameLine, LineError},
+            palette::palette::Palette,
+            puzzle::puzzle::Puzzle,
+            solution::solution::Solution,
+        },
+    };
+
+    /// Start of every puzzle code, naming the version of the code format.
+    const CODE_PREFIX: &str = "N1.";
+
+    /// Most bytes a code may expand to, so a small code can't expand into a huge document.
+    const MAX_PAYLOAD_SIZE: u64 = 1 << 20;
+
+    /// A puzzle loaded from a code, along with the lines drawn in it if the code has any.
+    #[derive(Clone, Debug)]
+    pub struct SharedPuzzle {
+        pub puzzle: Puzzle,
+        pub solution: Option<Solution>,
+    }
+
+    /// Json compressed into a code. The puzzle is kept as json so that it can be migrated from
+    /// the format version it was written in.
+    #[derive(Serialize, Deserialize)]
+    struct CodePayload {
+        puzzle: Value,
+        /// Lines drawn in the puzzle, kept apart from a `Solution` until `decode_puzzle` checks
+        /// them against the puzzle.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        solution: Option<Vec<GameLine>>,
+    }
+
+    /// Reasons a code couldn't be turned back into a puzzle.
+    #[derive(Debug)]
+    pub enum PuzzleCodeError {
+        /// The code doesn't start with the prefix of a known code format.
+        UnknownFormat,
+        /// The code has characters which can't appear in a code, or is cut short mid-character.
+        Encoding(base64::DecodeError),
+        /// The compressed data of the code is damaged, e.g. as the code was cut short.
+        Corrupt(io::Error),
+        /// The code expands to more data than any puzzle needs.
+        TooLarge,
+        /// The code doesn't hold a puzzle in json.
+        Parse(serde_json::Error),
+        /// The puzzle couldn't be upgraded from the format version it was written in.
+        Migration(MigrationError),
+        /// The puzzle was read but isn't playable.
+        Invalid(PuzzleValidationError),
+        /// A line shared along with the puzzle can't be drawn in it.
+        InvalidSolution(LineError),
+    }
+
+    impl fmt::Display for PuzzleCodeError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                PuzzleCodeError::UnknownFormat => {
+                    write!(f, "not a puzzle code, codes start with {}", CODE_PREFIX)
+                }
+                PuzzleCodeError::Encoding(e) => {
+                    write!(f, "code may be mistyped or cut short: {}", e)
+                }
+                PuzzleCodeError::Corrupt(e) => write!(f, "code is damaged or incomplete: {}", e),
+                PuzzleCodeError::TooLarge => write!(f, "code is too large to be a puzzle"),
+                PuzzleCodeError::Parse(e) => write!(f, "code does not hold a puzzle: {}", e),
+                PuzzleCodeError::Migration(e) => write!(f, "failed to migrate puzzle: {}", e),
+                PuzzleCodeError::Invalid(e) => write!(f, "invalid puzzle: {}", e),
+                PuzzleCodeError::InvalidSolution(e) => write!(f, "invalid shared line: {}", e),
+            }
+        }
+    }
+
+    impl Error for PuzzleCodeError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                PuzzleCodeError::UnknownFormat | PuzzleCodeError::TooLarge => None,
+                PuzzleCodeError::Encoding(e) => Some(e),
+                PuzzleCodeError::Corrupt(e) => Some(e),
+                PuzzleCodeError::Parse(e) => Some(e),
+                PuzzleCodeError::Migration(e) => Some(e),
+                PuzzleCodeError::Invalid(e) => Some(e),
+                PuzzleCodeError::InvalidSolution(e) => Some(e),
+            }
+        }
+    }
+
+    /// Encodes a puzzle, and optionally lines drawn in it, as a short url-safe code. Fields
+    /// left at their defaults are dropped and the json is compressed, whose checksum lets
+    /// damaged codes be told apart when decoding.
+    ///
+    /// # Parameters
+    /// - `puzzle`: The puzzle to share.
+    /// - `solution`: Lines drawn in the puzzle to share along with it, if any.
+    ///
+    /// # Returns
+    /// The code, made of the code prefix followed by url-safe base64.
+    pub fn encode_puzzle(puzzle: &Puzzle, solution: Option<&Solution>) -> String {
+        let mut document = serde_json::to_value(puzzle).expect("puzzles serialize to json");
+        if let Value::Object(object) = &mut document {
+            for (key, default) in get_default_fields() {
+                if object.get(key) == Some(&default) {
+                    object.remove(key);
+                }
+            }
+        }
+        let payload = CodePayload {
+            puzzle: document,
+            solution: solution.map(|solution| solution.lines().to_vec()),
+        };
+        let json = serde_json::to_vec(&payload).expect("puzzles serialize to json");
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::best());
+        encoder
+            .write_all(&json)
+            .and_then(|_| encoder.finish())
+            .map(|compressed| format!("{}{}", CODE_PREFIX, URL_SAFE_NO_PAD.encode(compressed)))
+            .expect("compressing in memory doesn't fail")
+    }
+
+    /// Decodes a puzzle code, migrating and validating the puzzle it holds as when loading a
+    /// puzzle file. Whitespace is ignored, as chat often wraps long codes across lines.
+    ///
+    /// # Parameters
+    /// - `code`: The code, as made by `encode_puzzle`.
+    ///
+    /// # Returns
+    /// The puzzle and any lines shared along with it, or why the code can't be played.
+    pub fn decode_puzzle(code: &str) -> Result<SharedPuzzle, PuzzleCodeError> {
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        let encoded = code
+            .strip_prefix(CODE_PREFIX)
+            .ok_or(PuzzleCodeError::UnknownFormat)?;
+        let compressed = URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(PuzzleCodeError::Encoding)?;
+
+        let mut json = Vec::new();
+        ZlibDecoder::new(compressed.as_slice())
+            .take(MAX_PAYLOAD_SIZE + 1)
+            .read_to_end(&mut json)
+            .map_err(PuzzleCodeError::Corrupt)?;
+        if json.len() as u64 > MAX_PAYLOAD_SIZE {
+            return Err(PuzzleCodeError::TooLarge);
+        }
+
+        let payload: CodePayload = serde_json::from_slice(&json).map_err(PuzzleCodeError::Parse)?;
+        let document = migrate_puzzle(payload.puzzle).map_err(PuzzleCodeError::Migration)?;
+        let puzzle: Puzzle = serde_json::from_value(document).map_err(PuzzleCodeError::Parse)?;
+        validate_puzzle(&puzzle).map_err(PuzzleCodeError::Invalid)?;
+
+        let solution = payload
+            .solution
+            .map(|solution| Solution::for_puzzle(&puzzle, solution))
+            .transpose()
+            .map_err(PuzzleCodeError::InvalidSolution)?;
+        Ok(SharedPuzzle { puzzle, solution })
+    }
+
+    /// Returns the optional puzzle fields with the json of their defaults, which codes leave
+    /// out.
+    fn get_default_fields() -> [(&'static str, Value); 6] {
+        [
+            ("allow_crossings", Value::Bool(false)),
+            (
+                "palette",
+                serde_json::to_value(Palette::default()).expect("palettes serialize to json"),
+            ),
+            (
+                "topology",
+                serde_json::to_value(Topology::default()).expect("topologies serialize to json"),
+            ),
+            ("toroidal", Value::Bool(false)),
+            ("fixed_lines", Value::Array(Vec::new())),
+            ("forbidden_lines", Value::Array(Vec::new())),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use walkdir::WalkDir;
+
+    use crate::{
+        logic::puzzle_code::puzzle_code::{decode_puzzle, encode_puzzle, PuzzleCodeError},
+        puzzle_manager::read_puzzle,
+        structs::immutable::{
+            game_line::game_line::{GameLine, LineError},
+            puzzle::puzzle::Puzzle,
+            solution::solution::Solution,
+        },
+    };
+
+    fn get_test_puzzle() -> Puzzle {
+        read_puzzle(Path::new(
+            "assets/campaign/puzzles/bounded_branch_equal.json",
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode_puzzle_all_campaign_puzzles_returns_same_puzzle() {
+        for entry in WalkDir::new(Path::new("assets/campaign/puzzles/"))
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+        {
+            let puzzle = read_puzzle(entry.path()).unwrap();
+
+            let code = encode_puzzle(&puzzle, None);
+            let shared = decode_puzzle(&code).unwrap();
+
+            assert!(code
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c)));
+            assert_eq!(
+                serde_json::to_value(&shared.puzzle).unwrap(),
+                serde_json::to_value(&puzzle).unwrap(),
+                "{} changed after encoding",
+                entry.path().display()
+            );
+            assert!(shared.solution.is_none());
+        }
+    }
+
+    #[test]
+    fn test_decode_puzzle_with_solution_returns_solution() {
+        let puzzle = get_test_puzzle();
+        let solution = Solution::for_puzzle(&puzzle, [GameLine::new(0, 1).unwrap()]).unwrap();
+
+        let code = encode_puzzle(&puzzle, Some(&solution));
+
+        assert_eq!(decode_puzzle(&code).unwrap().solution, Some(solution));
+    }
+
+    #[test]
+    fn test_decode_puzzle_wrapped_across_lines_returns_puzzle() {
+        let code = encode_puzzle(&get_test_puzzle(), None);
+        let (start, end) = code.split_at(code.len() / 2);
+
+        assert!(decode_puzzle(&format!("  {}\n{} ", start, end)).is_ok());
+    }
+
+    #[test]
+    fn test_decode_puzzle_without_prefix_returns_error() {
+        assert!(matches!(
+            decode_puzzle("eJyrVkrOz0nNVbJSKs9ILEkFAC8WBbE"),
+            Err(PuzzleCodeError::UnknownFormat)
+        ));
+    }
+
+    #[test]
+    fn test_decode_puzzle_invalid_characters_returns_error() {
+        assert!(matches!(
+            decode_puzzle("N1.not/a+code!"),
+            Err(PuzzleCodeError::Encoding(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_puzzle_changed_character_returns_error() {
+        let mut code = encode_puzzle(&get_test_puzzle(), None).into_bytes();
+        let idx = code.len() / 2;
+        code[idx] = if code[idx] == b'A' { b'B' } else { b'A' };
+
+        assert!(decode_puzzle(&String::from_utf8(code).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_decode_puzzle_cut_short_returns_error() {
+        let code = encode_puzzle(&get_test_puzzle(), None);
+
+        for cut in 2..12 {
+            assert!(decode_puzzle(&code[..code.len() - cut]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_decode_puzzle_line_outside_puzzle_returns_error() {
+        let puzzle = get_test_puzzle();
+        let node_count = puzzle.width as u16 * puzzle.height as u16;
+        let solution = Solution::from([GameLine::new(0, node_count).unwrap()]);
+
+        assert!(matches!(
+            decode_puzzle(&encode_puzzle(&puzzle, Some(&solution))),
+            Err(PuzzleCodeError::InvalidSolution(LineError::UnknownNode(_)))
+        ));
+    }
+}
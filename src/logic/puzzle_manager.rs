@@ -2,64 +2,168 @@ pub mod puzzle_manager {
     use bevy::prelude::*;
 
     use std::{
-        fs::{self, File},
-        io::Read,
-        path::PathBuf,
+        error::Error,
+        fmt, fs, io,
+        path::{Path, PathBuf},
     };
 
     use bevy::utils::HashMap;
     use uuid::Uuid;
     use walkdir::WalkDir;
 
-    use crate::structs::immutable::puzzle::puzzle::Puzzle;
+    use crate::{
+        logic::{
+            puzzle::{
+                migration::migration::{migrate_puzzle, MigrationError},
+                validation::validation::{validate_puzzle, PuzzleValidationError},
+            },
+            puzzle_code::puzzle_code::SharedPuzzle,
+        },
+        structs::immutable::{puzzle::puzzle::Puzzle, solution::solution::Solution},
+    };
+
+    /// Reasons a puzzle file couldn't be loaded.
+    #[derive(Debug)]
+    pub enum PuzzleLoadError {
+        /// The file couldn't be read.
+        Io(io::Error),
+        /// The file isn't a puzzle in json.
+        Parse(serde_json::Error),
+        /// The puzzle couldn't be upgraded from the format version it was written in.
+        Migration(MigrationError),
+        /// The puzzle was read but isn't playable.
+        Invalid(PuzzleValidationError),
+    }
+
+    impl fmt::Display for PuzzleLoadError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                PuzzleLoadError::Io(e) => write!(f, "failed to read file: {}", e),
+                PuzzleLoadError::Parse(e) => write!(f, "failed to parse puzzle: {}", e),
+                PuzzleLoadError::Migration(e) => write!(f, "failed to migrate puzzle: {}", e),
+                PuzzleLoadError::Invalid(e) => write!(f, "invalid puzzle: {}", e),
+            }
+        }
+    }
+
+    impl Error for PuzzleLoadError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                PuzzleLoadError::Io(e) => Some(e),
+                PuzzleLoadError::Parse(e) => Some(e),
+                PuzzleLoadError::Migration(e) => Some(e),
+                PuzzleLoadError::Invalid(e) => Some(e),
+            }
+        }
+    }
+
+    /// A puzzle file which was skipped when populating puzzles.
+    #[derive(Debug)]
+    pub struct InvalidPuzzle {
+        pub path: String,
+        pub error: PuzzleLoadError,
+    }
+
+    impl fmt::Display for InvalidPuzzle {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}: {}", self.path, self.error)
+        }
+    }
+
+    /// Reads, parses and validates the puzzle at the given path, migrating it from the format
+    /// version it was written in.
+    pub fn read_puzzle(path: &Path) -> Result<Puzzle, PuzzleLoadError> {
+        let contents = fs::read_to_string(path).map_err(PuzzleLoadError::Io)?;
+        let document = serde_json::from_str(&contents).map_err(PuzzleLoadError::Parse)?;
+        let document = migrate_puzzle(document).map_err(PuzzleLoadError::Migration)?;
+        let puzzle: Puzzle = serde_json::from_value(document).map_err(PuzzleLoadError::Parse)?;
+        validate_puzzle(&puzzle).map_err(PuzzleLoadError::Invalid)?;
+        Ok(puzzle)
+    }
 
     #[derive(Resource, Debug)]
     pub struct PuzzleManager {
         puzzles: HashMap<Uuid, String>,
+        invalid_puzzles: Vec<InvalidPuzzle>,
+        /// Puzzles loaded from codes, kept in memory until the game closes.
+        shared_puzzles: HashMap<Uuid, Puzzle>,
+        /// Lines shared along with puzzles loaded from codes, until they're first drawn.
+        shared_solutions: HashMap<Uuid, Solution>,
     }
 
     impl PuzzleManager {
         pub fn new() -> Self {
             PuzzleManager {
                 puzzles: HashMap::new(),
+                invalid_puzzles: Vec::new(),
+                shared_puzzles: HashMap::new(),
+                shared_solutions: HashMap::new(),
             }
         }
 
-        /// Populates locally stored puzzles (from ../assets/campaign/puzzles/)
-        pub fn populate_campaign(&mut self) -> serde_json::Result<()> {
+        /// Populates locally stored puzzles (from ../assets/campaign/puzzles/). Puzzles which
+        /// fail to load are skipped, logged and kept to be reported by `get_invalid_puzzles`.
+        pub fn populate_campaign(&mut self) {
+            self.invalid_puzzles.clear();
             for entry in WalkDir::new(&PathBuf::from("assets/campaign/puzzles/"))
                 .into_iter()
                 .filter_map(|e| e.ok())
                 .filter(|e| e.path().extension().map_or(false, |ext| ext == "json"))
             {
                 let path = entry.path();
-
-                // Open the file
-                let mut file = File::open(path).expect("Failed to open the file");
-
-                // Read the file contents into a string
-                let mut contents = String::new();
-                file.read_to_string(&mut contents)
-                    .expect("Failed to read the file");
-
-                let puzzle: Puzzle = match serde_json::from_str(&contents) {
-                    Ok(data) => data,
-                    Err(e) => panic!("Failed to load puzzle: {:?}", e),
-                };
-                self.add_puzzle(puzzle.uuid, path.to_string_lossy().into_owned());
+                match read_puzzle(path) {
+                    Ok(puzzle) => self.add_puzzle(puzzle.uuid, path.to_string_lossy().into_owned()),
+                    Err(error) => {
+                        let invalid_puzzle = InvalidPuzzle {
+                            path: path.to_string_lossy().into_owned(),
+                            error,
+                        };
+                        warn!("Skipping puzzle {}", invalid_puzzle);
+                        self.invalid_puzzles.push(invalid_puzzle);
+                    }
+                }
             }
-            Ok(())
         }
 
         fn add_puzzle(&mut self, uuid: Uuid, path: String) {
             self.puzzles.insert(uuid, path);
         }
 
+        /// Adds a puzzle loaded from a code, taking the place of any puzzle with the same uuid.
+        /// Lines shared along with it are drawn the next time it's played.
+        pub fn add_shared_puzzle(&mut self, shared_puzzle: SharedPuzzle) -> Uuid {
+            let uuid = shared_puzzle.puzzle.uuid;
+            match shared_puzzle.solution {
+                Some(solution) => self.shared_solutions.insert(uuid, solution),
+                None => self.shared_solutions.remove(&uuid),
+            };
+            self.shared_puzzles.insert(uuid, shared_puzzle.puzzle);
+            uuid
+        }
+
+        /// Takes the lines shared along with a puzzle loaded from a code, so they're only drawn
+        /// the first time it's played.
+        pub fn take_shared_solution(&mut self, uuid: &Uuid) -> Option<Solution> {
+            self.shared_solutions.remove(uuid)
+        }
+
         pub fn load_puzzle(&self, uuid: &Uuid) -> Option<Puzzle> {
-            self.puzzles.get(uuid).and_then(|path| {
-                let data = fs::read_to_string(path).ok()?;
-                serde_json::from_str(&data).ok()
-            })
+            if let Some(puzzle) = self.shared_puzzles.get(uuid) {
+                return Some(puzzle.clone());
+            }
+            self.puzzles
+                .get(uuid)
+                .and_then(|path| read_puzzle(Path::new(path)).ok())
+        }
+
+        /// Returns the puzzle files which failed to load when populating puzzles.
+        pub fn get_invalid_puzzles(&self) -> &[InvalidPuzzle] {
+            &self.invalid_puzzles
+        }
+
+        /// Returns the path of the file a puzzle was loaded from.
+        pub fn get_puzzle_path(&self, uuid: &Uuid) -> Option<&String> {
+            self.puzzles.get(uuid)
         }
 
         pub fn get_puzzle_uuids(&self) -> Vec<Uuid> {
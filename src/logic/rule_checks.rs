@@ -1,53 +1,108 @@
 pub mod rule_checks {
 
-    use crate::structs::immutable::{
-        game_set::game_set::GameSet,
-        solution::solution::{solution_to_adjacency_matrix, Solution},
+    use crate::{
+        logic::diagnostic::diagnostic::{CheckResult, Diagnostic},
+        structs::immutable::{game_set::game_set::GameSet, solution::solution::Solution},
     };
 
-    /// Checks if there are any nodes in the set which directly connect to a node also in the set.
-    pub fn is_disconnected(set: &GameSet, solution: &Solution) -> bool {
+    /// Checks if there are any nodes in the set which directly connect to a node also in the set,
+    /// reporting how many lines join two nodes within the set.
+    pub fn is_disconnected(set: &GameSet, solution: &Solution) -> CheckResult {
         if solution.is_empty() {
-            return false;
+            return CheckResult::new(false, Diagnostic::NoLines);
         }
 
-        let adj_matrix = solution_to_adjacency_matrix(solution);
+        let inner_lines = solution
+            .iter()
+            .filter(|line| {
+                set.nodes.contains(&line.node_a_id()) && set.nodes.contains(&line.node_b_id())
+            })
+            .count();
 
-        for (node, neighbors) in adj_matrix.iter() {
-            for neighbor in neighbors.iter() {
-                if set.nodes.contains(neighbor) && set.nodes.contains(node) {
-                    return false;
-                }
-            }
-        }
+        CheckResult::new(inner_lines == 0, Diagnostic::InnerLines(inner_lines))
+    }
 
-        true
+    /// Checks that there is exactly one node within the set that connects to a node outside the
+    /// set, reporting how many outward connections were found.
+    pub fn is_leaf(set: &GameSet, solution: &Solution) -> CheckResult {
+        let outward_connections = solution
+            .iter()
+            .filter(|line| {
+                set.nodes.contains(&line.node_a_id()) != set.nodes.contains(&line.node_b_id())
+            })
+            .count();
+
+        CheckResult::new(
+            outward_connections == 1,
+            Diagnostic::OutwardConnections(outward_connections),
+        )
     }
 
-    /// Checks that there is exactly one node within the set that connects to a node outside the set.
-    pub fn is_leaf(set: &GameSet, solution: &Solution) -> bool {
-        let adj_matrix = solution_to_adjacency_matrix(solution);
+    /// Checks that the lines within the set join all of its nodes into one network, reporting
+    /// how many separate groups the nodes of the set form.
+    pub fn is_connected(set: &GameSet, solution: &Solution) -> CheckResult {
+        let components = solution.filter_to_set(set).components();
+        let isolated_nodes = set
+            .nodes
+            .iter()
+            .filter(|node_id| {
+                !components
+                    .iter()
+                    .any(|component| component.contains(node_id))
+            })
+            .count();
+        let groups = components.len() + isolated_nodes;
 
-        let mut external_connection = false;
-        for (node, neighbors) in adj_matrix.iter() {
-            for neighbor in neighbors.iter() {
-                if !set.nodes.contains(neighbor) && set.nodes.contains(node) {
-                    if external_connection == true {
-                        return false;
-                    }
-                    external_connection = true;
-                }
-            }
-        }
+        CheckResult::new(groups == 1, Diagnostic::InnerGroups(groups))
+    }
+
+    /// Checks that the lines within the set don't form a cycle.
+    pub fn is_acyclic(set: &GameSet, solution: &Solution) -> CheckResult {
+        let inner_solution = solution.filter_to_set(set);
+        let components = inner_solution.components();
+
+        // A forest has exactly one line less than nodes in each of its networks
+        let nodes_with_lines: usize = components.iter().map(|component| component.len()).sum();
+        let has_cycle = inner_solution.len() + components.len() > nodes_with_lines;
+
+        CheckResult::new(!has_cycle, Diagnostic::InnerCycle(has_cycle))
+    }
+
+    /// Checks that the set contains exactly the given number of lines within it.
+    pub fn is_edge_count(set: &GameSet, solution: &Solution, count: u8) -> CheckResult {
+        let inner_lines = solution.filter_to_set(set).len();
 
-        external_connection
+        CheckResult::new(
+            inner_lines == count as usize,
+            Diagnostic::InnerLines(inner_lines),
+        )
+    }
+
+    /// Checks that no line joins a node within the set to a node outside it.
+    pub fn is_closed(set: &GameSet, solution: &Solution) -> CheckResult {
+        let outward_connections = solution
+            .iter()
+            .filter(|line| {
+                set.nodes.contains(&line.node_a_id()) != set.nodes.contains(&line.node_b_id())
+            })
+            .count();
+
+        CheckResult::new(
+            outward_connections == 0,
+            Diagnostic::OutwardConnections(outward_connections),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        logic::rule_checks::rule_checks::{is_disconnected, is_leaf},
+        logic::{
+            diagnostic::diagnostic::Diagnostic,
+            rule_checks::rule_checks::{
+                is_acyclic, is_closed, is_connected, is_disconnected, is_edge_count, is_leaf,
+            },
+        },
         structs::immutable::{
             game_line::game_line::GameLine, game_set::game_set::GameSet,
             set_rule::set_rule::SetRule, solution::solution::Solution,
@@ -60,6 +115,7 @@ mod tests {
             id: 0,
             rules: rules,
             connected_rules: Vec::new(),
+            meta_rules: Vec::new(),
             bounded: false,
         }
     }
@@ -70,43 +126,34 @@ mod tests {
 
         let solution = Solution::new();
 
-        assert!(!is_disconnected(&set, &solution));
+        assert!(!is_disconnected(&set, &solution).satisfied);
     }
 
     #[test]
     fn test_disconnected_no_connected_nodes_inside_set_returns_true() {
         let set = get_test_set(4, [SetRule::Disconnected].to_vec());
 
-        let solution = Solution::from([GameLine {
-            node_a_id: 5,
-            node_b_id: 6,
-        }]);
+        let solution = Solution::from([GameLine::new(5, 6).unwrap()]);
 
-        assert!(is_disconnected(&set, &solution));
+        assert!(is_disconnected(&set, &solution).satisfied);
     }
 
     #[test]
     fn test_disconnected_connected_node_outside_set_returns_true() {
         let set = get_test_set(4, [SetRule::Disconnected].to_vec());
 
-        let solution = Solution::from([GameLine {
-            node_a_id: 0,
-            node_b_id: 5,
-        }]);
+        let solution = Solution::from([GameLine::new(0, 5).unwrap()]);
 
-        assert!(is_disconnected(&set, &solution));
+        assert!(is_disconnected(&set, &solution).satisfied);
     }
 
     #[test]
     fn test_disconnected_two_connected_nodes_returns_false() {
         let set = get_test_set(4, [SetRule::Disconnected].to_vec());
 
-        let solution = Solution::from([GameLine {
-            node_a_id: 0,
-            node_b_id: 1,
-        }]);
+        let solution = Solution::from([GameLine::new(0, 1).unwrap()]);
 
-        assert!(!is_disconnected(&set, &solution));
+        assert!(!is_disconnected(&set, &solution).satisfied);
     }
 
     #[test]
@@ -115,36 +162,149 @@ mod tests {
 
         let solution = Solution::new();
 
-        assert!(!is_leaf(&set, &solution));
+        assert!(!is_leaf(&set, &solution).satisfied);
     }
 
     #[test]
     fn test_leaf_one_node_connected_outside_set_returns_true() {
         let set = get_test_set(4, [SetRule::Leaf].to_vec());
 
-        let solution = Solution::from([GameLine {
-            node_a_id: 0,
-            node_b_id: 5,
-        }]);
+        let solution = Solution::from([GameLine::new(0, 5).unwrap()]);
 
-        assert!(is_leaf(&set, &solution));
+        assert!(is_leaf(&set, &solution).satisfied);
     }
 
     #[test]
     fn test_leaf_two_nodes_connected_outside_set_returns_false() {
         let set = get_test_set(4, [SetRule::Leaf].to_vec());
 
+        let solution = Solution::from([GameLine::new(0, 5).unwrap(), GameLine::new(1, 6).unwrap()]);
+
+        assert!(!is_leaf(&set, &solution).satisfied);
+    }
+
+    #[test]
+    fn test_leaf_two_nodes_connected_outside_set_reports_outward_connections() {
+        let set = get_test_set(4, [SetRule::Leaf].to_vec());
+
+        let solution = Solution::from([GameLine::new(0, 5).unwrap(), GameLine::new(1, 6).unwrap()]);
+
+        assert_eq!(
+            is_leaf(&set, &solution).diagnostic,
+            Diagnostic::OutwardConnections(2)
+        );
+    }
+
+    #[test]
+    fn test_connected_path_through_set_returns_true() {
+        let set = get_test_set(3, [SetRule::Connected].to_vec());
+
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(1, 2).unwrap()]);
+
+        assert!(is_connected(&set, &solution).satisfied);
+    }
+
+    #[test]
+    fn test_connected_joined_only_outside_set_returns_false() {
+        let set = get_test_set(3, [SetRule::Connected].to_vec());
+
         let solution = Solution::from([
-            GameLine {
-                node_a_id: 0,
-                node_b_id: 5,
-            },
-            GameLine {
-                node_a_id: 1,
-                node_b_id: 6,
-            },
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 5).unwrap(),
+            GameLine::new(2, 5).unwrap(),
+        ]);
+
+        assert!(!is_connected(&set, &solution).satisfied);
+    }
+
+    #[test]
+    fn test_connected_isolated_node_reports_groups() {
+        let set = get_test_set(3, [SetRule::Connected].to_vec());
+
+        let solution = Solution::from([GameLine::new(0, 1).unwrap()]);
+
+        assert_eq!(
+            is_connected(&set, &solution).diagnostic,
+            Diagnostic::InnerGroups(2)
+        );
+    }
+
+    #[test]
+    fn test_acyclic_tree_within_set_returns_true() {
+        let set = get_test_set(4, [SetRule::Acyclic].to_vec());
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(0, 2).unwrap(),
+            GameLine::new(0, 3).unwrap(),
         ]);
 
-        assert!(!is_leaf(&set, &solution));
+        assert!(is_acyclic(&set, &solution).satisfied);
+    }
+
+    #[test]
+    fn test_acyclic_cycle_within_set_returns_false() {
+        let set = get_test_set(3, [SetRule::Acyclic].to_vec());
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(2, 0).unwrap(),
+        ]);
+
+        assert!(!is_acyclic(&set, &solution).satisfied);
+    }
+
+    #[test]
+    fn test_acyclic_cycle_through_outside_node_returns_true() {
+        let set = get_test_set(2, [SetRule::Acyclic].to_vec());
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 5).unwrap(),
+            GameLine::new(5, 0).unwrap(),
+        ]);
+
+        assert!(is_acyclic(&set, &solution).satisfied);
+    }
+
+    #[test]
+    fn test_edge_count_matching_inner_lines_returns_true() {
+        let set = get_test_set(3, [SetRule::EdgeCount(2)].to_vec());
+
+        let solution = Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(2, 5).unwrap(),
+        ]);
+
+        assert!(is_edge_count(&set, &solution, 2).satisfied);
+    }
+
+    #[test]
+    fn test_edge_count_other_inner_lines_returns_false() {
+        let set = get_test_set(3, [SetRule::EdgeCount(1)].to_vec());
+
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(1, 2).unwrap()]);
+
+        assert!(!is_edge_count(&set, &solution, 1).satisfied);
+    }
+
+    #[test]
+    fn test_closed_no_outward_connections_returns_true() {
+        let set = get_test_set(3, [SetRule::Closed].to_vec());
+
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(5, 6).unwrap()]);
+
+        assert!(is_closed(&set, &solution).satisfied);
+    }
+
+    #[test]
+    fn test_closed_outward_connection_returns_false() {
+        let set = get_test_set(3, [SetRule::Closed].to_vec());
+
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(1, 5).unwrap()]);
+
+        assert!(!is_closed(&set, &solution).satisfied);
     }
 }
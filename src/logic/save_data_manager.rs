@@ -1,25 +1,42 @@
 pub mod save_data_manager {
     use std::path::PathBuf;
 
+    use bevy::log::warn;
     use dirs::data_dir;
     use serde::{Deserialize, Serialize};
     use uuid::Uuid;
 
-    use crate::structs::immutable::solution::solution::Solution;
+    use crate::structs::immutable::{
+        game_line::game_line::GameLine, puzzle::puzzle::Puzzle, solution::solution::Solution,
+    };
 
-    #[derive(Serialize, Deserialize)]
+    #[derive(Serialize)]
     pub struct PuzzleSaveData {
         pub puzzle_uuid: Uuid,
         pub solution: Solution,
         pub solved: bool,
+        /// Number of hints shown for the puzzle.
+        pub hints_used: u32,
     }
 
-    pub fn save_progress(puzzle_uuid: Uuid, solution: Solution, solved: bool) {
+    /// Save data as read back, with the drawn lines kept apart from a `Solution` until
+    /// `load_progress` checks them against the puzzle.
+    #[derive(Deserialize)]
+    struct StoredSaveData {
+        puzzle_uuid: Uuid,
+        solution: Vec<GameLine>,
+        solved: bool,
+        #[serde(default)]
+        hints_used: u32,
+    }
+
+    pub fn save_progress(puzzle_uuid: Uuid, solution: Solution, solved: bool, hints_used: u32) {
         // TODO if puzzle was previously solved and is not currently solved, don't save solution
         let save_data = PuzzleSaveData {
             puzzle_uuid,
             solution,
             solved,
+            hints_used,
         };
 
         let save_data_json = serde_json::to_string(&save_data);
@@ -35,18 +52,31 @@ pub mod save_data_manager {
         }
     }
 
-    pub fn load_progress(puzzle_uuid: Uuid) -> Option<PuzzleSaveData> {
-        let save_data_path = get_save_data_path(&puzzle_uuid.to_string());
+    /// Loads the saved progress on a puzzle, if any. Saves which can't be parsed, or whose
+    /// solution has lines which can't be drawn in the puzzle, are ignored.
+    pub fn load_progress(puzzle: &Puzzle) -> Option<PuzzleSaveData> {
+        let save_data_path = get_save_data_path(&puzzle.uuid.to_string());
 
-        let save_data_json = std::fs::read_to_string(save_data_path);
-        match save_data_json {
-            Ok(json) => {
-                let save_data: PuzzleSaveData = serde_json::from_str(&json).unwrap();
-                return Some(save_data);
-            }
-            Err(_error) => {
+        let json = std::fs::read_to_string(save_data_path).ok()?;
+        let save_data: StoredSaveData = match serde_json::from_str(&json) {
+            Ok(save_data) => save_data,
+            Err(error) => {
+                warn!("Ignoring save data for puzzle {}: {}", puzzle.uuid, error);
                 return None;
             }
+        };
+
+        match Solution::for_puzzle(puzzle, save_data.solution) {
+            Ok(solution) => Some(PuzzleSaveData {
+                puzzle_uuid: save_data.puzzle_uuid,
+                solution,
+                solved: save_data.solved,
+                hints_used: save_data.hints_used,
+            }),
+            Err(error) => {
+                warn!("Ignoring save data for puzzle {}: {}", puzzle.uuid, error);
+                None
+            }
         }
     }
 
@@ -56,8 +86,8 @@ pub mod save_data_manager {
         let save_data_json = std::fs::read_to_string(save_data_path);
         match save_data_json {
             Ok(json) => {
-                let save_data: PuzzleSaveData = serde_json::from_str(&json).unwrap();
-                return save_data.solved;
+                return serde_json::from_str::<StoredSaveData>(&json)
+                    .is_ok_and(|save_data| save_data.solved);
             }
             Err(_error) => {
                 return false;
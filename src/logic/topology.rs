@@ -0,0 +1,262 @@
+pub mod topology {
+    use serde::{Deserialize, Serialize};
+
+    /// Position of a node on the grid, as its column and row.
+    pub type GridPosition = (i32, i32);
+
+    /// Layout of the grid a puzzle's nodes lie on, deciding which nodes are adjacent and where
+    /// they are drawn. Whatever the layout, node ids count up each column from the bottom left,
+    /// so a node's id gives its column and row.
+    pub trait GridTopology {
+        /// Returns the positions adjacent to the position, in clockwise order. Positions may lie
+        /// outside the board, which callers filter out.
+        fn neighbors(&self, position: GridPosition) -> Vec<GridPosition>;
+
+        /// Returns every pair of positions adjacent to the position in opposite directions, such
+        /// that a path through the position and both of them runs straight.
+        fn opposite_neighbors(&self, position: GridPosition) -> Vec<(GridPosition, GridPosition)>;
+
+        /// Returns the line which crosses the line between the two adjacent positions, or None
+        /// if lines of the grid can't cross it.
+        fn crossing_line(
+            &self,
+            position_a: GridPosition,
+            position_b: GridPosition,
+        ) -> Option<(GridPosition, GridPosition)>;
+
+        /// Returns where the position is drawn, in units of the distance between the centers of
+        /// adjacent nodes.
+        fn scene_position(&self, position: GridPosition) -> (f32, f32);
+
+        /// Returns the position under every rotation and reflection of the grid, always in the
+        /// same order. They are given in coordinates where moving a shape across the grid adds
+        /// the same offset to each of its positions.
+        fn symmetries(&self, position: GridPosition) -> Vec<GridPosition>;
+    }
+
+    /// Grid of square cells with a node at each corner, whose nodes are adjacent to those left,
+    /// right, above and below them, and optionally to those diagonal from them.
+    pub struct SquareGrid {
+        pub diagonals: bool,
+    }
+
+    impl GridTopology for SquareGrid {
+        fn neighbors(&self, (x, y): GridPosition) -> Vec<GridPosition> {
+            let neighbors = [
+                (x - 1, y),
+                (x - 1, y + 1),
+                (x, y + 1),
+                (x + 1, y + 1),
+                (x + 1, y),
+                (x + 1, y - 1),
+                (x, y - 1),
+                (x - 1, y - 1),
+            ];
+            if self.diagonals {
+                neighbors.to_vec()
+            } else {
+                neighbors.into_iter().step_by(2).collect()
+            }
+        }
+
+        fn opposite_neighbors(&self, position: GridPosition) -> Vec<(GridPosition, GridPosition)> {
+            // Neighbors are in clockwise order, so each is opposite the one halfway round
+            let neighbors = self.neighbors(position);
+            let half = neighbors.len() / 2;
+            (0..half)
+                .map(|idx| (neighbors[idx], neighbors[idx + half]))
+                .collect()
+        }
+
+        fn crossing_line(
+            &self,
+            (a_x, a_y): GridPosition,
+            (b_x, b_y): GridPosition,
+        ) -> Option<(GridPosition, GridPosition)> {
+            // Only diagonals cross, with the other diagonal of the same cell
+            if !self.diagonals || a_x == b_x || a_y == b_y {
+                return None;
+            }
+            Some(((a_x, b_y), (b_x, a_y)))
+        }
+
+        fn scene_position(&self, (x, y): GridPosition) -> (f32, f32) {
+            (x as f32, y as f32)
+        }
+
+        fn symmetries(&self, (x, y): GridPosition) -> Vec<GridPosition> {
+            [
+                (x, y),
+                (-y, x),
+                (-x, -y),
+                (y, -x),
+                (-x, y),
+                (y, x),
+                (x, -y),
+                (-y, -x),
+            ]
+            .to_vec()
+        }
+    }
+
+    /// Grid of hexagonal cells with a node at the center of each, whose nodes are adjacent to the
+    /// six nodes around them. Columns are stacked vertically, with odd columns shifted up by half
+    /// a row.
+    pub struct HexGrid;
+
+    /// Directions to the neighbors of a hex in axial coordinates, in clockwise order starting
+    /// from the upper left.
+    const HEX_DIRECTIONS: [GridPosition; 6] = [(-1, 1), (0, 1), (1, 0), (1, -1), (0, -1), (-1, 0)];
+
+    impl HexGrid {
+        /// Returns the axial coordinates of the position, in which the neighbors of every hex lie
+        /// in the same directions.
+        fn to_axial((x, y): GridPosition) -> GridPosition {
+            (x, y - x.div_euclid(2))
+        }
+
+        fn from_axial((q, r): GridPosition) -> GridPosition {
+            (q, r + q.div_euclid(2))
+        }
+
+        fn neighbor(position: GridPosition, (d_q, d_r): GridPosition) -> GridPosition {
+            let (q, r) = Self::to_axial(position);
+            Self::from_axial((q + d_q, r + d_r))
+        }
+    }
+
+    impl GridTopology for HexGrid {
+        fn neighbors(&self, position: GridPosition) -> Vec<GridPosition> {
+            HEX_DIRECTIONS
+                .iter()
+                .map(|direction| Self::neighbor(position, *direction))
+                .collect()
+        }
+
+        fn opposite_neighbors(&self, position: GridPosition) -> Vec<(GridPosition, GridPosition)> {
+            let neighbors = self.neighbors(position);
+            (0..3)
+                .map(|idx| (neighbors[idx], neighbors[idx + 3]))
+                .collect()
+        }
+
+        fn crossing_line(
+            &self,
+            _position_a: GridPosition,
+            _position_b: GridPosition,
+        ) -> Option<(GridPosition, GridPosition)> {
+            None
+        }
+
+        fn scene_position(&self, (x, y): GridPosition) -> (f32, f32) {
+            let shift = if x.rem_euclid(2) == 1 { 0.5 } else { 0.0 };
+            (x as f32 * 3f32.sqrt() / 2.0, y as f32 + shift)
+        }
+
+        fn symmetries(&self, position: GridPosition) -> Vec<GridPosition> {
+            // Rotate by 60 degrees at a time in cube coordinates, then reflect each rotation
+            let (q, r) = Self::to_axial(position);
+            let mut rotations = Vec::with_capacity(6);
+            let (mut q, mut r, mut s) = (q, r, -q - r);
+            for _ in 0..6 {
+                rotations.push((q, r, s));
+                (q, r, s) = (-r, -s, -q);
+            }
+
+            rotations
+                .iter()
+                .map(|(q, r, _)| (*q, *r))
+                .chain(rotations.iter().map(|(q, _, s)| (*q, *s)))
+                .collect()
+        }
+    }
+
+    /// Which grid the nodes of a puzzle lie on, named in json by its number of neighbors.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum Topology {
+        /// Square grid without diagonal lines.
+        #[serde(rename = "square-4")]
+        Square4,
+        /// Square grid with diagonal lines, which may cross.
+        #[default]
+        #[serde(rename = "square-8")]
+        Square8,
+        /// Hexagonal grid.
+        #[serde(rename = "hex")]
+        Hex,
+    }
+
+    impl Topology {
+        pub fn grid(&self) -> &'static dyn GridTopology {
+            match self {
+                Topology::Square4 => &SquareGrid { diagonals: false },
+                Topology::Square8 => &SquareGrid { diagonals: true },
+                Topology::Hex => &HexGrid,
+            }
+        }
+
+        /// Returns true if the grid is made of square cells, which set borders are drawn around.
+        pub fn is_square(&self) -> bool {
+            matches!(self, Topology::Square4 | Topology::Square8)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic::topology::topology::{GridTopology, HexGrid, SquareGrid, Topology};
+
+    #[test]
+    fn test_square_4_neighbors_returns_orthogonal_positions() {
+        let grid = SquareGrid { diagonals: false };
+
+        assert_eq!(grid.neighbors((1, 1)), [(0, 1), (1, 2), (2, 1), (1, 0)]);
+    }
+
+    #[test]
+    fn test_hex_neighbors_even_and_odd_columns_returns_surrounding_positions() {
+        assert_eq!(
+            HexGrid.neighbors((2, 2)),
+            [(1, 2), (2, 3), (3, 2), (3, 1), (2, 1), (1, 1)]
+        );
+        assert_eq!(
+            HexGrid.neighbors((1, 1)),
+            [(0, 2), (1, 2), (2, 2), (2, 1), (1, 0), (0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_hex_neighbors_are_one_apart_in_scene() {
+        for position in [(2, 2), (1, 1)] {
+            let (x, y) = HexGrid.scene_position(position);
+            for neighbor in HexGrid.neighbors(position) {
+                let (n_x, n_y) = HexGrid.scene_position(neighbor);
+                assert!(((n_x - x).hypot(n_y - y) - 1.0).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hex_symmetries_keep_neighbors_adjacent() {
+        let neighbors = HexGrid.neighbors((2, 2));
+        let center = HexGrid.symmetries((2, 2));
+
+        for neighbor in neighbors {
+            for (idx, image) in HexGrid.symmetries(neighbor).into_iter().enumerate() {
+                let offset = (image.0 - center[idx].0, image.1 - center[idx].1);
+                assert!([(-1, 1), (0, 1), (1, 0), (1, -1), (0, -1), (-1, 0)].contains(&offset));
+            }
+        }
+    }
+
+    #[test]
+    fn test_topology_json_names_parse() {
+        let topologies: Vec<Topology> =
+            serde_json::from_str(r#"["square-4", "square-8", "hex"]"#).unwrap();
+
+        assert_eq!(
+            topologies,
+            [Topology::Square4, Topology::Square8, Topology::Hex]
+        );
+    }
+}
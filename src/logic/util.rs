@@ -3,147 +3,134 @@ use std::collections::HashMap;
 use bevy::prelude::*;
 
 use crate::{
+    logic::{
+        puzzle::evaluation::evaluation::{EvaluationId, EvaluationReport},
+        topology::topology::GridPosition,
+    },
     scenes::puzzle::scene::scene::SatisfiedStatesMap,
     structs::{
-        active::{
-            active_identifier::active_identifier::ActiveIdentifier,
-            active_node::active_node::ActiveNode, active_set::active_set::ActiveSet,
-        },
+        active::{active_node::active_node::ActiveNode, active_set::active_set::ActiveSet},
         immutable::{
             connected_node_condition::connected_node_condition::ConnectedNodeCondition,
             connected_set_rule::connected_set_rule::ConnectedSetRule,
+            game_line::game_line::GameLine,
             game_node::game_node::{GameNode, GameNodeId},
             game_set::game_set::GameSet,
             puzzle::puzzle::Puzzle,
-            solution::solution::{active_nodes_to_solution, Solution},
+            solution::solution::Solution,
         },
     },
     SPRITE_SPACING,
 };
 
-pub fn is_left_edge(node: &u16, puzzle: &Puzzle) -> bool {
-    *node < puzzle.height as u16
-}
-
-pub fn is_top_edge(node: &u16, puzzle: &Puzzle) -> bool {
-    (*node + 1) % puzzle.height as u16 == 0
-}
-
-pub fn is_right_edge(node: &u16, puzzle: &Puzzle) -> bool {
-    *node + puzzle.height as u16 >= puzzle.width as u16 * puzzle.height as u16
+/// Returns the node if the puzzle has a node at its position, as puzzles may leave out
+/// positions of their grid.
+fn get_existing_node(node: u16, puzzle: &Puzzle) -> Option<u16> {
+    puzzle.has_node(node).then_some(node)
 }
 
-pub fn is_bottom_edge(node: &u16, puzzle: &Puzzle) -> bool {
-    *node % puzzle.height as u16 == 0
-}
-
-pub fn get_node_left(node: &u16, puzzle: &Puzzle) -> Option<u16> {
-    if !is_left_edge(node, puzzle) {
-        Some(*node - puzzle.height as u16)
-    } else {
-        None
-    }
-}
-
-pub fn get_node_up_left(node: &u16, puzzle: &Puzzle) -> Option<u16> {
-    if !is_left_edge(node, puzzle) && !is_top_edge(node, puzzle) {
-        Some(*node - puzzle.height as u16 + 1)
-    } else {
-        None
-    }
+/// Returns the column and row of the node on the grid.
+pub fn node_to_grid_position(node: &u16, puzzle: &Puzzle) -> GridPosition {
+    let height = puzzle.height as u16;
+    ((node / height) as i32, (node % height) as i32)
 }
 
-pub fn get_node_up(node: &u16, puzzle: &Puzzle) -> Option<u16> {
-    if !is_top_edge(node, puzzle) {
-        Some(*node + 1)
-    } else {
-        None
+/// Returns the node at the grid position, if the puzzle has a node there. Positions beyond the
+/// board wrap around to the other side on a toroidal board, and have no node otherwise.
+pub fn grid_position_to_node(position: GridPosition, puzzle: &Puzzle) -> Option<u16> {
+    let (width, height) = (puzzle.width as i32, puzzle.height as i32);
+    if width == 0 || height == 0 {
+        return None;
     }
-}
-
-pub fn get_node_up_right(node: &u16, puzzle: &Puzzle) -> Option<u16> {
-    if !is_top_edge(node, puzzle) && !is_right_edge(node, puzzle) {
-        Some(*node + puzzle.height as u16 + 1)
+    let (x, y) = if puzzle.toroidal {
+        (position.0.rem_euclid(width), position.1.rem_euclid(height))
     } else {
-        None
+        position
+    };
+    if x < 0 || y < 0 || x >= width || y >= height {
+        return None;
     }
+    get_existing_node(x as u16 * height as u16 + y as u16, puzzle)
 }
 
-pub fn get_node_right(node: &u16, puzzle: &Puzzle) -> Option<u16> {
-    if !is_right_edge(node, puzzle) {
-        Some(*node + puzzle.height as u16)
-    } else {
-        None
+/// Returns all nodes adjacent to the given node on the grid of the puzzle, in clockwise order.
+/// Boards too small to wrap around onto a different node only count each neighbor once.
+pub fn get_adjacent_nodes(node: &u16, puzzle: &Puzzle) -> Vec<u16> {
+    let mut adjacent_nodes: Vec<u16> = Vec::new();
+    for position in puzzle
+        .topology
+        .grid()
+        .neighbors(node_to_grid_position(node, puzzle))
+    {
+        if let Some(adjacent) = grid_position_to_node(position, puzzle) {
+            if adjacent != *node && !adjacent_nodes.contains(&adjacent) {
+                adjacent_nodes.push(adjacent);
+            }
+        }
     }
+    adjacent_nodes
 }
 
-pub fn get_node_down_right(node: &u16, puzzle: &Puzzle) -> Option<u16> {
-    if !is_right_edge(node, puzzle) && !is_bottom_edge(node, puzzle) {
-        Some(*node + puzzle.height as u16 - 1)
-    } else {
-        None
-    }
+/// Returns the grid position at which the neighbor is adjacent to the node. On a toroidal board
+/// this lies beyond the border for lines wrapping around it, so the line keeps its direction.
+/// Returns None if the nodes aren't adjacent.
+pub fn get_neighbor_position(node: &u16, neighbor: &u16, puzzle: &Puzzle) -> Option<GridPosition> {
+    let neighbor_position = node_to_grid_position(neighbor, puzzle);
+    let positions: Vec<GridPosition> = puzzle
+        .topology
+        .grid()
+        .neighbors(node_to_grid_position(node, puzzle))
+        .into_iter()
+        .filter(|position| grid_position_to_node(*position, puzzle) == Some(*neighbor))
+        .collect();
+
+    // Prefer reaching the neighbor within the board, if it is adjacent both ways
+    positions
+        .iter()
+        .find(|position| **position == neighbor_position)
+        .or(positions.first())
+        .copied()
 }
 
-pub fn get_node_down(node: &u16, puzzle: &Puzzle) -> Option<u16> {
-    if !is_bottom_edge(node, puzzle) {
-        Some(*node - 1)
-    } else {
-        None
-    }
+/// Returns true if the line between the nodes wraps around the border of a toroidal board.
+pub fn is_wrapping_line(node_a: &u16, node_b: &u16, puzzle: &Puzzle) -> bool {
+    get_neighbor_position(node_a, node_b, puzzle)
+        .is_some_and(|position| position != node_to_grid_position(node_b, puzzle))
 }
 
-pub fn get_node_down_left(node: &u16, puzzle: &Puzzle) -> Option<u16> {
-    if !is_bottom_edge(node, puzzle) && !is_left_edge(node, puzzle) {
-        Some(*node - puzzle.height as u16 - 1)
-    } else {
-        None
-    }
+/// Returns every pair of nodes adjacent to the given node in opposite directions, such that a
+/// path through the node and both of them runs straight.
+pub fn get_opposite_node_pairs(node: &u16, puzzle: &Puzzle) -> Vec<(u16, u16)> {
+    puzzle
+        .topology
+        .grid()
+        .opposite_neighbors(node_to_grid_position(node, puzzle))
+        .into_iter()
+        .filter_map(|(position_a, position_b)| {
+            Some((
+                grid_position_to_node(position_a, puzzle)?,
+                grid_position_to_node(position_b, puzzle)?,
+            ))
+        })
+        // Small toroidal boards can wrap both directions onto the same node
+        .filter(|(node_a, node_b)| node_a != node_b && node_a != node && node_b != node)
+        .collect()
 }
 
-pub fn get_adjacent_nodes(node: &u16, puzzle: &Puzzle) -> Vec<u16> {
-    let mut adjacent = Vec::new();
-    let height = puzzle.height as u16;
-    let node = *node;
-
-    let is_left_edge = node >= height;
-    let is_top_edge = node + 1 % height == 0;
-    let is_right_edge = node + height >= puzzle.width as u16 * height;
-    let is_bottom_edge = node % height == 0;
+/// Returns the center in the scene of the grid position, laid out by the grid of the puzzle.
+/// The position may lie beyond the board, e.g. where a wrapping line leaves it.
+pub fn grid_position_to_scene(position: GridPosition, puzzle: &Puzzle) -> (f32, f32) {
+    let (x, y) = puzzle.topology.grid().scene_position(position);
 
-    if !is_left_edge {
-        adjacent.push(node - height);
-    }
-    if !is_left_edge && !is_top_edge {
-        adjacent.push(node - height + 1);
-    }
-    if !is_top_edge {
-        adjacent.push(node + 1);
-    }
-    if !is_top_edge && !is_right_edge {
-        adjacent.push(node + height + 1);
-    }
-    if !is_right_edge {
-        adjacent.push(node + height);
-    }
-    if !is_right_edge && !is_bottom_edge {
-        adjacent.push(node + height - 1);
-    }
-    if !is_bottom_edge {
-        adjacent.push(node - 1);
-    }
-    if !is_bottom_edge && !is_left_edge {
-        adjacent.push(node - height - 1);
-    }
-
-    adjacent
+    (
+        x * SPRITE_SPACING * 2. + SPRITE_SPACING,
+        y * SPRITE_SPACING * 2. + SPRITE_SPACING,
+    )
 }
 
+/// Returns the center of the node in the scene, laid out by the grid of the puzzle.
 pub fn node_to_position(node: &u16, puzzle: &Puzzle) -> (f32, f32) {
-    let x = (node / puzzle.height as u16) as f32 * SPRITE_SPACING * 2. + SPRITE_SPACING;
-    let y = (node % puzzle.height as u16) as f32 * SPRITE_SPACING * 2. + SPRITE_SPACING;
-
-    (x, y)
+    grid_position_to_scene(node_to_grid_position(node, puzzle), puzzle)
 }
 
 pub fn is_mouse_over_sprite(sprite: &Sprite, sprite_transform: Transform, cursor: Vec2) -> bool {
@@ -156,29 +143,40 @@ pub fn is_mouse_over_sprite(sprite: &Sprite, sprite_transform: Transform, cursor
     false
 }
 
-/// Returns the bounded solution(s) for a given node if within a bounded set. If not in any bounded sets, it will
-/// return only the main solution.
-fn get_bounded_solutions(
+/// Returns the line which crosses the given line, e.g. the other diagonal of the same cell on a
+/// square grid. Lines which can't be crossed return None, as do crossing lines the puzzle leaves
+/// out by missing one of their nodes.
+pub fn get_crossing_line(line: &GameLine, puzzle: &Puzzle) -> Option<GameLine> {
+    // Lines wrapping around a toroidal board are crossed beyond the border they wrap across
+    let (position_a, position_b) = puzzle.topology.grid().crossing_line(
+        node_to_grid_position(&line.node_a_id(), puzzle),
+        get_neighbor_position(&line.node_a_id(), &line.node_b_id(), puzzle)?,
+    )?;
+    let corner_a = grid_position_to_node(position_a, puzzle)?;
+    let corner_b = grid_position_to_node(position_b, puzzle)?;
+    GameLine::new(corner_a, corner_b).ok()
+}
+
+/// Returns true if the solution contains any pair of crossing lines.
+pub fn has_crossing_lines(solution: &Solution, puzzle: &Puzzle) -> bool {
+    solution.iter().any(|line| {
+        get_crossing_line(line, puzzle).is_some_and(|crossing| solution.contains(&crossing))
+    })
+}
+
+/// Returns the bounded solution(s) for a given node if within a bounded or scoped set. If not in any such sets, it
+/// will return only the main solution.
+pub fn get_bounded_solutions(
     node_id: &GameNodeId,
-    active_sets: &Vec<ActiveSet>,
+    sets: Vec<&GameSet>,
     solution: &Solution,
 ) -> Vec<Solution> {
     let mut bounded_solutions: Vec<Solution> = vec![];
 
-    active_sets.iter().for_each(|set| {
-        if set.set.bounded && set.set.nodes.contains(node_id) {
+    sets.iter().for_each(|set| {
+        if set.is_scoped() && set.nodes.contains(node_id) {
             // Construct a new bounded solution consisting of only game lines which contain nodes both within the bounded set.
-            let mut bounded_solution: Solution = vec![];
-
-            solution.iter().for_each(|game_line| {
-                if set.set.nodes.contains(&game_line.node_a_id)
-                    && set.set.nodes.contains(&game_line.node_b_id)
-                {
-                    bounded_solution.push(*game_line);
-                }
-            });
-
-            bounded_solutions.push(bounded_solution);
+            bounded_solutions.push(solution.filter_to_set(set));
         }
     });
 
@@ -189,107 +187,139 @@ fn get_bounded_solutions(
     }
 }
 
-/// Returns SatisfiedStatesMap containing all nodes, conditions, and set rules.
+/// Returns SatisfiedStatesMap containing all nodes, conditions, and set rules, by mapping the evaluation report
+/// of the puzzle onto their active ids.
 pub fn get_all_satisfied_states(
-    active_nodes: &Vec<ActiveNode>,
-    active_sets: &Vec<ActiveSet>,
+    report: &EvaluationReport,
+    active_nodes: &[ActiveNode],
+    active_sets: &[ActiveSet],
 ) -> SatisfiedStatesMap {
     let mut satisfied_states: SatisfiedStatesMap = SatisfiedStatesMap::new();
-    let solution = active_nodes_to_solution(&active_nodes);
 
-    // Check all nodes and their conditions
+    // Active conditions and rules are created in the same order as in the puzzle, so their index is their id
     for node in active_nodes.iter() {
-        satisfied_states.insert(node.active_id, node.check_satisfied(&solution));
-
-        // Get the bounded solution for the condition if bounded, otherwise the original solution
-        let bounded_solutions = get_bounded_solutions(&node.node.id, &active_sets, &solution);
-
-        // Get the satisfied states of each condition.
-        for condition in node.active_conditions.iter() {
-            let mut all_bounds_satisfied = true;
-
-            bounded_solutions.iter().for_each(|bounded_solution| {
-                if !condition.check_satisfied(&node, &bounded_solution) {
-                    all_bounds_satisfied = false;
-                }
-            });
+        let node_id = node.node.id;
+        satisfied_states.insert(
+            node.active_id,
+            report.is_satisfied(&EvaluationId::Node(node_id)),
+        );
+        for (idx, condition) in node.active_conditions.iter().enumerate() {
+            satisfied_states.insert(
+                condition.active_id,
+                report.is_satisfied(&EvaluationId::NodeCondition(node_id, idx)),
+            );
+        }
+        for (idx, con_cdtn) in node.active_connected_conditions.iter().enumerate() {
+            satisfied_states.insert(
+                con_cdtn.active_id,
+                report.is_satisfied(&EvaluationId::ConnectedNodeCondition(node_id, idx)),
+            );
+        }
+    }
 
-            satisfied_states.insert(condition.active_id, all_bounds_satisfied);
+    for set in active_sets.iter() {
+        let set_id = set.set.id;
+        for (idx, rule) in set.active_set_rules.iter().enumerate() {
+            satisfied_states.insert(
+                rule.active_id,
+                report.is_satisfied(&EvaluationId::SetRule(set_id, idx)),
+            );
+        }
+        for (idx, meta_rule) in set.active_meta_set_rules.iter().enumerate() {
+            satisfied_states.insert(
+                meta_rule.active_id,
+                report.is_satisfied(&EvaluationId::MetaSetRule(set_id, idx)),
+            );
+        }
+        for (idx, con_rule) in set.active_connected_set_rules.iter().enumerate() {
+            satisfied_states.insert(
+                con_rule.active_id,
+                report.is_satisfied(&EvaluationId::ConnectedSetRule(set_id, idx)),
+            );
         }
     }
 
-    // Create map of connected node condition to active id of connected node condition
-    // TODO do this once upon loading puzzle
-    let mut con_cdtn_map: HashMap<ConnectedNodeCondition, Vec<&ActiveIdentifier>> = HashMap::new();
+    satisfied_states
+}
+
+/// Returns the id of the condition or rule whose sprite the cursor is over, if any. Like
+/// `get_all_satisfied_states`, this relies on active conditions and rules being in puzzle order.
+pub fn get_hovered_evaluation_id(
+    active_nodes: &[ActiveNode],
+    active_sets: &[ActiveSet],
+    cursor: Vec2,
+) -> Option<EvaluationId> {
+    let is_hovered =
+        |sprite: &SpriteBundle| is_mouse_over_sprite(&sprite.sprite, sprite.transform, cursor);
 
-    // Create groups mapping connected condition (and class) to game nodes
-    // TODO do this once upon loading puzzle
-    let mut con_cdtn_groups: HashMap<ConnectedNodeCondition, Vec<&GameNode>> = HashMap::new();
     for node in active_nodes.iter() {
-        for con_cdtn in node.active_connected_conditions.iter() {
-            // Update groups
-            if let Some(nodes) = con_cdtn_groups.get_mut(&con_cdtn.condition) {
-                nodes.push(&node.node);
-            } else {
-                con_cdtn_groups.insert(con_cdtn.condition, Vec::from([&node.node]));
-            }
-            // Update map to active ids
-            if let Some(active_ids) = con_cdtn_map.get_mut(&con_cdtn.condition) {
-                active_ids.push(&con_cdtn.active_id);
-            } else {
-                con_cdtn_map.insert(con_cdtn.condition, Vec::from([&con_cdtn.active_id]));
-            }
+        let node_id = node.node.id;
+        if let Some(idx) = node
+            .active_conditions
+            .iter()
+            .position(|condition| is_hovered(&condition.sprite))
+        {
+            return Some(EvaluationId::NodeCondition(node_id, idx));
         }
-    }
-
-    // Now update all connected conditions based on their grouping
-    con_cdtn_groups.iter().for_each(|(con_cdtn, nodes)| {
-        let sat = con_cdtn.is_satisfied(nodes.to_vec(), &solution);
-        for active_id in con_cdtn_map.get(con_cdtn).unwrap() {
-            satisfied_states.insert(**active_id, sat);
+        if let Some(idx) = node
+            .active_connected_conditions
+            .iter()
+            .position(|con_cdtn| is_hovered(&con_cdtn.sprite))
+        {
+            return Some(EvaluationId::ConnectedNodeCondition(node_id, idx));
         }
-    });
+    }
 
-    // Check all set rules
     for set in active_sets.iter() {
-        for rule in set.active_set_rules.iter() {
-            satisfied_states.insert(rule.active_id, rule.check_satisfied(&set, &solution));
+        let set_id = set.set.id;
+        if let Some(idx) = set
+            .active_set_rules
+            .iter()
+            .position(|rule| is_hovered(&rule.sprite))
+        {
+            return Some(EvaluationId::SetRule(set_id, idx));
+        }
+        if let Some(idx) = set
+            .active_meta_set_rules
+            .iter()
+            .position(|meta_rule| is_hovered(&meta_rule.sprite))
+        {
+            return Some(EvaluationId::MetaSetRule(set_id, idx));
+        }
+        if let Some(idx) = set
+            .active_connected_set_rules
+            .iter()
+            .position(|con_rule| is_hovered(&con_rule.sprite))
+        {
+            return Some(EvaluationId::ConnectedSetRule(set_id, idx));
         }
     }
 
-    // Create map of connected set rule to active id of connected set rule
-    // TODO do this once upon loading puzzle
-    let mut con_rule_map: HashMap<ConnectedSetRule, Vec<&ActiveIdentifier>> = HashMap::new();
+    None
+}
 
-    // Create groups mapping connected rules (and class) to game sets
-    // TODO do this once upon loading puzzle
-    let mut con_rule_groups: HashMap<ConnectedSetRule, Vec<&GameSet>> = HashMap::new();
-    for set in active_sets.iter() {
-        for con_rule in set.active_connected_set_rules.iter() {
-            // Update groups
-            if let Some(sets) = con_rule_groups.get_mut(&con_rule.rule) {
-                sets.push(&set.set);
-            } else {
-                con_rule_groups.insert(con_rule.rule, Vec::from([&set.set]));
-            }
-            // Update map to active ids
-            if let Some(active_ids) = con_rule_map.get_mut(&con_rule.rule) {
-                active_ids.push(&con_rule.active_id);
-            } else {
-                con_rule_map.insert(con_rule.rule, Vec::from([&con_rule.active_id]));
-            }
+/// Groups nodes by the connected node conditions (and class) they share.
+pub fn get_connected_condition_groups(
+    nodes: &[GameNode],
+) -> HashMap<ConnectedNodeCondition, Vec<&GameNode>> {
+    let mut con_cdtn_groups: HashMap<ConnectedNodeCondition, Vec<&GameNode>> = HashMap::new();
+    for node in nodes.iter() {
+        for con_cdtn in node.connected_conditions.iter() {
+            con_cdtn_groups.entry(*con_cdtn).or_default().push(node);
         }
     }
+    con_cdtn_groups
+}
 
-    // Now update all connected rules based on their grouping
-    con_rule_groups.iter().for_each(|(con_rule, sets)| {
-        let sat = con_rule.is_satisfied(sets.to_vec(), &solution);
-        for active_id in con_rule_map.get(con_rule).unwrap() {
-            satisfied_states.insert(**active_id, sat);
+/// Groups sets by the connected set rules (and class) they share.
+pub fn get_connected_rule_groups(sets: &[GameSet]) -> HashMap<ConnectedSetRule, Vec<&GameSet>> {
+    let mut con_rule_groups: HashMap<ConnectedSetRule, Vec<&GameSet>> = HashMap::new();
+    for set in sets.iter() {
+        for con_rule in set.connected_rules.iter() {
+            con_rule_groups.entry(*con_rule).or_default().push(set);
         }
-    });
-
-    satisfied_states
+    }
+    con_rule_groups
 }
 
 pub fn get_sets_containing_node(sets: Vec<GameSet>, node_id: GameNodeId) -> Vec<GameSet> {
@@ -72,6 +72,10 @@ fn main() {
         .finish();
     set_global_default(subscriber).expect("Setting default subscriber failed.");
 
+    // Load the campaign puzzles up front, skipping (and reporting in the menu) any invalid ones
+    let mut puzzle_manager = puzzle_manager::PuzzleManager::new();
+    puzzle_manager.populate_campaign();
+
     App::new()
         // Share the MousePosition resource for campaign and puzzle plugins
         .init_resource::<MousePosition>()
@@ -80,7 +84,7 @@ fn main() {
         // Share the CameraControl resource
         .init_resource::<MainCamera>()
         // Create a new puzzle manager to store puzzles (do it here and allow other plugins to manage shared load/unload)
-        .insert_resource(puzzle_manager::PuzzleManager::new())
+        .insert_resource(puzzle_manager)
         // Create a new api caller to interface with backend
         .insert_resource(api::NodalApi::new())
         // Adds the steamworks plugin (needs to be before Default for RenderPlugin)
@@ -100,7 +100,7 @@ pub mod campaign {
     fn campaign_setup(
         mut commands: Commands,
         asset_server: Res<AssetServer>,
-        mut puzzle_manager: ResMut<PuzzleManager>,
+        puzzle_manager: Res<PuzzleManager>,
         mut clickable_campaign_puzzles: ResMut<ClickableCampaignPuzzles>,
         q_window: Query<&Window, With<PrimaryWindow>>,
         mut q_camera: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
@@ -112,9 +112,6 @@ pub mod campaign {
         ))
         .unwrap();
 
-        // Populate the puzzle manager with the campaign puzzles
-        let _ = puzzle_manager.populate_campaign();
-
         // Get the texture that will be used for the puzzle images
         let puzzle_tex = asset_server.load(Texture::NodeCampaign.path());
 
@@ -131,8 +128,11 @@ pub mod campaign {
             // Check if puzzle is completed
             let solved = is_solved(campaign_puzzle.puzzle_uuid.clone());
 
-            // Check if puzzle is unlocked
-            let unlocked = is_unlocked(puzzle_grid.clone(), campaign_puzzle);
+            // Check if puzzle is unlocked (puzzles which failed to load stay locked)
+            let unlocked = is_unlocked(puzzle_grid.clone(), campaign_puzzle)
+                && puzzle_manager
+                    .get_puzzle_path(&campaign_puzzle.puzzle_uuid)
+                    .is_some();
 
             // Add the puzzle as a sprite
             let pos = get_campaign_puzzle_position(campaign_puzzle);
@@ -1,5 +1,13 @@
 pub mod menu {
-    use bevy::{app::AppExit, prelude::*};
+    use bevy::{
+        app::AppExit,
+        input::{
+            keyboard::{Key, KeyboardInput},
+            ButtonState,
+        },
+        prelude::*,
+        text::BreakLineOn,
+    };
     use uuid::Uuid;
 
     use crate::{
@@ -8,7 +16,10 @@ pub mod menu {
             button_icon_style, button_text_style, text_button_style, NORMAL_BUTTON, TEXT_COLOR,
         },
         despawn_screen,
+        logic::puzzle_code::puzzle_code::decode_puzzle,
+        puzzle_manager::PuzzleManager,
         texture::Texture,
+        ui::clipboard::clipboard::read_clipboard,
         AppState, SelectedPuzzle,
     };
 
@@ -22,6 +33,7 @@ pub mod menu {
             // entering the `GameState::Menu` state.
             // Current screen in the menu is handled by an independent state from `GameState`
             .init_state::<MenuState>()
+            .init_resource::<PastedCode>()
             .add_systems(OnEnter(AppState::Menu), menu_setup)
             // Systems to handle the main menu screen
             .add_systems(OnEnter(MenuState::Main), main_menu_setup)
@@ -32,6 +44,18 @@ pub mod menu {
                 OnExit(MenuState::SubMenu),
                 despawn_screen::<OnSubMenuScreen>,
             )
+            // Systems to handle the screen for pasting a puzzle code
+            .add_systems(OnEnter(MenuState::PasteCode), paste_code_setup)
+            .add_systems(
+                OnExit(MenuState::PasteCode),
+                despawn_screen::<OnPasteCodeScreen>,
+            )
+            .add_systems(
+                Update,
+                (paste_code_input, update_paste_code_text)
+                    .chain()
+                    .run_if(in_state(MenuState::PasteCode)),
+            )
             // Systems to handle the puzzle select screen
             // .add_systems(OnEnter(MenuState::PuzzleSelect), puzzle_select_setup)
             .add_systems(
@@ -47,6 +71,7 @@ pub mod menu {
     enum MenuState {
         Main,
         SubMenu,
+        PasteCode,
         PuzzleSelect,
         #[default]
         Disabled,
@@ -60,6 +85,26 @@ pub mod menu {
     #[derive(Component)]
     struct OnSubMenuScreen;
 
+    // Tag component used to tag entities added on the paste code screen
+    #[derive(Component)]
+    struct OnPasteCodeScreen;
+
+    // Tag component used to mark the text showing the pasted code
+    #[derive(Component)]
+    struct PastedCodeText;
+
+    // Tag component used to mark the text showing why the pasted code can't be played
+    #[derive(Component)]
+    struct PasteCodeErrorText;
+
+    /// Puzzle code being pasted on the paste code screen, and why it can't be played if it was
+    /// tried.
+    #[derive(Resource, Default)]
+    struct PastedCode {
+        code: String,
+        error: Option<String>,
+    }
+
     // Tag component used to tag entities added on the puzzle select screen
     #[derive(Component)]
     struct OnPuzzleSelectScreen;
@@ -69,6 +114,9 @@ pub mod menu {
     enum MenuButtonAction {
         Campaign,
         SubMenu,
+        PasteCode,
+        PasteFromClipboard,
+        PlayCode,
         PlayPuzzle,
         Quit,
     }
@@ -173,7 +221,11 @@ pub mod menu {
             });
     }
 
-    fn submenu_setup(mut commands: Commands, _asset_server: Res<AssetServer>) {
+    fn submenu_setup(
+        mut commands: Commands,
+        _asset_server: Res<AssetServer>,
+        puzzle_manager: Res<PuzzleManager>,
+    ) {
         commands
             .spawn((
                 NodeBundle {
@@ -216,11 +268,253 @@ pub mod menu {
                                     button_text_style(),
                                 ));
                             });
+                        // Paste a puzzle code shared by another player
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: text_button_style(),
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                MenuButtonAction::PasteCode,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    "Paste Code",
+                                    button_text_style(),
+                                ));
+                            });
                         // TODO browse levels, daily challenge
+
+                        // Report any campaign puzzles which were skipped since they failed to load
+                        let invalid_puzzles = puzzle_manager.get_invalid_puzzles();
+                        if !invalid_puzzles.is_empty() {
+                            let mut report =
+                                format!("{} puzzle(s) failed to load:", invalid_puzzles.len());
+                            for invalid_puzzle in invalid_puzzles {
+                                report.push_str(&format!("\n{}", invalid_puzzle));
+                            }
+                            parent.spawn(
+                                TextBundle::from_section(
+                                    report,
+                                    TextStyle {
+                                        font_size: 20.0,
+                                        color: TEXT_COLOR,
+                                        ..default()
+                                    },
+                                )
+                                .with_style(Style {
+                                    margin: UiRect::all(Val::Px(20.0)),
+                                    ..default()
+                                }),
+                            );
+                        }
+                    });
+            });
+    }
+
+    fn paste_code_setup(mut commands: Commands, mut pasted_code: ResMut<PastedCode>) {
+        pasted_code.error = None;
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                OnPasteCodeScreen,
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: Color::BLACK.into(),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            "Paste a puzzle code",
+                            button_text_style(),
+                        ));
+
+                        // Codes are long without spaces, so wrap them at any character
+                        let code_text = TextBundle::from_section(
+                            get_pasted_code_text(&pasted_code),
+                            TextStyle {
+                                font_size: 20.0,
+                                color: TEXT_COLOR,
+                                ..default()
+                            },
+                        );
+                        parent.spawn((
+                            TextBundle {
+                                text: Text {
+                                    linebreak_behavior: BreakLineOn::AnyCharacter,
+                                    ..code_text.text
+                                },
+                                ..code_text
+                            }
+                            .with_style(Style {
+                                width: Val::Px(600.0),
+                                margin: UiRect::all(Val::Px(20.0)),
+                                ..default()
+                            }),
+                            PastedCodeText,
+                        ));
+                        parent.spawn((
+                            TextBundle::from_section(
+                                "",
+                                TextStyle {
+                                    font_size: 20.0,
+                                    color: TEXT_COLOR,
+                                    ..default()
+                                },
+                            )
+                            .with_style(Style {
+                                max_width: Val::Px(600.0),
+                                ..default()
+                            }),
+                            PasteCodeErrorText,
+                        ));
+
+                        for (action, label) in [
+                            (MenuButtonAction::PasteFromClipboard, "Paste"),
+                            (MenuButtonAction::PlayCode, "Play"),
+                            (MenuButtonAction::SubMenu, "Back"),
+                        ] {
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: text_button_style(),
+                                        background_color: NORMAL_BUTTON.into(),
+                                        ..default()
+                                    },
+                                    action,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        label,
+                                        button_text_style(),
+                                    ));
+                                });
+                        }
                     });
             });
     }
 
+    /// Returns the text showing the pasted code, or how to enter one if there's none yet.
+    fn get_pasted_code_text(pasted_code: &PastedCode) -> String {
+        if pasted_code.code.is_empty() {
+            "Type a code, or paste one with Ctrl+V".to_string()
+        } else {
+            pasted_code.code.clone()
+        }
+    }
+
+    /// A system for editing the pasted code from the keyboard. Characters are typed in,
+    /// Backspace deletes the last one, Ctrl+V (Cmd+V on macOS) pastes from the clipboard and
+    /// Enter plays the code.
+    fn paste_code_input(
+        mut keyboard_events: EventReader<KeyboardInput>,
+        keys: Res<ButtonInput<KeyCode>>,
+        mut pasted_code: ResMut<PastedCode>,
+        mut puzzle_manager: ResMut<PuzzleManager>,
+        mut selected_puzzle: ResMut<SelectedPuzzle>,
+        mut menu_state: ResMut<NextState<MenuState>>,
+        mut app_state: ResMut<NextState<AppState>>,
+    ) {
+        let modifier_pressed = keys.any_pressed([
+            KeyCode::ControlLeft,
+            KeyCode::ControlRight,
+            KeyCode::SuperLeft,
+            KeyCode::SuperRight,
+        ]);
+        for event in keyboard_events.read() {
+            if event.state != ButtonState::Pressed {
+                continue;
+            }
+            match &event.logical_key {
+                Key::Enter => play_pasted_code(
+                    &mut pasted_code,
+                    &mut puzzle_manager,
+                    &mut selected_puzzle,
+                    &mut menu_state,
+                    &mut app_state,
+                ),
+                Key::Backspace => {
+                    pasted_code.code.pop();
+                }
+                Key::Character(text) if !modifier_pressed => pasted_code.code.push_str(text),
+                Key::Character(text) if text.eq_ignore_ascii_case("v") => {
+                    paste_from_clipboard(&mut pasted_code);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// A system for showing the pasted code, and why it can't be played if it was tried.
+    fn update_paste_code_text(
+        pasted_code: Res<PastedCode>,
+        mut q_code_text: Query<&mut Text, (With<PastedCodeText>, Without<PasteCodeErrorText>)>,
+        mut q_error_text: Query<&mut Text, (With<PasteCodeErrorText>, Without<PastedCodeText>)>,
+    ) {
+        if !pasted_code.is_changed() {
+            return;
+        }
+        for mut text in &mut q_code_text {
+            text.sections[0].value = get_pasted_code_text(&pasted_code);
+        }
+        for mut text in &mut q_error_text {
+            text.sections[0].value = pasted_code.error.clone().unwrap_or_default();
+        }
+    }
+
+    /// Replaces the pasted code with the text on the clipboard.
+    fn paste_from_clipboard(pasted_code: &mut PastedCode) {
+        match read_clipboard() {
+            Ok(text) => {
+                pasted_code.code = text.trim().to_string();
+                pasted_code.error = None;
+            }
+            Err(error) => {
+                warn!("Failed to read clipboard: {}", error);
+                pasted_code.error = Some(format!("Couldn't read the clipboard: {}", error));
+            }
+        }
+    }
+
+    /// Decodes the pasted code and plays its puzzle, or shows why the code can't be played.
+    fn play_pasted_code(
+        pasted_code: &mut PastedCode,
+        puzzle_manager: &mut PuzzleManager,
+        selected_puzzle: &mut SelectedPuzzle,
+        menu_state: &mut NextState<MenuState>,
+        app_state: &mut NextState<AppState>,
+    ) {
+        match decode_puzzle(&pasted_code.code) {
+            Ok(shared_puzzle) => {
+                pasted_code.error = None;
+                selected_puzzle.uuid = puzzle_manager.add_shared_puzzle(shared_puzzle);
+                app_state.set(AppState::Puzzle);
+                menu_state.set(MenuState::Disabled);
+            }
+            Err(error) => {
+                pasted_code.error = Some(format!("Couldn't load the code: {}", error));
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn menu_action(
         interaction_query: Query<
             (&Interaction, &MenuButtonAction, Option<&ButtonPuzzleId>),
@@ -230,6 +524,8 @@ pub mod menu {
         mut menu_state: ResMut<NextState<MenuState>>,
         mut app_state: ResMut<NextState<AppState>>,
         mut selected_puzzle: ResMut<SelectedPuzzle>,
+        mut pasted_code: ResMut<PastedCode>,
+        mut puzzle_manager: ResMut<PuzzleManager>,
         _api: Res<NodalApi>,
     ) {
         for (interaction, menu_button_action, button_puzzle_id) in &interaction_query {
@@ -241,6 +537,19 @@ pub mod menu {
                     MenuButtonAction::SubMenu => {
                         menu_state.set(MenuState::SubMenu);
                     }
+                    MenuButtonAction::PasteCode => {
+                        menu_state.set(MenuState::PasteCode);
+                    }
+                    MenuButtonAction::PasteFromClipboard => {
+                        paste_from_clipboard(&mut pasted_code);
+                    }
+                    MenuButtonAction::PlayCode => play_pasted_code(
+                        &mut pasted_code,
+                        &mut puzzle_manager,
+                        &mut selected_puzzle,
+                        &mut menu_state,
+                        &mut app_state,
+                    ),
                     MenuButtonAction::Campaign => {
                         app_state.set(AppState::Campaign);
                         menu_state.set(MenuState::Disabled);
@@ -1,5 +1,5 @@
 pub mod scene {
-    use std::{collections::HashSet, process::exit};
+    use std::collections::HashSet;
 
     use bevy::{
         app::{App, Update},
@@ -13,46 +13,56 @@ pub mod scene {
         },
         hierarchy::BuildChildren,
         input::{mouse::MouseButton, ButtonInput},
+        log::{info, warn},
         math::{Vec2, Vec3},
         prelude::IntoSystemConfigs,
         render::camera::OrthographicProjection,
+        render::view::Visibility,
         sprite::{Sprite, SpriteBundle},
         state::{
             condition::in_state,
             state::{NextState, OnEnter, OnExit},
         },
+        text::{Text, TextStyle},
         transform::components::Transform,
         ui::{
-            node_bundles::{ButtonBundle, NodeBundle},
+            node_bundles::{ButtonBundle, NodeBundle, TextBundle},
             widget::Button,
-            AlignItems, Interaction, JustifyContent, Style, UiImage, Val,
+            AlignItems, Interaction, JustifyContent, PositionType, Style, UiImage, UiRect, Val,
         },
         utils::HashMap, window::{PrimaryWindow, Window},
     };
     use itertools::Itertools;
 
     use crate::{
-        buttons::icon_button_style,
-        despawn_screen, get_all_satisfied_states, is_mouse_over_sprite,
+        buttons::{button_text_style, icon_button_style, NORMAL_BUTTON, TEXT_COLOR},
+        despawn_screen, get_all_satisfied_states, get_hovered_evaluation_id, is_mouse_over_sprite,
         logic::{
             puzzle::{
+                evaluation::evaluation::IncrementalEvaluator,
+                hint::hint::{find_hint, Hint},
                 solution_check::solution_check::is_puzzle_solved,
                 tile_placement::tile_placement::get_set_upper_left_node,
             },
+            puzzle_code::puzzle_code::encode_puzzle,
             save_data_manager::save_data_manager::{load_progress, save_progress, PuzzleSaveData},
         },
         node_to_position,
         puzzle_manager::PuzzleManager,
         scenes::puzzle::util::{
             add_line, clear_all_lines, exit_puzzle, get_color_for_set_tile,
-            get_mut_start_end_nodes, get_set_tiles, remove_line, unload_active_elements, update_camera,
+            get_forbidden_line_sprites, get_mut_start_end_nodes, get_puzzle_background_tile,
+            get_set_tiles, remove_line, spawn_hint_line, spawn_sprite_label,
+            unload_active_elements, update_camera,
         },
         structs::{
             active::{
                 active_connected_node_condition::active_connected_node_condition::ActiveConnectedNodeCondition,
                 active_connected_set_rule::active_connected_set_rule::ActiveConnectedSetRule,
                 active_identifier::active_identifier::ActiveIdentifier,
-                active_line::active_line::ActiveLine, active_node::active_node::ActiveNode,
+                active_line::active_line::ActiveLine,
+                active_meta_set_rule::active_meta_set_rule::ActiveMetaSetRule,
+                active_node::active_node::ActiveNode,
                 active_node_condition::active_node_condition::ActiveNodeCondition,
                 active_set::active_set::ActiveSet, active_set_rule::active_set_rule::ActiveSetRule,
                 traits::traits::Satisfiable,
@@ -60,14 +70,18 @@ pub mod scene {
             immutable::{
                 connected_node_condition::connected_node_condition::ConnectedNodeCondition,
                 connected_set_rule::connected_set_rule::ConnectedSetRule,
-                game_node::game_node::GameNodeId, node_condition::node_condition::NodeCondition,
+                game_line::game_line::GameLine, game_node::game_node::GameNodeId,
+                meta_set_rule::meta_set_rule::MetaSetRule,
+                node_condition::node_condition::NodeCondition, puzzle::puzzle::Puzzle,
                 set_rule::set_rule::SetRule, solution::solution::active_nodes_to_solution,
             },
         },
         texture::Texture,
+        ui::clipboard::clipboard::write_clipboard,
         AppState, Hoverable, MainCamera, MousePosition, SelectedPuzzle, CDTN_RULE_SPRITE_SIZE,
-        COLOR_NODE_UNSAT, INTERNAL_SPACING_X, INTERNAL_SPACING_Y, SPRITE_SPACING,
-        STACK_CDTN_RULE_SPACING, TILE_NODE_SPRITE_SIZE, Z_RULE_CDTN_NODE, Z_SET_RULE_BOX,
+        COLOR_HINT_DRAW, COLOR_HINT_REMOVE, COLOR_NODE_UNSAT, COLOR_TOOLTIP_BG, INTERNAL_SPACING_X,
+        INTERNAL_SPACING_Y, STACK_CDTN_RULE_SPACING, TILE_NODE_SPRITE_SIZE, TOOLTIP_FONT_SIZE,
+        TOOLTIP_OFFSET, TOOLTIP_PADDING, Z_RULE_CDTN_NODE, Z_SET_RULE_BOX,
     };
 
     // This plugin will contain a playable puzzle.
@@ -77,6 +91,10 @@ pub mod scene {
             .add_systems(OnExit(AppState::Puzzle), despawn_screen::<OnPuzzleUI>)
             .add_systems(Update, line_system.run_if(in_state(AppState::Puzzle)))
             .add_systems(Update, ui_action.run_if(in_state(AppState::Puzzle)))
+            .add_systems(
+                Update,
+                diagnostic_tooltip_system.run_if(in_state(AppState::Puzzle)),
+            )
             .add_event::<UpdateSatisfiedStates>()
             .add_event::<PuzzleSolved>()
             .add_systems(Update, update_satisfied_states_ui)
@@ -85,6 +103,8 @@ pub mod scene {
             .insert_resource(ActiveSets::default())
             .insert_resource(ActiveLines::default())
             .insert_resource(CurrentLine::default())
+            .insert_resource(CurrentPuzzle::default())
+            .insert_resource(CurrentHint::default())
             .insert_resource(PreviouslySolved::default());
     }
 
@@ -127,6 +147,19 @@ pub mod scene {
         start_node_id: Option<u16>,
     }
 
+    // The puzzle currently being played, along with the evaluation of the lines drawn in it
+    #[derive(Default, Resource)]
+    struct CurrentPuzzle {
+        evaluator: Option<IncrementalEvaluator>,
+    }
+
+    // Tracks the hints shown in the puzzle, and the line highlighted by the last one
+    #[derive(Default, Resource)]
+    struct CurrentHint {
+        hints_used: u32,
+        hint_line_entities: Vec<Entity>,
+    }
+
     // Tag component used to tag entities added on the puzzle scene
     #[derive(Component)]
     pub struct OnPuzzleScene;
@@ -135,9 +168,15 @@ pub mod scene {
     #[derive(Component)]
     struct OnPuzzleUI;
 
+    // Tag component used to tag the tooltip explaining the hovered condition or rule
+    #[derive(Component)]
+    struct DiagnosticTooltip;
+
     // All actions that can be triggered from a button click
     #[derive(Component)]
     enum PuzzleButtonAction {
+        Hint,
+        Share,
         Reset,
         ReturnToPreviousPage,
     }
@@ -146,12 +185,14 @@ pub mod scene {
         mut commands: Commands,
         asset_server: Res<AssetServer>,
         puzzle_id: Res<SelectedPuzzle>,
-        puzzle_manager: Res<PuzzleManager>,
+        mut puzzle_manager: ResMut<PuzzleManager>,
         mut active_nodes: ResMut<ActiveNodes>,
         mut active_sets: ResMut<ActiveSets>,
         mut active_lines: ResMut<ActiveLines>,
         mut event_writer: EventWriter<UpdateSatisfiedStates>,
         mut previously_solved: ResMut<PreviouslySolved>,
+        mut current_puzzle: ResMut<CurrentPuzzle>,
+        mut current_hint: ResMut<CurrentHint>,
         // Query to get camera transform
         mut q_camera: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
         // Query to get executable window
@@ -166,10 +207,6 @@ pub mod scene {
         }
         let puzzle = opt_puzzle.unwrap();
 
-        // Sort nodes by id (bottom to top, left to right)
-        let mut ordered_nodes = puzzle.nodes.clone();
-        ordered_nodes.sort_by(|a, b| a.id.cmp(&b.id));
-
         // TODO load only when needed, then cache in map to re-access in the screen spawning loop
         // Load node textures
         let tex_node = asset_server.load(Texture::NodePuzzle.path());
@@ -179,39 +216,60 @@ pub mod scene {
         let tex_cdtn_leaf = asset_server.load(Texture::CdtnLeaf.path());
         let tex_cdtn_internal = asset_server.load(Texture::CdtnInternal.path());
         let tex_cdtn_cycle = asset_server.load(Texture::CdtnCycle.path());
+        let tex_cdtn_degree = asset_server.load(Texture::CdtnDegree.path());
+        let tex_cdtn_cut_vertex = asset_server.load(Texture::CdtnCutVertex.path());
+        let tex_cdtn_straight = asset_server.load(Texture::CdtnStraight.path());
         let tex_cdtn_degree_equal = asset_server.load(Texture::CdtnDegreeEqual.path());
         let tex_cdtn_distance_equal = asset_server.load(Texture::CdtnDistanceEqual.path());
+        let tex_cdtn_degree_distinct = asset_server.load(Texture::CdtnDegreeDistinct.path());
+        let tex_cdtn_distance_distinct = asset_server.load(Texture::CdtnDistanceDistinct.path());
+        let tex_cdtn_same_component_path = asset_server.load(Texture::CdtnSameComponentPath.path());
 
         // Load set rule textures
         let tex_rule_disconnected = asset_server.load(Texture::SetRuleDisconnected.path());
         let tex_rule_leaf = asset_server.load(Texture::SetRuleLeaf.path());
+        let tex_rule_connected = asset_server.load(Texture::SetRuleConnected.path());
+        let tex_rule_acyclic = asset_server.load(Texture::SetRuleAcyclic.path());
+        let tex_rule_edge_count = asset_server.load(Texture::SetRuleEdgeCount.path());
+        let tex_rule_closed = asset_server.load(Texture::SetRuleClosed.path());
         let tex_rule_homomorphism = asset_server.load(Texture::SetRuleHomomorphism.path());
+        let tex_rule_isomorphism = asset_server.load(Texture::SetRuleIsomorphism.path());
+        let tex_rule_congruent = asset_server.load(Texture::SetRuleCongruent.path());
+        let tex_rule_edge_count_equal = asset_server.load(Texture::SetRuleEdgeCountEqual.path());
+        let tex_rule_mutually_disconnected =
+            asset_server.load(Texture::SetRuleMutuallyDisconnected.path());
+        let tex_rule_xor = asset_server.load(Texture::SetRuleXor.path());
+        let tex_rule_scope = asset_server.load(Texture::SetRuleScope.path());
 
         // Load set rule box texture
         let tex_rule_box = asset_server.load(Texture::SetRuleBox.path());
 
         // Create a width x height grid of nodes as sprite bundles, accounting for background tiles
+        // and holes
         for x in 0..puzzle.width * 2 + 1 {
             for y in 0..puzzle.height * 2 + 1 {
-                // If background tile, spawn it and continue
+                // If background tile, spawn it and continue. Tiles lie between the cells of a
+                // square grid, so other grids go without them
                 if x % 2 == 0 || y % 2 == 0 {
-                    // TODO add back the bg tiles if necessary
-                    // commands.spawn((
-                    //     get_puzzle_background_tile(x, y, puzzle.width, puzzle.height, asset_server.clone()),
-                    //     OnPuzzleScene,
-                    // ));
+                    if puzzle.topology.is_square() {
+                        if let Some(tile) =
+                            get_puzzle_background_tile(x, y, &puzzle, asset_server.clone())
+                        {
+                            commands.spawn((tile, OnPuzzleScene));
+                        }
+                    }
                     continue;
                 }
 
-                let node = ordered_nodes
-                    .get((x / 2 * puzzle.height + y / 2) as usize)
-                    .unwrap_or_else(|| {
-                        exit(1);
-                    });
+                // Positions the puzzle leaves out are holes in the board
+                let node_id =
+                    (x / 2) as GameNodeId * puzzle.height as GameNodeId + (y / 2) as GameNodeId;
+                let Some(node) = puzzle.get_node(node_id) else {
+                    continue;
+                };
 
-                // Spawn the node on screen
-                let node_x = x as f32 * SPRITE_SPACING;
-                let node_y = y as f32 * SPRITE_SPACING;
+                // Spawn the node on screen, where the grid of the puzzle places it
+                let (node_x, node_y) = node_to_position(&node_id, &puzzle);
 
                 // TODO move this to ActiveNode passing node_x and node_y
                 let node_sprite = SpriteBundle {
@@ -241,6 +299,9 @@ pub mod scene {
                         NodeCondition::Leaf => tex_cdtn_leaf.clone(),
                         NodeCondition::Internal => tex_cdtn_internal.clone(),
                         NodeCondition::Cycle => tex_cdtn_cycle.clone(),
+                        NodeCondition::Degree(_) => tex_cdtn_degree.clone(),
+                        NodeCondition::CutVertex => tex_cdtn_cut_vertex.clone(),
+                        NodeCondition::Straight => tex_cdtn_straight.clone(),
                     };
 
                     let condition_sprite = SpriteBundle {
@@ -262,6 +323,10 @@ pub mod scene {
                         .insert(OnPuzzleScene)
                         .id();
 
+                    if let NodeCondition::Degree(degree) = condition {
+                        spawn_sprite_label(&mut commands, *degree, condition_sprite.transform);
+                    }
+
                     active_node_conditions.push(ActiveNodeCondition {
                         active_id: ActiveIdentifier::new(),
                         sprite: condition_sprite,
@@ -284,11 +349,20 @@ pub mod scene {
                         ConnectedNodeCondition::DistanceEqual(_cond_cdtn) => {
                             tex_cdtn_distance_equal.clone()
                         }
+                        ConnectedNodeCondition::DegreeDistinct(_con_cdtn) => {
+                            tex_cdtn_degree_distinct.clone()
+                        }
+                        ConnectedNodeCondition::DistanceDistinct(_con_cdtn) => {
+                            tex_cdtn_distance_distinct.clone()
+                        }
+                        ConnectedNodeCondition::SameComponentPath(_con_cdtn) => {
+                            tex_cdtn_same_component_path.clone()
+                        }
                     };
 
                     let con_cdtn_sprite = SpriteBundle {
                         texture: con_cdtn_texture,
-                        sprite: con_cdtn.sprite().clone(),
+                        sprite: con_cdtn.sprite(&puzzle.palette),
                         transform: Transform::from_xyz(
                             node_x + TILE_NODE_SPRITE_SIZE - INTERNAL_SPACING_X,
                             node_y + TILE_NODE_SPRITE_SIZE
@@ -311,6 +385,7 @@ pub mod scene {
                         sprite: con_cdtn_sprite,
                         sprite_entity_id: connected_condition_sprite_id,
                         satisfied: false,
+                        colors: puzzle.palette.condition_colors(con_cdtn.condition_class()),
                     });
 
                     total_cdtn_idx += 1;
@@ -363,6 +438,30 @@ pub mod scene {
                 //     },
                 //     PuzzleButtonAction::CheckAnswer,
                 // ));
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..icon_button_style()
+                            },
+                            background_color: NORMAL_BUTTON.into(),
+                            ..Default::default()
+                        },
+                        PuzzleButtonAction::Hint,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section("?", button_text_style()));
+                    });
+                parent.spawn((
+                    ButtonBundle {
+                        style: icon_button_style(),
+                        image: UiImage::new(asset_server.load(Texture::BtnShare.path())),
+                        ..Default::default()
+                    },
+                    PuzzleButtonAction::Share,
+                ));
                 parent.spawn((
                     ButtonBundle {
                         style: icon_button_style(),
@@ -381,6 +480,29 @@ pub mod scene {
                 ));
             });
 
+        // Tooltip shown while hovering a condition or rule, hidden until then
+        commands.spawn((
+            TextBundle {
+                visibility: Visibility::Hidden,
+                ..TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: TOOLTIP_FONT_SIZE,
+                        color: TEXT_COLOR,
+                        ..Default::default()
+                    },
+                )
+                .with_style(Style {
+                    position_type: PositionType::Absolute,
+                    padding: UiRect::all(Val::Px(TOOLTIP_PADDING)),
+                    ..Default::default()
+                })
+                .with_background_color(COLOR_TOOLTIP_BG)
+            },
+            DiagnosticTooltip,
+            OnPuzzleUI,
+        ));
+
         // Create map of nodes by id to the total rules using that node as reference, in case of overlapping sets by top left corner
         let mut node_to_rule_count_map: HashMap<GameNodeId, u8> = HashMap::new();
 
@@ -407,11 +529,65 @@ pub mod scene {
                 total_rule_idx += existing_offset;
             }
 
+            let mut active_meta_set_rules: Vec<ActiveMetaSetRule> = vec![];
+
+            // Meta rules go first, as they apply to the set rules below them
+            for meta_rule in set.meta_rules.iter() {
+                let meta_rule_texture = match meta_rule {
+                    MetaSetRule::Xor => tex_rule_xor.clone(),
+                };
+
+                let transform_x = node_x - TILE_NODE_SPRITE_SIZE + INTERNAL_SPACING_X;
+                let transform_y = node_y + TILE_NODE_SPRITE_SIZE
+                    - INTERNAL_SPACING_Y
+                    - total_rule_idx as f32 * (CDTN_RULE_SPRITE_SIZE + STACK_CDTN_RULE_SPACING);
+                commands
+                    .spawn(SpriteBundle {
+                        texture: tex_rule_box.clone(),
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::new(
+                                CDTN_RULE_SPRITE_SIZE,
+                                CDTN_RULE_SPRITE_SIZE,
+                            )),
+                            color: get_color_for_set_tile(set.clone(), puzzle_sets.clone()),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_xyz(transform_x, transform_y, Z_SET_RULE_BOX),
+                        ..Default::default()
+                    })
+                    .insert(OnPuzzleScene);
+                let meta_rule_sprite = SpriteBundle {
+                    texture: meta_rule_texture,
+                    sprite: meta_rule.sprite(),
+                    transform: Transform::from_xyz(transform_x, transform_y, Z_RULE_CDTN_NODE),
+                    ..Default::default()
+                };
+                let meta_rule_sprite_id = commands
+                    .spawn(meta_rule_sprite.clone())
+                    .insert(OnPuzzleScene)
+                    .id();
+
+                active_meta_set_rules.push(ActiveMetaSetRule {
+                    active_id: ActiveIdentifier::new(),
+                    rule: *meta_rule,
+                    sprite: meta_rule_sprite,
+                    sprite_entity_id: meta_rule_sprite_id,
+                    satisfied: false,
+                });
+
+                total_rule_idx += 1;
+            }
+
             for rule in set.rules.iter() {
                 // TODO get textures via either set_rule.rs or texture.rs
                 let rule_texture = match rule {
                     SetRule::Disconnected => tex_rule_disconnected.clone(),
                     SetRule::Leaf => tex_rule_leaf.clone(),
+                    SetRule::Scope => tex_rule_scope.clone(),
+                    SetRule::Connected => tex_rule_connected.clone(),
+                    SetRule::Acyclic => tex_rule_acyclic.clone(),
+                    SetRule::EdgeCount(_) => tex_rule_edge_count.clone(),
+                    SetRule::Closed => tex_rule_closed.clone(),
                 };
 
                 let transform_x = node_x - TILE_NODE_SPRITE_SIZE + INTERNAL_SPACING_X;
@@ -443,6 +619,9 @@ pub mod scene {
                     .spawn(rule_sprite.clone())
                     .insert(OnPuzzleScene)
                     .id();
+                if let SetRule::EdgeCount(count) = rule {
+                    spawn_sprite_label(&mut commands, *count, rule_sprite.transform);
+                }
 
                 active_set_rules.push(ActiveSetRule {
                     active_id: ActiveIdentifier::new(),
@@ -461,6 +640,12 @@ pub mod scene {
                 // TODO get textures via either connected_set_rule.rs or texture.rs
                 let crule_texture = match crule {
                     ConnectedSetRule::Homomorphic(_crule) => tex_rule_homomorphism.clone(),
+                    ConnectedSetRule::Isomorphic(_crule) => tex_rule_isomorphism.clone(),
+                    ConnectedSetRule::Congruent(_crule) => tex_rule_congruent.clone(),
+                    ConnectedSetRule::EdgeCountEqual(_crule) => tex_rule_edge_count_equal.clone(),
+                    ConnectedSetRule::MutuallyDisconnected(_crule) => {
+                        tex_rule_mutually_disconnected.clone()
+                    }
                 };
 
                 let transform_x = node_x - TILE_NODE_SPRITE_SIZE + INTERNAL_SPACING_X;
@@ -484,7 +669,7 @@ pub mod scene {
                     .insert(OnPuzzleScene);
                 let crule_sprite = SpriteBundle {
                     texture: crule_texture,
-                    sprite: crule.sprite().clone(),
+                    sprite: crule.sprite(&puzzle.palette),
                     transform: Transform::from_xyz(transform_x, transform_y, Z_RULE_CDTN_NODE),
                     ..Default::default()
                 };
@@ -499,6 +684,7 @@ pub mod scene {
                     sprite: crule_sprite,
                     sprite_entity_id: crule_sprite_id,
                     satisfied: false,
+                    colors: puzzle.palette.rule_colors(crule.rule_class()),
                 });
 
                 total_rule_idx += 1;
@@ -513,44 +699,93 @@ pub mod scene {
                 active_id: ActiveIdentifier::new(),
                 active_set_rules: active_set_rules,
                 active_connected_set_rules: active_connected_set_rules,
+                active_meta_set_rules,
                 sprites: set_tiles,
                 sprite_entity_ids: set_sprite_entity_ids,
             });
         }
 
-        // Load progress on puzzle if exists
-        let puzzle_save_data: Option<PuzzleSaveData> = load_progress(puzzle.uuid);
-        match puzzle_save_data {
-            Some(data) => {
+        // Hints of a previous puzzle don't apply to this one
+        *current_hint = CurrentHint::default();
+
+        // Draw the lines fixed by the puzzle, and mark the lines it forbids
+        let find_node_idx = |active_nodes: &[ActiveNode], id: GameNodeId| {
+            active_nodes
+                .iter()
+                .position(|active_node| active_node.node.id == id)
+        };
+        for line in puzzle.fixed_lines.iter() {
+            let nodes = &mut active_nodes.active_nodes;
+            if let (Some(i), Some(j)) = (
+                find_node_idx(nodes, line.node_a_id()),
+                find_node_idx(nodes, line.node_b_id()),
+            ) {
+                let (start_node, end_node) = get_mut_start_end_nodes(nodes, i, j);
+                add_line(
+                    &mut commands,
+                    asset_server.clone(),
+                    start_node,
+                    end_node,
+                    &mut active_lines.lines,
+                    &puzzle,
+                );
+            }
+        }
+        for line in puzzle.forbidden_lines.iter() {
+            let nodes = &active_nodes.active_nodes;
+            if let (Some(i), Some(j)) = (
+                find_node_idx(nodes, line.node_a_id()),
+                find_node_idx(nodes, line.node_b_id()),
+            ) {
+                let sprites =
+                    get_forbidden_line_sprites(&nodes[i], &nodes[j], &puzzle, &asset_server);
+                for sprite in sprites {
+                    commands.spawn(sprite).insert(OnPuzzleScene);
+                }
+            }
+        }
+
+        // Load the lines shared along with a puzzle from a code, or else progress on the puzzle if
+        // exists
+        let loaded_solution = match puzzle_manager.take_shared_solution(&puzzle.uuid) {
+            Some(solution) => Some(solution),
+            None => load_progress(&puzzle).map(|data: PuzzleSaveData| {
                 // Mark it as previously solved or not
                 previously_solved.solved = data.solved;
-
-                for line in data.solution {
-                    for i in 0..active_nodes.active_nodes.len() {
-                        for j in 0..active_nodes.active_nodes.len() {
-                            if active_nodes.active_nodes[i].node.id == line.node_a_id
-                                && active_nodes.active_nodes[j].node.id == line.node_b_id
-                            {
-                                let (start_node, end_node) =
-                                    get_mut_start_end_nodes(&mut active_nodes.active_nodes, i, j);
-                                add_line(
-                                    &mut commands,
-                                    asset_server.clone(),
-                                    start_node,
-                                    end_node,
-                                    &mut active_lines.lines,
-                                )
-                            }
-                        }
+                current_hint.hints_used = data.hints_used;
+                data.solution
+            }),
+        };
+        for line in loaded_solution.into_iter().flatten() {
+            for i in 0..active_nodes.active_nodes.len() {
+                for j in 0..active_nodes.active_nodes.len() {
+                    if active_nodes.active_nodes[i].node.id == line.node_a_id()
+                        && active_nodes.active_nodes[j].node.id == line.node_b_id()
+                    {
+                        let (start_node, end_node) =
+                            get_mut_start_end_nodes(&mut active_nodes.active_nodes, i, j);
+                        add_line(
+                            &mut commands,
+                            asset_server.clone(),
+                            start_node,
+                            end_node,
+                            &mut active_lines.lines,
+                            &puzzle,
+                        )
                     }
                 }
-                event_writer.send(UpdateSatisfiedStates(get_all_satisfied_states(
-                    &active_nodes.active_nodes,
-                    &active_sets.active_sets,
-                )));
             }
-            None => {}
         }
+
+        // Evaluate the loaded lines once, after which line changes are evaluated incrementally
+        let evaluator =
+            IncrementalEvaluator::new(puzzle, active_nodes_to_solution(&active_nodes.active_nodes));
+        event_writer.send(UpdateSatisfiedStates(get_all_satisfied_states(
+            evaluator.report(),
+            &active_nodes.active_nodes,
+            &active_sets.active_sets,
+        )));
+        current_puzzle.evaluator = Some(evaluator);
     }
 
     /// A system for handling lines added to the puzzle.
@@ -561,6 +796,8 @@ pub mod scene {
     /// - `active_nodes`: Resource containing all active nodes in the puzzle.
     /// - `current_line`: Resource containing the current line being drawn by the user.
     /// - `lines`: Resource containing all lines in the puzzle.
+    /// - `current_puzzle`: Resource containing the puzzle being played, used to check which lines may be added and to evaluate them.
+    /// - `current_hint`: Resource containing the last hint, whose highlight is removed once the lines change.
     /// - `mouse_button_input`: Bevy's mouse button input system, used to check if the left mouse button is pressed.
     /// - `q_window`: Bevy's query system, used to get the window, so we can read current cursor position.
     /// - `q_camera`: Bevy's query system, used to get the camera transform to also read current cursor position.
@@ -571,6 +808,8 @@ pub mod scene {
         active_sets: Res<ActiveSets>,
         mut current_line: ResMut<CurrentLine>,
         mut active_lines: ResMut<ActiveLines>,
+        mut current_puzzle: ResMut<CurrentPuzzle>,
+        mut current_hint: ResMut<CurrentHint>,
         mouse_button_input: Res<ButtonInput<MouseButton>>,
         mouse_position: Res<MousePosition>,
         mut event_writer: EventWriter<UpdateSatisfiedStates>,
@@ -594,6 +833,11 @@ pub mod scene {
                 return;
             }
 
+            // If the puzzle isn't loaded yet, return
+            let Some(evaluator) = current_puzzle.evaluator.as_mut() else {
+                return;
+            };
+
             // Grab the start node and end node objects from the active_nodes as an iter_mut
             let mut opt_start_node: Option<&mut ActiveNode> = None;
             let mut opt_end_node: Option<&mut ActiveNode> = None;
@@ -619,12 +863,19 @@ pub mod scene {
 
             let start_node = opt_start_node.unwrap();
             let end_node = opt_end_node.unwrap();
+            let line = GameLine::new(start_node.node.id, end_node.node.id).ok();
+
+            // The lines are about to change, so the last hint may no longer apply
+            clear_hint_line(&mut commands, &mut current_hint);
 
             // If start node and end node both have each other as connection, remove the line.
             if start_node.connections.contains(&end_node.node.id)
                 && end_node.connections.contains(&start_node.node.id)
             {
                 remove_line(&mut commands, start_node, end_node, &mut active_lines.lines);
+                if let Some(line) = line {
+                    evaluator.remove_line(&line);
+                }
             // Otherwise, add a new line
             } else {
                 add_line(
@@ -633,21 +884,72 @@ pub mod scene {
                     start_node,
                     end_node,
                     &mut active_lines.lines,
+                    evaluator.puzzle(),
                 );
+                // The line isn't added if it's invalid, e.g. if it crosses another line
+                if let Some(line) = line {
+                    if start_node.connections.contains(&end_node.node.id) {
+                        evaluator.add_line(line);
+                    }
+                }
             }
 
             // Regardless if we ended on a node or not, clear the current line
             current_line.start_node_id = None;
 
-            // Update satisfied states given the current start and end node
-            let satisfied_states =
-                get_all_satisfied_states(&active_nodes.active_nodes, &active_sets.active_sets);
+            // Update satisfied states given the lines now drawn
+            let satisfied_states = get_all_satisfied_states(
+                evaluator.report(),
+                &active_nodes.active_nodes,
+                &active_sets.active_sets,
+            );
 
             // Send an event to update all the relevant states visually
             event_writer.send(UpdateSatisfiedStates(satisfied_states));
         }
     }
 
+    /// A system for showing why the condition or rule under the cursor is (un)satisfied, in a
+    /// tooltip next to the cursor.
+    fn diagnostic_tooltip_system(
+        current_puzzle: Res<CurrentPuzzle>,
+        active_nodes: Res<ActiveNodes>,
+        active_sets: Res<ActiveSets>,
+        mouse_position: Res<MousePosition>,
+        q_window: Query<&Window, With<PrimaryWindow>>,
+        mut q_tooltip: Query<(&mut Text, &mut Style, &mut Visibility), With<DiagnosticTooltip>>,
+    ) {
+        let Ok((mut text, mut style, mut visibility)) = q_tooltip.get_single_mut() else {
+            return;
+        };
+
+        let diagnostic = current_puzzle.evaluator.as_ref().and_then(|evaluator| {
+            get_hovered_evaluation_id(
+                &active_nodes.active_nodes,
+                &active_sets.active_sets,
+                mouse_position.position,
+            )
+            .and_then(|id| evaluator.report().get_diagnostic(&id))
+        });
+        let cursor = q_window
+            .get_single()
+            .ok()
+            .and_then(|window| window.cursor_position());
+
+        match (diagnostic, cursor) {
+            (Some(diagnostic), Some(cursor)) => {
+                let message = diagnostic.to_string();
+                if text.sections[0].value != message {
+                    text.sections[0].value = message;
+                }
+                style.left = Val::Px(cursor.x + TOOLTIP_OFFSET);
+                style.top = Val::Px(cursor.y + TOOLTIP_OFFSET);
+                *visibility = Visibility::Inherited;
+            }
+            _ => *visibility = Visibility::Hidden,
+        }
+    }
+
     /// A system for updating all (relevant) satisfiable sprites on screen when an UpdateSatisfiedStates event is sent.
     fn update_satisfied_states_ui(
         mut event_reader: EventReader<UpdateSatisfiedStates>,
@@ -718,6 +1020,20 @@ pub mod scene {
                             }
                         }
                     });
+                active_set
+                    .active_meta_set_rules
+                    .iter_mut()
+                    .for_each(|active_meta_set_rule| {
+                        if satisfied_states.contains_key(&active_meta_set_rule.active_id) {
+                            active_meta_set_rule
+                                .set_satisfied(satisfied_states[&active_meta_set_rule.active_id]);
+                            if let Ok(mut sprite) =
+                                q_sprites.get_mut(active_meta_set_rule.sprite_entity_id)
+                            {
+                                active_meta_set_rule.update_sprites(Vec::from([sprite.as_mut()]));
+                            }
+                        }
+                    });
                 active_set.active_connected_set_rules.iter_mut().for_each(
                     |active_connected_set_rule| {
                         if satisfied_states.contains_key(&active_connected_set_rule.active_id) {
@@ -756,6 +1072,7 @@ pub mod scene {
         mut active_lines: ResMut<ActiveLines>,
         puzzle: Res<SelectedPuzzle>,
         previously_solved: Res<PreviouslySolved>,
+        current_hint: Res<CurrentHint>,
         mut app_state: ResMut<NextState<AppState>>,
     ) {
         for PuzzleSolved in event_reader.read() {
@@ -769,6 +1086,7 @@ pub mod scene {
             exit_puzzle(
                 puzzle.uuid,
                 true,
+                current_hint.hints_used,
                 &mut commands,
                 &mut active_nodes.active_nodes,
                 &mut active_sets.active_sets,
@@ -778,6 +1096,13 @@ pub mod scene {
         }
     }
 
+    /// Removes the highlighted line of the last hint, if any is shown.
+    fn clear_hint_line(commands: &mut Commands, current_hint: &mut CurrentHint) {
+        for entity in current_hint.hint_line_entities.drain(..) {
+            commands.entity(entity).despawn();
+        }
+    }
+
     fn ui_action(
         mut commands: Commands,
         interaction_query: Query<
@@ -788,6 +1113,9 @@ pub mod scene {
         mut active_sets: ResMut<ActiveSets>,
         mut active_lines: ResMut<ActiveLines>,
         puzzle: Res<SelectedPuzzle>,
+        mut current_puzzle: ResMut<CurrentPuzzle>,
+        mut current_hint: ResMut<CurrentHint>,
+        asset_server: Res<AssetServer>,
         _q_sprites: Query<&mut Sprite>,
         mut app_state: ResMut<NextState<AppState>>,
         mut event_writer: EventWriter<UpdateSatisfiedStates>,
@@ -796,17 +1124,70 @@ pub mod scene {
         for (interaction, ui_button_action) in &interaction_query {
             if *interaction == Interaction::Pressed {
                 match ui_button_action {
+                    // Highlight a line which must be drawn, avoided or removed to reach a solution
+                    PuzzleButtonAction::Hint => {
+                        let Some(evaluator) = current_puzzle.evaluator.as_ref() else {
+                            continue;
+                        };
+                        clear_hint_line(&mut commands, &mut current_hint);
+
+                        let Some(hint) = find_hint(evaluator.puzzle(), evaluator.solution()) else {
+                            continue;
+                        };
+                        let (line, color) = match hint {
+                            Hint::Draw(line) => (line, COLOR_HINT_DRAW),
+                            Hint::Avoid(line) | Hint::Remove(line) => (line, COLOR_HINT_REMOVE),
+                        };
+
+                        let find_node = |id: GameNodeId| {
+                            active_nodes
+                                .active_nodes
+                                .iter()
+                                .find(|active_node| active_node.node.id == id)
+                        };
+                        if let (Some(start_node), Some(end_node)) =
+                            (find_node(line.node_a_id()), find_node(line.node_b_id()))
+                        {
+                            current_hint.hint_line_entities = spawn_hint_line(
+                                &mut commands,
+                                asset_server.clone(),
+                                start_node,
+                                end_node,
+                                color,
+                                evaluator.puzzle(),
+                            );
+                            current_hint.hints_used += 1;
+                        }
+                    }
+                    // Copy a code of the puzzle and the lines drawn in it, to be pasted into chat
+                    PuzzleButtonAction::Share => {
+                        let Some(evaluator) = current_puzzle.evaluator.as_ref() else {
+                            continue;
+                        };
+                        let solution =
+                            Some(evaluator.solution()).filter(|solution| !solution.is_empty());
+                        let code = encode_puzzle(evaluator.puzzle(), solution);
+                        info!("Puzzle code: {}", code);
+                        if let Err(error) = write_clipboard(&code) {
+                            warn!("Failed to copy puzzle code: {}", error);
+                        }
+                    }
                     // Delete all lines on screen and connections in active nodes, and update satisfied states
                     PuzzleButtonAction::Reset => {
+                        clear_hint_line(&mut commands, &mut current_hint);
                         clear_all_lines(
                             &mut commands,
                             &mut active_nodes.active_nodes,
                             &mut active_lines.lines,
                         );
-                        event_writer.send(UpdateSatisfiedStates(get_all_satisfied_states(
-                            &active_nodes.active_nodes,
-                            &active_sets.active_sets,
-                        )));
+                        if let Some(evaluator) = current_puzzle.evaluator.as_mut() {
+                            evaluator.clear_lines();
+                            event_writer.send(UpdateSatisfiedStates(get_all_satisfied_states(
+                                evaluator.report(),
+                                &active_nodes.active_nodes,
+                                &active_sets.active_sets,
+                            )));
+                        }
                     }
                     PuzzleButtonAction::ReturnToPreviousPage => {
                         let solved = is_puzzle_solved(
@@ -816,6 +1197,7 @@ pub mod scene {
                         exit_puzzle(
                             puzzle.uuid,
                             solved,
+                            current_hint.hints_used,
                             &mut commands,
                             &mut active_nodes.active_nodes,
                             &mut active_sets.active_sets,
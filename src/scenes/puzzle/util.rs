@@ -1,214 +1,364 @@
 use std::f32::consts::PI;
 
 use bevy::{
-    asset::{AssetServer, Handle},
+    asset::AssetServer,
     color::Color,
-    math::{Vec2, Vec3},
+    math::{Quat, Rect, Vec2, Vec3},
+    ecs::entity::Entity,
     prelude::{default, Commands, Mut, NextState, OrthographicProjection},
-    render::{texture::Image, view::window},
+    render::view::window,
     sprite::{Sprite, SpriteBundle},
+    text::{Text, Text2dBundle, TextStyle},
     transform::components::Transform, window::Window,
 };
 use tracing::error;
 use uuid::Uuid;
 
 use crate::{
-    get_node_down, get_node_down_left, get_node_down_right, get_node_left, get_node_right, get_node_up, get_node_up_left, get_node_up_right, get_set_order, get_sets_containing_node, is_bottom_edge, is_left_edge, is_right_edge, is_top_edge, logic::save_data_manager::save_data_manager::save_progress, node_to_position, structs::{
+    get_crossing_line, get_neighbor_position, grid_position_to_node, grid_position_to_scene, is_wrapping_line, get_set_order, get_sets_containing_node, logic::{save_data_manager::save_data_manager::save_progress, topology::topology::{GridTopology, SquareGrid}}, node_to_grid_position, node_to_position, structs::{
         active::{
             active_identifier::active_identifier::ActiveIdentifier,
             active_line::active_line::ActiveLine, active_node::active_node::ActiveNode,
             active_set::active_set::ActiveSet,
         },
         immutable::{
-            game_node::game_node::GameNodeId, game_set::game_set::GameSet, puzzle::puzzle::Puzzle,
+            game_line::game_line::GameLine, game_node::game_node::GameNodeId,
+            game_set::game_set::GameSet, puzzle::puzzle::Puzzle,
             solution::solution::active_nodes_to_solution,
         },
-    }, texture::Texture, AppState, BG_SET_SPRITE_SIZE, COLOR_SET_0, COLOR_SET_1, COLOR_SET_2, COLOR_SET_BORDER, SPRITE_SPACING, TILE_NODE_SPRITE_SIZE, WINDOW_PADDING, Z_BACKGROUND, Z_LINE, Z_SET_FILL
+    }, texture::Texture, buttons::TEXT_COLOR, AppState, BG_SET_SPRITE_SIZE, CDTN_LABEL_FONT_SIZE, COLOR_SET_0, LINE_TEXTURE_SIZE, COLOR_SET_1, COLOR_SET_2, COLOR_SET_BORDER, SPRITE_SPACING, TILE_NODE_SPRITE_SIZE, WINDOW_PADDING, Z_BACKGROUND, Z_CDTN_LABEL, Z_HINT_LINE, Z_LINE, Z_SET_FILL
 };
 
 use super::scene::scene::OnPuzzleScene;
 
-/// Returns a background tile as a sprite bundle.
+/// Returns a background tile as a sprite bundle, shaped around the nodes of the puzzle so
+/// holes in the board are left uncovered.
 ///
 /// # Parameters
 ///
-/// - `x_pos`: The x position of the tile in the grid (0..width*2+1)
-/// - `y_pos`: The y position of the tile in the grid (0..height*2+1)
-/// - `width`: The width of the puzzle.
-/// - `height`: The height of the puzzle.
-/// - `sprite_size`: The size of the sprite in pixels.
-/// - `spacing`: The spacing between each sprite in the grid.
+/// - `x`: The x position of the tile in the grid (0..width*2+1)
+/// - `y`: The y position of the tile in the grid (0..height*2+1)
+/// - `puzzle`: The puzzle whose board the tile is part of.
+/// - `asset_server`: Server to load the tile textures from.
 ///
 /// # Returns
 ///
-/// A sprite bundle representing the background tile at the given position.
+/// A sprite bundle representing the background tile at the given position, or None if no
+/// node of the puzzle borders it.
 pub fn get_puzzle_background_tile(
     x: u8,
     y: u8,
-    width: u8,
-    height: u8,
+    puzzle: &Puzzle,
     asset_server: AssetServer,
-) -> SpriteBundle {
-    // Load background textures
-    let bg_side_left: Handle<Image> = asset_server.load(Texture::BgTileSideLeft.path());
-    let bg_side_right: Handle<Image> = asset_server.load(Texture::BgTileSideRight.path());
-    let bg_side_bottom: Handle<Image> = asset_server.load(Texture::BgTileSideBottom.path());
-    let bg_side_top: Handle<Image> = asset_server.load(Texture::BgTileSideTop.path());
-    let bg_bottom_left: Handle<Image> = asset_server.load(Texture::BgTileBottomLeft.path());
-    let bg_bottom_right: Handle<Image> = asset_server.load(Texture::BgTileBottomRight.path());
-    let bg_top_left: Handle<Image> = asset_server.load(Texture::BgTileTopLeft.path());
-    let bg_top_right: Handle<Image> = asset_server.load(Texture::BgTileTopRight.path());
-    let bg_between_horizontal: Handle<Image> =
-        asset_server.load(Texture::BgTileBetweenHorizontal.path());
-    let bg_between_vertical: Handle<Image> =
-        asset_server.load(Texture::BgTileBetweenVertical.path());
-    let bg_between_cross: Handle<Image> = asset_server.load(Texture::BgTileBetweenCross.path());
-
-    let transform = Transform::from_xyz(
-        x as f32 * SPRITE_SPACING,
-        y as f32 * SPRITE_SPACING,
-        Z_BACKGROUND,
-    );
-    let sprite = Sprite {
-        custom_size: Some(Vec2::new(TILE_NODE_SPRITE_SIZE, TILE_NODE_SPRITE_SIZE)),
-        ..Default::default()
-    };
+) -> Option<SpriteBundle> {
+    let texture = get_puzzle_background_texture(x, y, puzzle)?;
 
-    // Bottom left corner
-    if x == 0 && y == 0 {
-        SpriteBundle {
-            texture: bg_bottom_left,
-            sprite,
-            transform,
-            ..default()
-        }
-    } else if x == 0 {
-        // Top left corner
-        if y == height * 2 {
-            SpriteBundle {
-                texture: bg_top_left,
-                sprite,
-                transform,
-                ..default()
-            }
-        } else {
-            // Left side
-            SpriteBundle {
-                texture: bg_side_left,
-                sprite,
-                transform,
-                ..default()
-            }
-        }
-    } else if y == 0 {
-        // Bottom right corner
-        if x == width * 2 {
-            SpriteBundle {
-                texture: bg_bottom_right,
-                sprite,
-                transform,
-                ..default()
-            }
-        } else {
-            // Bottom side
-            SpriteBundle {
-                texture: bg_side_bottom,
-                sprite,
-                transform,
-                ..default()
-            }
-        }
-    } else {
-        // Top right corner
-        if x == width * 2 && y == height * 2 {
-            SpriteBundle {
-                texture: bg_top_right,
-                sprite,
-                transform,
-                ..default()
-            }
-        } else if x == width * 2 {
-            // Right side
-            SpriteBundle {
-                texture: bg_side_right,
-                sprite,
-                transform,
-                ..default()
-            }
-        } else if y == height * 2 {
-            // Top side
-            SpriteBundle {
-                texture: bg_side_top,
-                sprite,
-                transform,
-                ..default()
-            }
+    Some(SpriteBundle {
+        texture: asset_server.load(texture.path().to_string()),
+        sprite: Sprite {
+            custom_size: Some(Vec2::new(TILE_NODE_SPRITE_SIZE, TILE_NODE_SPRITE_SIZE)),
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(
+            x as f32 * SPRITE_SPACING,
+            y as f32 * SPRITE_SPACING,
+            Z_BACKGROUND,
+        ),
+        ..default()
+    })
+}
+
+/// Returns the texture of a background tile, picked by which sides of the tile the board ends
+/// on.
+///
+/// # Parameters
+///
+/// - `x`: The x position of the tile in the grid (0..width*2+1)
+/// - `y`: The y position of the tile in the grid (0..height*2+1)
+/// - `puzzle`: The puzzle whose board the tile is part of.
+///
+/// # Returns
+///
+/// The texture of the background tile at the given position, or None if no node of the puzzle
+/// borders it.
+fn get_puzzle_background_texture(x: u8, y: u8, puzzle: &Puzzle) -> Option<Texture> {
+    // Columns and rows of the nodes bordering the tile, as even tiles lie between two of them
+    let bordering = |pos: u8| -> Vec<i32> {
+        if pos % 2 == 0 {
+            [pos as i32 / 2 - 1, pos as i32 / 2].to_vec()
         } else {
-            if x % 2 == 0 && y % 2 == 0 {
-                // Between cross
-                SpriteBundle {
-                    texture: bg_between_cross,
-                    sprite,
-                    transform,
-                    ..default()
-                }
-            } else if x % 2 == 0 {
-                // Between horizontal
-                SpriteBundle {
-                    texture: bg_between_horizontal,
-                    sprite,
-                    transform,
-                    ..default()
-                }
-            } else {
-                // Between vertical
-                SpriteBundle {
-                    texture: bg_between_vertical,
-                    sprite,
-                    transform,
-                    ..default()
-                }
-            }
+            [pos as i32 / 2].to_vec()
         }
+    };
+    let (columns, rows) = (bordering(x), bordering(y));
+    let has_node_at = |column: i32, row: i32| {
+        column >= 0
+            && row >= 0
+            && column < puzzle.width as i32
+            && row < puzzle.height as i32
+            && puzzle.has_node((column * puzzle.height as i32 + row) as GameNodeId)
+    };
+    let has_column = |column: i32| rows.iter().any(|row| has_node_at(column, *row));
+    let has_row = |row: i32| columns.iter().any(|column| has_node_at(*column, row));
+    if !columns.iter().any(|column| has_column(*column)) {
+        return None;
     }
+
+    // The board ends on a side of an even tile if no node lies beyond it on that side
+    let open_left = x % 2 == 0 && !has_column(columns[0]);
+    let open_right = x % 2 == 0 && !has_column(columns[columns.len() - 1]);
+    let open_bottom = y % 2 == 0 && !has_row(rows[0]);
+    let open_top = y % 2 == 0 && !has_row(rows[rows.len() - 1]);
+
+    Some(match (open_left, open_right, open_bottom, open_top) {
+        (true, _, true, _) => Texture::BgTileBottomLeft,
+        (true, _, _, true) => Texture::BgTileTopLeft,
+        (_, true, true, _) => Texture::BgTileBottomRight,
+        (_, true, _, true) => Texture::BgTileTopRight,
+        (true, _, _, _) => Texture::BgTileSideLeft,
+        (_, true, _, _) => Texture::BgTileSideRight,
+        (_, _, true, _) => Texture::BgTileSideBottom,
+        (_, _, _, true) => Texture::BgTileSideTop,
+        _ if x % 2 == 0 && y % 2 == 0 => Texture::BgTileBetweenCross,
+        _ if x % 2 == 0 => Texture::BgTileBetweenHorizontal,
+        _ => Texture::BgTileBetweenVertical,
+    })
 }
 
-/// Returns the texture for a line connecting two active nodes.
+/// Returns the texture for a line connecting two active nodes, and the angle to rotate it by.
 ///
 /// # Parameters
 ///
 /// - `start_node`: The active node at the start of the line.
 /// - `end_node`: The active node at the end of the line.
+/// - `puzzle`: The puzzle whose grid decides which nodes are adjacent.
 ///
 /// # Returns
 ///
-/// A texture for the line connecting the two active nodes, or `None` if the line is invalid
-/// (non-adjacent nodes).
+/// A texture for the line connecting the two active nodes along with its rotation in radians,
+/// or `None` if the line is invalid (non-adjacent nodes). Lines along the square grid use their
+/// own textures, while others (e.g. on a hex grid) rotate the horizontal line.
 pub fn get_line_texture(
     start_node: &ActiveNode,
     end_node: &ActiveNode,
-) -> Option<&'static Texture> {
-    let start_pos = start_node.sprite.transform.translation.truncate();
-    let end_pos = end_node.sprite.transform.translation.truncate();
+    puzzle: &Puzzle,
+) -> Option<(&'static Texture, f32)> {
+    // Determine if line is valid connection between adjacent nodes, taking the direction in
+    // which it leaves the start node for lines wrapping around the board
+    let end_position = get_neighbor_position(&start_node.node.id, &end_node.node.id, puzzle)?;
+
+    let start_pos = Vec2::from(node_to_position(&start_node.node.id, puzzle));
+    let end_pos = Vec2::from(grid_position_to_scene(end_position, puzzle));
     let direction = end_pos - start_pos;
-    let distance = direction.length();
     let angle = direction.y.atan2(direction.x);
 
-    // Determine if line is valid connection between adjacent nodes
-    if distance > SPRITE_SPACING + TILE_NODE_SPRITE_SIZE && (angle == 0.0 || angle == PI / 2.0) {
-        return None;
-    } else if distance > (2.0 * (SPRITE_SPACING + TILE_NODE_SPRITE_SIZE).powi(2)).sqrt() {
-        return None;
+    // Lines look the same both ways, so only their angle within half a turn matters
+    let line_angle = angle.rem_euclid(PI);
+    let is_angle = |target: f32| (line_angle - target).abs() < 1e-3;
+
+    if is_angle(0.0) || is_angle(PI) {
+        Some((&Texture::LineHorizontal, 0.0))
+    } else if is_angle(PI / 2.0) {
+        Some((&Texture::LineVertical, 0.0))
+    } else if is_angle(PI / 4.0) {
+        Some((&Texture::LineDiagonalBottomLeftTopRight, 0.0))
+    } else if is_angle(3.0 * PI / 4.0) {
+        Some((&Texture::LineDiagonalTopLeftBottomRight, 0.0))
+    } else {
+        Some((&Texture::LineHorizontal, line_angle))
+    }
+}
+
+/// Returns the sprites drawing a line between two active nodes: one sprite between them, or
+/// for a line wrapping around a toroidal board, a half-line from each node to the border.
+///
+/// # Parameters
+///
+/// - `start_node`: The active node at the start of the line.
+/// - `end_node`: The active node at the end of the line.
+/// - `puzzle`: The puzzle whose grid decides where the line runs.
+/// - `asset_server`: The asset server used to load textures.
+/// - `color`: The color of the line.
+/// - `z`: The layer the line is drawn on.
+///
+/// # Returns
+///
+/// The sprites of the line, or an empty vector if the nodes aren't adjacent.
+fn get_line_sprites(
+    start_node: &ActiveNode,
+    end_node: &ActiveNode,
+    puzzle: &Puzzle,
+    asset_server: &AssetServer,
+    color: Color,
+    z: f32,
+) -> Vec<SpriteBundle> {
+    let Some((line_texture, rotation)) = get_line_texture(start_node, end_node, puzzle) else {
+        return Vec::new();
+    };
+    // Lines fixed by the puzzle are drawn locked, so players can tell they can't be removed
+    let is_fixed = GameLine::new(start_node.node.id, end_node.node.id)
+        .is_ok_and(|line| puzzle.fixed_lines.contains(&line));
+    let line_texture = if is_fixed {
+        get_fixed_line_texture(line_texture)
+    } else {
+        line_texture
+    };
+    let texture = asset_server.load(line_texture.path());
+    let line_sprite = |center: Vec2, size: Vec2, rect: Option<Rect>| SpriteBundle {
+        texture: texture.clone(),
+        sprite: Sprite {
+            custom_size: Some(size),
+            rect,
+            color,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(center.x, center.y, z)
+            .with_rotation(Quat::from_rotation_z(rotation)),
+        ..Default::default()
+    };
+
+    let start_id = start_node.node.id;
+    let end_id = end_node.node.id;
+    if !is_wrapping_line(&start_id, &end_id, puzzle) {
+        let start_pos = Vec2::from(node_to_position(&start_id, puzzle));
+        let end_pos = Vec2::from(node_to_position(&end_id, puzzle));
+        return vec![line_sprite(
+            (start_pos + end_pos) / 2.0,
+            Vec2::splat(TILE_NODE_SPRITE_SIZE),
+            None,
+        )];
     }
 
-    if angle.abs() == 0.0 || angle.abs() == PI {
-        Some(&Texture::LineHorizontal)
-    } else if angle.abs() == PI / 2.0 {
-        Some(&Texture::LineVertical)
-    } else if angle == PI / 4.0 || angle == -3.0 * PI / 4.0 {
-        Some(&Texture::LineDiagonalBottomLeftTopRight)
+    [(start_id, end_id), (end_id, start_id)]
+        .into_iter()
+        .filter_map(|(from_id, to_id)| {
+            let from_pos = Vec2::from(node_to_position(&from_id, puzzle));
+            let beyond_pos = Vec2::from(grid_position_to_scene(
+                get_neighbor_position(&from_id, &to_id, puzzle)?,
+                puzzle,
+            ));
+            let direction = beyond_pos - from_pos;
+
+            // Keep the half of the texture nearest the node, along each axis the line runs in
+            let local = Vec2::from_angle(-rotation).rotate(direction);
+            let half = LINE_TEXTURE_SIZE / 2.0;
+            let (min_x, max_x) = match local.x {
+                x if x > 1.0 => (0.0, half),
+                x if x < -1.0 => (half, LINE_TEXTURE_SIZE),
+                _ => (0.0, LINE_TEXTURE_SIZE),
+            };
+            // Texture rows count down from the top
+            let (min_y, max_y) = match local.y {
+                y if y > 1.0 => (half, LINE_TEXTURE_SIZE),
+                y if y < -1.0 => (0.0, half),
+                _ => (0.0, LINE_TEXTURE_SIZE),
+            };
+            let size =
+                Vec2::new(max_x - min_x, max_y - min_y) / LINE_TEXTURE_SIZE * TILE_NODE_SPRITE_SIZE;
+
+            // A full line covers the middle half of the way between nodes, so the half nearest
+            // the node is centered three eighths of the way along
+            Some(line_sprite(
+                from_pos + direction * 0.375,
+                size,
+                Some(Rect::new(min_x, min_y, max_x, max_y)),
+            ))
+        })
+        .collect()
+}
+
+/// Returns the locked version of a line texture, for lines fixed by the puzzle.
+fn get_fixed_line_texture(texture: &'static Texture) -> &'static Texture {
+    match texture {
+        Texture::LineHorizontal => &Texture::LineFixedHorizontal,
+        Texture::LineVertical => &Texture::LineFixedVertical,
+        Texture::LineDiagonalBottomLeftTopRight => &Texture::LineFixedDiagonalBottomLeftTopRight,
+        Texture::LineDiagonalTopLeftBottomRight => &Texture::LineFixedDiagonalTopLeftBottomRight,
+        _ => texture,
+    }
+}
+
+/// Returns the sprites marking a forbidden line between two active nodes, halfway between them
+/// or, for a line wrapping around a toroidal board, at both borders it would cross.
+pub fn get_forbidden_line_sprites(
+    start_node: &ActiveNode,
+    end_node: &ActiveNode,
+    puzzle: &Puzzle,
+    asset_server: &AssetServer,
+) -> Vec<SpriteBundle> {
+    let (start_id, end_id) = (start_node.node.id, end_node.node.id);
+    let ends = if is_wrapping_line(&start_id, &end_id, puzzle) {
+        [(start_id, end_id), (end_id, start_id)].to_vec()
     } else {
-        Some(&Texture::LineDiagonalTopLeftBottomRight)
+        [(start_id, end_id)].to_vec()
+    };
+
+    ends.into_iter()
+        .filter_map(|(from_id, to_id)| {
+            let from_pos = Vec2::from(node_to_position(&from_id, puzzle));
+            let to_pos = Vec2::from(grid_position_to_scene(
+                get_neighbor_position(&from_id, &to_id, puzzle)?,
+                puzzle,
+            ));
+            let center = (from_pos + to_pos) / 2.0;
+            Some(SpriteBundle {
+                texture: asset_server.load(Texture::LineForbidden.path()),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(TILE_NODE_SPRITE_SIZE / 2.0)),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(center.x, center.y, Z_LINE),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Which of the positions around a node on a square grid hold nodes of a set, including the
+/// diagonal ones whatever the topology, as set borders run along the edges of square cells.
+struct SquareSetNeighbors {
+    left: bool,
+    up_left: bool,
+    up: bool,
+    up_right: bool,
+    right: bool,
+    down_right: bool,
+    down: bool,
+    down_left: bool,
+}
+
+/// Returns which of the positions around a node on a square grid hold nodes of the set.
+/// Positions beyond the border wrap around a toroidal board, so sets continuing across the
+/// border aren't closed off there.
+///
+/// # Parameters
+///
+/// - `node`: The node of the set whose surroundings are looked up.
+/// - `set`: The game set the surrounding nodes are checked against.
+/// - `puzzle`: The puzzle in which the set lives.
+///
+/// # Returns
+///
+/// The surrounding positions holding nodes of the set.
+fn get_square_set_neighbors(node: &u16, set: &GameSet, puzzle: &Puzzle) -> SquareSetNeighbors {
+    let in_set: Vec<bool> = SquareGrid { diagonals: true }
+        .neighbors(node_to_grid_position(node, puzzle))
+        .into_iter()
+        .map(|position| {
+            grid_position_to_node(position, puzzle).is_some_and(|node| set.nodes.contains(&node))
+        })
+        .collect();
+
+    // Neighbors of the square grid run clockwise from the left
+    SquareSetNeighbors {
+        left: in_set[0],
+        up_left: in_set[1],
+        up: in_set[2],
+        up_right: in_set[3],
+        right: in_set[4],
+        down_right: in_set[5],
+        down: in_set[6],
+        down_left: in_set[7],
     }
 }
 
@@ -227,22 +377,20 @@ fn set_border_sprite() -> Sprite {
 ///
 /// # Parameters
 ///
-/// - `node`: The node around which set tiles are being added.
+/// - `neighbors`: Which positions around the node hold nodes of the set.
 /// - `node_x`: The node's X coordinate in the scene.
 /// - `node_y`: The node's Y coordinate in the scene.
 /// - `set`: The game set for which tiles are being added.
-/// - `puzzle`: The puzzle for which the tiles are being added.
 /// - `asset_server`: The asset server used to load textures.
 ///
 /// # Returns
 ///
 /// A vector of Sprite Bundles.
 fn get_set_tiles_vertical(
-    node: &u16,
+    neighbors: &SquareSetNeighbors,
     node_x: f32,
     node_y: f32,
     set: &GameSet,
-    puzzle: &Puzzle,
     asset_server: AssetServer,
 ) -> Vec<SpriteBundle> {
     let mut vertical_tiles = Vec::new();
@@ -252,8 +400,7 @@ fn get_set_tiles_vertical(
         Texture::SetTileVertical.path()
     });
 
-    let node_left = get_node_left(node, puzzle).unwrap_or(u16::MAX);
-    if is_left_edge(node, puzzle) || !set.nodes.contains(&node_left) {
+    if !neighbors.left {
         vertical_tiles.push(SpriteBundle {
             texture: tex_set_tile_vertical.clone(),
             sprite: set_border_sprite(),
@@ -262,8 +409,7 @@ fn get_set_tiles_vertical(
         })
     }
 
-    let node_right = get_node_right(node, puzzle).unwrap_or(u16::MAX);
-    if is_right_edge(node, puzzle) || !set.nodes.contains(&node_right) {
+    if !neighbors.right {
         vertical_tiles.push(SpriteBundle {
             texture: tex_set_tile_vertical.clone(),
             sprite: set_border_sprite(),
@@ -272,44 +418,24 @@ fn get_set_tiles_vertical(
         });
     }
 
-    if !is_top_edge(node, puzzle) {
-        let node_up = get_node_up(node, puzzle).unwrap_or(u16::MAX);
-
-        // Above left
-        let node_up_left = get_node_up_left(node, puzzle).unwrap_or(u16::MAX);
-        if !set.nodes.contains(&node_up_left)
-            && !set.nodes.contains(&node_left)
-            && set.nodes.contains(&node_up)
-        {
-            vertical_tiles.push(SpriteBundle {
-                texture: tex_set_tile_vertical.clone(),
-                sprite: set_border_sprite(),
-                transform: Transform::from_xyz(
-                    node_x - SPRITE_SPACING,
-                    node_y + SPRITE_SPACING,
-                    0.0,
-                ),
-                ..default()
-            });
-        }
+    // Above left
+    if !neighbors.up_left && !neighbors.left && neighbors.up {
+        vertical_tiles.push(SpriteBundle {
+            texture: tex_set_tile_vertical.clone(),
+            sprite: set_border_sprite(),
+            transform: Transform::from_xyz(node_x - SPRITE_SPACING, node_y + SPRITE_SPACING, 0.0),
+            ..default()
+        });
+    }
 
-        // Above right
-        let node_up_right = get_node_up_right(node, puzzle).unwrap_or(u16::MAX);
-        if !set.nodes.contains(&node_up_right)
-            && !set.nodes.contains(&node_right)
-            && set.nodes.contains(&node_up)
-        {
-            vertical_tiles.push(SpriteBundle {
-                texture: tex_set_tile_vertical.clone(),
-                sprite: set_border_sprite(),
-                transform: Transform::from_xyz(
-                    node_x + SPRITE_SPACING,
-                    node_y + SPRITE_SPACING,
-                    0.0,
-                ),
-                ..default()
-            });
-        }
+    // Above right
+    if !neighbors.up_right && !neighbors.right && neighbors.up {
+        vertical_tiles.push(SpriteBundle {
+            texture: tex_set_tile_vertical.clone(),
+            sprite: set_border_sprite(),
+            transform: Transform::from_xyz(node_x + SPRITE_SPACING, node_y + SPRITE_SPACING, 0.0),
+            ..default()
+        });
     }
 
     vertical_tiles
@@ -321,22 +447,20 @@ fn get_set_tiles_vertical(
 ///
 /// # Parameters
 ///
-/// - `node`: The node around which set tiles are being added.
+/// - `neighbors`: Which positions around the node hold nodes of the set.
 /// - `node_x`: The node's X coordinate in the scene.
 /// - `node_y`: The node's Y coordinate in the scene.
 /// - `set`: The game set for which tiles are being added.
-/// - `puzzle`: The puzzle for which the tiles are being added.
 /// - `asset_server`: The asset server used to load textures.
 ///
 /// # Returns
 ///
 /// A vector of Sprite Bundles.
 fn get_set_tiles_horizontal(
-    node: &u16,
+    neighbors: &SquareSetNeighbors,
     node_x: f32,
     node_y: f32,
     set: &GameSet,
-    puzzle: &Puzzle,
     asset_server: AssetServer,
 ) -> Vec<SpriteBundle> {
     let mut horizontal_tiles = Vec::new();
@@ -347,8 +471,7 @@ fn get_set_tiles_horizontal(
     });
 
     // Directly above
-    let node_up = get_node_up(node, puzzle).unwrap_or(u16::MAX);
-    if is_top_edge(node, &puzzle) || !set.nodes.contains(&node_up) {
+    if !neighbors.up {
         horizontal_tiles.push(SpriteBundle {
             texture: tex_set_tile_horizontal.clone(),
             sprite: set_border_sprite(),
@@ -358,8 +481,7 @@ fn get_set_tiles_horizontal(
     }
 
     // Directly below
-    let node_down = get_node_down(node, puzzle).unwrap_or(u16::MAX);
-    if is_bottom_edge(node, &puzzle) || !set.nodes.contains(&node_down) {
+    if !neighbors.down {
         horizontal_tiles.push(SpriteBundle {
             texture: tex_set_tile_horizontal.clone(),
             sprite: set_border_sprite(),
@@ -368,44 +490,24 @@ fn get_set_tiles_horizontal(
         });
     }
 
-    if !is_right_edge(node, &puzzle) {
-        let node_right = get_node_right(node, puzzle).unwrap_or(u16::MAX);
-
-        // Above right
-        let node_up_right = get_node_up_right(node, puzzle).unwrap_or(u16::MAX);
-        if !set.nodes.contains(&node_up_right)
-            && !set.nodes.contains(&node_up)
-            && set.nodes.contains(&node_right)
-        {
-            horizontal_tiles.push(SpriteBundle {
-                texture: tex_set_tile_horizontal.clone(),
-                sprite: set_border_sprite(),
-                transform: Transform::from_xyz(
-                    node_x + SPRITE_SPACING,
-                    node_y + SPRITE_SPACING,
-                    0.0,
-                ),
-                ..default()
-            });
-        }
+    // Above right
+    if !neighbors.up_right && !neighbors.up && neighbors.right {
+        horizontal_tiles.push(SpriteBundle {
+            texture: tex_set_tile_horizontal.clone(),
+            sprite: set_border_sprite(),
+            transform: Transform::from_xyz(node_x + SPRITE_SPACING, node_y + SPRITE_SPACING, 0.0),
+            ..default()
+        });
+    }
 
-        // Below right
-        let node_down_right = get_node_down_right(node, puzzle).unwrap_or(u16::MAX);
-        if !set.nodes.contains(&node_down_right)
-            && !set.nodes.contains(&node_down)
-            && set.nodes.contains(&node_right)
-        {
-            horizontal_tiles.push(SpriteBundle {
-                texture: tex_set_tile_horizontal.clone(),
-                sprite: set_border_sprite(),
-                transform: Transform::from_xyz(
-                    node_x + SPRITE_SPACING,
-                    node_y - SPRITE_SPACING,
-                    0.0,
-                ),
-                ..default()
-            });
-        }
+    // Below right
+    if !neighbors.down_right && !neighbors.down && neighbors.right {
+        horizontal_tiles.push(SpriteBundle {
+            texture: tex_set_tile_horizontal.clone(),
+            sprite: set_border_sprite(),
+            transform: Transform::from_xyz(node_x + SPRITE_SPACING, node_y - SPRITE_SPACING, 0.0),
+            ..default()
+        });
     }
 
     horizontal_tiles
@@ -417,22 +519,20 @@ fn get_set_tiles_horizontal(
 ///
 /// # Parameters
 ///
-/// - `node`: The node around which set tiles are being added.
+/// - `neighbors`: Which positions around the node hold nodes of the set.
 /// - `node_x`: The node's X coordinate in the scene.
 /// - `node_y`: The node's Y coordinate in the scene.
 /// - `set`: The game set for which tiles are being added.
-/// - `puzzle`: The puzzle for which the tiles are being added.
 /// - `asset_server`: The asset server used to load textures.
 ///
 /// # Returns
 ///
 /// A vector of Sprite Bundles.
 fn get_set_tiles_bottom_right(
-    node: &u16,
+    neighbors: &SquareSetNeighbors,
     node_x: f32,
     node_y: f32,
     set: &GameSet,
-    puzzle: &Puzzle,
     asset_server: AssetServer,
 ) -> Vec<SpriteBundle> {
     let mut bottom_right_tiles = Vec::new();
@@ -442,9 +542,7 @@ fn get_set_tiles_bottom_right(
         Texture::SetTileBottomRight.path()
     });
 
-    let node_down = get_node_down(node, puzzle).unwrap_or(u16::MAX);
-    let node_right = get_node_right(node, puzzle).unwrap_or(u16::MAX);
-    if !set.nodes.contains(&node_down) && !set.nodes.contains(&node_right) {
+    if !neighbors.down && !neighbors.right {
         bottom_right_tiles.push(SpriteBundle {
             texture: tex_set_tile_bottom_right.clone(),
             sprite: set_border_sprite(),
@@ -453,13 +551,7 @@ fn get_set_tiles_bottom_right(
         });
     }
 
-    let node_up_left = get_node_up_left(node, &puzzle).unwrap_or(u16::MAX);
-    let node_up = get_node_up(node, puzzle).unwrap_or(u16::MAX);
-    let node_left = get_node_left(node, puzzle).unwrap_or(u16::MAX);
-    if set.nodes.contains(&node_up)
-        && set.nodes.contains(&node_left)
-        && !set.nodes.contains(&node_up_left)
-    {
+    if neighbors.up && neighbors.left && !neighbors.up_left {
         bottom_right_tiles.push(SpriteBundle {
             texture: tex_set_tile_bottom_right.clone(),
             sprite: set_border_sprite(),
@@ -477,22 +569,20 @@ fn get_set_tiles_bottom_right(
 ///
 /// # Parameters
 ///
-/// - `node`: The node around which set tiles are being added.
+/// - `neighbors`: Which positions around the node hold nodes of the set.
 /// - `node_x`: The node's X coordinate in the scene.
 /// - `node_y`: The node's Y coordinate in the scene.
 /// - `set`: The game set for which tiles are being added.
-/// - `puzzle`: The puzzle for which the tiles are being added.
 /// - `asset_server`: The asset server used to load textures.
 ///
 /// # Returns
 ///
 /// A vector of Sprite Bundles.
 fn get_set_tiles_bottom_left(
-    node: &u16,
+    neighbors: &SquareSetNeighbors,
     node_x: f32,
     node_y: f32,
     set: &GameSet,
-    puzzle: &Puzzle,
     asset_server: AssetServer,
 ) -> Vec<SpriteBundle> {
     let mut bottom_left_tiles = Vec::new();
@@ -502,9 +592,7 @@ fn get_set_tiles_bottom_left(
         Texture::SetTileBottomLeft.path()
     });
 
-    let node_down = get_node_down(node, puzzle).unwrap_or(u16::MAX);
-    let node_left = get_node_left(node, puzzle).unwrap_or(u16::MAX);
-    if !set.nodes.contains(&node_down) && !set.nodes.contains(&node_left) {
+    if !neighbors.down && !neighbors.left {
         bottom_left_tiles.push(SpriteBundle {
             texture: tex_set_tile_bottom_left.clone(),
             sprite: set_border_sprite(),
@@ -513,13 +601,7 @@ fn get_set_tiles_bottom_left(
         });
     }
 
-    let node_up_right = get_node_up_right(node, &puzzle).unwrap_or(u16::MAX);
-    let node_up = get_node_up(node, puzzle).unwrap_or(u16::MAX);
-    let node_right = get_node_right(node, puzzle).unwrap_or(u16::MAX);
-    if set.nodes.contains(&node_up)
-        && set.nodes.contains(&node_right)
-        && !set.nodes.contains(&node_up_right)
-    {
+    if neighbors.up && neighbors.right && !neighbors.up_right {
         bottom_left_tiles.push(SpriteBundle {
             texture: tex_set_tile_bottom_left.clone(),
             sprite: set_border_sprite(),
@@ -537,22 +619,20 @@ fn get_set_tiles_bottom_left(
 ///
 /// # Parameters
 ///
-/// - `node`: The node around which set tiles are being added.
+/// - `neighbors`: Which positions around the node hold nodes of the set.
 /// - `node_x`: The node's X coordinate in the scene.
 /// - `node_y`: The node's Y coordinate in the scene.
 /// - `set`: The game set for which tiles are being added.
-/// - `puzzle`: The puzzle for which the tiles are being added.
 /// - `asset_server`: The asset server used to load textures.
 ///
 /// # Returns
 ///
 /// A vector of Sprite Bundles.
 fn get_set_tiles_top_right(
-    node: &u16,
+    neighbors: &SquareSetNeighbors,
     node_x: f32,
     node_y: f32,
     set: &GameSet,
-    puzzle: &Puzzle,
     asset_server: AssetServer,
 ) -> Vec<SpriteBundle> {
     let mut top_right_tiles = Vec::new();
@@ -562,9 +642,7 @@ fn get_set_tiles_top_right(
         Texture::SetTileTopRight.path()
     });
 
-    let node_up = get_node_up(node, puzzle).unwrap_or(u16::MAX);
-    let node_right = get_node_right(node, puzzle).unwrap_or(u16::MAX);
-    if !set.nodes.contains(&node_up) && !set.nodes.contains(&node_right) {
+    if !neighbors.up && !neighbors.right {
         top_right_tiles.push(SpriteBundle {
             texture: tex_set_tile_top_right.clone(),
             sprite: set_border_sprite(),
@@ -573,13 +651,7 @@ fn get_set_tiles_top_right(
         });
     }
 
-    let node_down_left = get_node_down_left(node, &puzzle).unwrap_or(u16::MAX);
-    let node_down = get_node_down(node, puzzle).unwrap_or(u16::MAX);
-    let node_left = get_node_left(node, puzzle).unwrap_or(u16::MAX);
-    if set.nodes.contains(&node_down)
-        && set.nodes.contains(&node_left)
-        && !set.nodes.contains(&node_down_left)
-    {
+    if neighbors.down && neighbors.left && !neighbors.down_left {
         top_right_tiles.push(SpriteBundle {
             texture: tex_set_tile_top_right.clone(),
             sprite: set_border_sprite(),
@@ -597,22 +669,20 @@ fn get_set_tiles_top_right(
 ///
 /// # Parameters
 ///
-/// - `node`: The node around which set tiles are being added.
+/// - `neighbors`: Which positions around the node hold nodes of the set.
 /// - `node_x`: The node's X coordinate in the scene.
 /// - `node_y`: The node's Y coordinate in the scene.
 /// - `set`: The game set for which tiles are being added.
-/// - `puzzle`: The puzzle for which the tiles are being added.
 /// - `asset_server`: The asset server used to load textures.
 ///
 /// # Returns
 ///
 /// A vector of Sprite Bundles.
 fn get_set_tiles_top_left(
-    node: &u16,
+    neighbors: &SquareSetNeighbors,
     node_x: f32,
     node_y: f32,
     set: &GameSet,
-    puzzle: &Puzzle,
     asset_server: AssetServer,
 ) -> Vec<SpriteBundle> {
     let mut top_left_tiles = Vec::new();
@@ -622,9 +692,7 @@ fn get_set_tiles_top_left(
         Texture::SetTileTopLeft.path()
     });
 
-    let node_up = get_node_up(node, puzzle).unwrap_or(u16::MAX);
-    let node_left = get_node_left(node, puzzle).unwrap_or(u16::MAX);
-    if !set.nodes.contains(&node_up) && !set.nodes.contains(&node_left) {
+    if !neighbors.up && !neighbors.left {
         top_left_tiles.push(SpriteBundle {
             texture: tex_set_tile_top_left.clone(),
             sprite: Sprite {
@@ -637,13 +705,7 @@ fn get_set_tiles_top_left(
         });
     }
 
-    let node_down_right = get_node_down_right(node, &puzzle).unwrap_or(u16::MAX);
-    let node_down = get_node_down(node, puzzle).unwrap_or(u16::MAX);
-    let node_right = get_node_right(node, puzzle).unwrap_or(u16::MAX);
-    if set.nodes.contains(&node_down)
-        && set.nodes.contains(&node_right)
-        && !set.nodes.contains(&node_down_right)
-    {
+    if neighbors.down && neighbors.right && !neighbors.down_right {
         top_left_tiles.push(SpriteBundle {
             texture: tex_set_tile_top_left.clone(),
             sprite: set_border_sprite(),
@@ -655,6 +717,63 @@ fn get_set_tiles_top_left(
     top_left_tiles
 }
 
+/// Returns a vector of sprite bundles for the edge set tiles around one node on a grid other
+/// than the square grid. An edge is drawn halfway towards each adjacent position without a
+/// node of the set, turned to face it and as long as the side of the node's cell.
+///
+/// # Parameters
+///
+/// - `node`: The node around which set tiles are being added.
+/// - `set`: The game set for which tiles are being added.
+/// - `puzzle`: The puzzle for which the tiles are being added.
+/// - `asset_server`: The asset server used to load textures.
+///
+/// # Returns
+///
+/// A vector of Sprite Bundles.
+fn get_set_tiles_edges(
+    node: &u16,
+    set: &GameSet,
+    puzzle: &Puzzle,
+    asset_server: AssetServer,
+) -> Vec<SpriteBundle> {
+    let tex_set_tile_edge = asset_server.load(if set.bounded {
+        Texture::SetTileBoundedVertical.path()
+    } else {
+        Texture::SetTileVertical.path()
+    });
+    let node_pos = Vec2::from(node_to_position(node, puzzle));
+    let neighbors = puzzle
+        .topology
+        .grid()
+        .neighbors(node_to_grid_position(node, puzzle));
+    // Sides of a regular cell, where the nodes of adjacent cells lie twice the spacing apart
+    let edge_length = SPRITE_SPACING * 2.0 * (PI / neighbors.len() as f32).tan();
+
+    neighbors
+        .into_iter()
+        .filter(|position| {
+            !grid_position_to_node(*position, puzzle).is_some_and(|node| set.nodes.contains(&node))
+        })
+        .map(|position| {
+            // Positions beyond a toroidal border are drawn beyond it, so the edge faces them
+            let direction = Vec2::from(grid_position_to_scene(position, puzzle)) - node_pos;
+            let center = node_pos + direction / 2.0;
+            SpriteBundle {
+                texture: tex_set_tile_edge.clone(),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::new(TILE_NODE_SPRITE_SIZE, edge_length)),
+                    color: COLOR_SET_BORDER,
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(center.x, center.y, 0.0)
+                    .with_rotation(Quat::from_rotation_z(direction.to_angle())),
+                ..default()
+            }
+        })
+        .collect()
+}
+
 /// Returns the appropriate texture for a set tile behind a given node. The reason for this is that overlapping
 /// sets need to be distinguished by interlaced colors with sprites that match that offset.
 ///
@@ -774,53 +893,58 @@ pub fn get_set_tiles(
     let mut tiles = Vec::new();
 
     for node in set.nodes.iter() {
+        if !puzzle.topology.is_square() {
+            tiles.append(&mut get_set_tiles_edges(
+                node,
+                set,
+                puzzle,
+                asset_server.clone(),
+            ));
+            continue;
+        }
+
         let (node_x, node_y) = node_to_position(node, &puzzle);
+        let neighbors = get_square_set_neighbors(node, set, puzzle);
         tiles.append(&mut get_set_tiles_vertical(
-            node,
+            &neighbors,
             node_x,
             node_y,
             set,
-            puzzle,
             asset_server.clone(),
         ));
         tiles.append(&mut get_set_tiles_horizontal(
-            node,
+            &neighbors,
             node_x,
             node_y,
             set,
-            puzzle,
             asset_server.clone(),
         ));
         tiles.append(&mut get_set_tiles_bottom_right(
-            node,
+            &neighbors,
             node_x,
             node_y,
             set,
-            puzzle,
             asset_server.clone(),
         ));
         tiles.append(&mut get_set_tiles_bottom_left(
-            node,
+            &neighbors,
             node_x,
             node_y,
             set,
-            puzzle,
             asset_server.clone(),
         ));
         tiles.append(&mut get_set_tiles_top_right(
-            node,
+            &neighbors,
             node_x,
             node_y,
             set,
-            puzzle,
             asset_server.clone(),
         ));
         tiles.append(&mut get_set_tiles_top_left(
-            node,
+            &neighbors,
             node_x,
             node_y,
             set,
-            puzzle,
             asset_server.clone(),
         ));
     }
@@ -838,6 +962,19 @@ pub fn remove_line(
     end_node: &mut ActiveNode,
     active_lines: &mut Vec<ActiveLine>,
 ) {
+    // Fixed lines are part of the puzzle, so they stay drawn
+    let (first_id, second_id) = (
+        start_node.node.id.min(end_node.node.id),
+        start_node.node.id.max(end_node.node.id),
+    );
+    if active_lines.iter().any(|active_line| {
+        active_line.fixed
+            && active_line.start_node.node.id == first_id
+            && active_line.end_node.node.id == second_id
+    }) {
+        return;
+    }
+
     // Remove connection from start to end
     start_node.connections.remove(&end_node.node.id);
 
@@ -859,9 +996,12 @@ pub fn remove_line(
         if active_lines[idx].start_node.node.id == first_node.node.id
             && active_lines[idx].end_node.node.id == second_node.node.id
         {
-            commands
-                .entity(active_lines[idx].sprite_entity_id)
-                .despawn();
+            active_lines[idx]
+                .sprite_entity_ids
+                .iter()
+                .for_each(|sprite| {
+                    commands.entity(*sprite).despawn();
+                });
             active_lines.remove(idx);
             break;
         }
@@ -883,48 +1023,80 @@ pub fn get_mut_start_end_nodes(
     (first, second)
 }
 
+/// Returns true if a line between start_node and end_node would cross a diagonal line already
+/// drawn within the same grid cell.
+fn is_crossing_active_line(
+    start_node: &ActiveNode,
+    end_node: &ActiveNode,
+    active_lines: &[ActiveLine],
+    puzzle: &Puzzle,
+) -> bool {
+    GameLine::new(start_node.node.id, end_node.node.id)
+        .ok()
+        .and_then(|line| get_crossing_line(&line, puzzle))
+        .is_some_and(|crossing| {
+            // Active lines are stored with the smallest id as the start node
+            active_lines.iter().any(|active_line| {
+                active_line.start_node.node.id == crossing.node_a_id()
+                    && active_line.end_node.node.id == crossing.node_b_id()
+            })
+        })
+}
+
 /// Adds a line connecting start_node and end_node to the current puzzle. Also updates
-/// the game state to visually add the line.
+/// the game state to visually add the line. Lines crossing an existing diagonal line are
+/// not added, unless the puzzle allows crossings, and neither are forbidden lines. Fixed lines
+/// are added locked, so they can't be removed.
 pub fn add_line(
     commands: &mut Commands,
     asset_server: AssetServer,
     start_node: &mut ActiveNode,
     end_node: &mut ActiveNode,
     active_lines: &mut Vec<ActiveLine>,
+    puzzle: &Puzzle,
 ) {
-    let start_pos = start_node.sprite.transform.translation.truncate();
-    let end_pos = end_node.sprite.transform.translation.truncate();
-
-    // Get the appropriate line texture, if exists (otherwise invalid node pair)
-    let line_texture = get_line_texture(start_node, end_node).unwrap_or(&Texture::Missing);
+    // Lines already drawn (e.g. fixed lines in saved progress) and forbidden lines aren't added
+    let Ok(line) = GameLine::new(start_node.node.id, end_node.node.id) else {
+        return;
+    };
+    if start_node.connections.contains(&end_node.node.id) || puzzle.forbidden_lines.contains(&line)
+    {
+        return;
+    }
 
-    if *line_texture == Texture::Missing {
+    // Get the sprites of the line, if any (otherwise invalid node pair)
+    let line_sprites = get_line_sprites(
+        start_node,
+        end_node,
+        puzzle,
+        &asset_server,
+        Color::WHITE,
+        Z_LINE,
+    );
+    if line_sprites.is_empty() {
         return;
     }
 
-    let line_sprite = SpriteBundle {
-        texture: asset_server.load(line_texture.path()),
-        sprite: Sprite {
-            custom_size: Some(Vec2::new(TILE_NODE_SPRITE_SIZE, TILE_NODE_SPRITE_SIZE)),
-            ..Default::default()
-        },
-        transform: Transform::from_xyz(
-            (end_pos.x + start_pos.x) / 2.0,
-            (end_pos.y + start_pos.y) / 2.0,
-            Z_LINE,
-        ),
-        ..Default::default()
-    };
+    if !puzzle.allow_crossings
+        && is_crossing_active_line(start_node, end_node, active_lines, puzzle)
+    {
+        return;
+    }
 
     // Update connections of both start and end node
     start_node.connections.insert(end_node.node.id);
     end_node.connections.insert(start_node.node.id);
 
     // Add line to the screen
-    let line_entity_id = commands
-        .spawn(line_sprite.clone())
-        .insert(OnPuzzleScene)
-        .id();
+    let line_entity_ids: Vec<Entity> = line_sprites
+        .iter()
+        .map(|line_sprite| {
+            commands
+                .spawn(line_sprite.clone())
+                .insert(OnPuzzleScene)
+                .id()
+        })
+        .collect();
 
     // Update list of lines, putting the smallest ID first.
     let first_node = if start_node.node.id < end_node.node.id {
@@ -940,25 +1112,81 @@ pub fn add_line(
     active_lines.push(ActiveLine {
         start_node: first_node.clone(),
         end_node: second_node.clone(),
-        sprite: line_sprite.clone(),
+        sprites: line_sprites,
         active_id: ActiveIdentifier::new(),
-        sprite_entity_id: line_entity_id,
+        sprite_entity_ids: line_entity_ids,
+        fixed: puzzle.fixed_lines.contains(&line),
     });
 }
 
-/// Clear all lines from a puzzle.
+/// Highlights the line between start_node and end_node for a hint, whether or not the line is
+/// drawn. Returns the entities of the highlight, which are empty if the nodes aren't adjacent.
+pub fn spawn_hint_line(
+    commands: &mut Commands,
+    asset_server: AssetServer,
+    start_node: &ActiveNode,
+    end_node: &ActiveNode,
+    color: Color,
+    puzzle: &Puzzle,
+) -> Vec<Entity> {
+    get_line_sprites(
+        start_node,
+        end_node,
+        puzzle,
+        &asset_server,
+        color,
+        Z_HINT_LINE,
+    )
+    .into_iter()
+    .map(|hint_sprite| commands.spawn(hint_sprite).insert(OnPuzzleScene).id())
+    .collect()
+}
+
+/// Writes a number over a condition or rule sprite, for those whose texture is left empty to
+/// hold their value (e.g. Degree).
+pub fn spawn_sprite_label(commands: &mut Commands, value: u8, sprite_transform: Transform) {
+    let mut label_transform = sprite_transform;
+    label_transform.translation.z = Z_CDTN_LABEL;
+    commands
+        .spawn(Text2dBundle {
+            text: Text::from_section(
+                value.to_string(),
+                TextStyle {
+                    font_size: CDTN_LABEL_FONT_SIZE,
+                    color: TEXT_COLOR,
+                    ..Default::default()
+                },
+            ),
+            transform: label_transform,
+            ..Default::default()
+        })
+        .insert(OnPuzzleScene);
+}
+
+/// Clear all lines from a puzzle, except its fixed lines.
 pub fn clear_all_lines(
     commands: &mut Commands,
     active_nodes: &mut Vec<ActiveNode>,
     active_lines: &mut Vec<ActiveLine>,
 ) {
-    active_nodes.iter_mut().for_each(|node| {
-        node.connections.clear();
+    // Fixed lines are part of the puzzle, so only the others are cleared
+    active_lines.retain(|active_line| {
+        if !active_line.fixed {
+            active_line.sprite_entity_ids.iter().for_each(|sprite| {
+                commands.entity(*sprite).despawn();
+            });
+        }
+        active_line.fixed
     });
-    active_lines.iter_mut().for_each(|active_line| {
-        commands.entity(active_line.sprite_entity_id).despawn();
+    active_nodes.iter_mut().for_each(|node| {
+        let node_id = node.node.id;
+        node.connections.retain(|other_id| {
+            active_lines.iter().any(|active_line| {
+                active_line.start_node.node.id == node_id.min(*other_id)
+                    && active_line.end_node.node.id == node_id.max(*other_id)
+            })
+        });
     });
-    active_lines.clear();
 }
 
 /// Unload all active elements from a puzzle.
@@ -974,7 +1202,9 @@ pub fn unload_active_elements(
     active_nodes.clear();
 
     active_lines.iter_mut().for_each(|active_line| {
-        commands.entity(active_line.sprite_entity_id).despawn();
+        active_line.sprite_entity_ids.iter().for_each(|sprite| {
+            commands.entity(*sprite).despawn();
+        });
     });
     active_lines.clear();
 
@@ -989,42 +1219,95 @@ pub fn unload_active_elements(
 pub(crate) fn exit_puzzle(
     puzzle: Uuid,
     solved: bool,
+    hints_used: u32,
     commands: &mut Commands,
     active_nodes: &mut Vec<ActiveNode>,
     active_sets: &mut Vec<ActiveSet>,
     active_lines: &mut Vec<ActiveLine>,
     app_state: &mut NextState<AppState>,
 ) {
-    save_progress(puzzle, active_nodes_to_solution(&active_nodes), solved);
+    save_progress(
+        puzzle,
+        active_nodes_to_solution(&active_nodes),
+        solved,
+        hints_used,
+    );
     unload_active_elements(commands, active_nodes, active_sets, active_lines);
     app_state.set(AppState::Campaign);
 }
 
-/// Sets the camera to focus on the center of the puzzle and scale according to its width and height
+/// Sets the camera to focus on the center of the nodes of the puzzle and scale to fit them,
+/// leaving out any holes along the edges of the board
 pub(crate) fn update_camera(
     window: &Window,
     camera_transform: &mut Mut<Transform>,
     camera_projection: &mut Mut<OrthographicProjection>,
     puzzle: &Puzzle,
 ) {
+    let positions: Vec<(f32, f32)> = puzzle
+        .nodes
+        .iter()
+        .map(|node| node_to_position(&node.id, puzzle))
+        .collect();
+    if positions.is_empty() {
+        return;
+    }
+
+    // Get the bounds of the nodes to include in the window
+    let min_x = positions.iter().map(|(x, _)| *x).fold(f32::MAX, f32::min);
+    let max_x = positions.iter().map(|(x, _)| *x).fold(f32::MIN, f32::max);
+    let min_y = positions.iter().map(|(_, y)| *y).fold(f32::MAX, f32::min);
+    let max_y = positions.iter().map(|(_, y)| *y).fold(f32::MIN, f32::max);
+
     // Get the center and move camera there
-    let focus_point = Vec2::new(puzzle.width as f32 * SPRITE_SPACING, puzzle.height as f32 * SPRITE_SPACING);
+    let focus_point = Vec2::new((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
 
     **camera_transform = Transform {
-        translation: Vec3::new(focus_point.x as f32, focus_point.y as f32, 0.0),
+        translation: Vec3::new(focus_point.x, focus_point.y, 0.0),
         ..Default::default()
     };
 
-    // Get the topmost and rightmost positions of nodes to include in the window
-    let rightmost_position = node_to_position(&((puzzle.width - 1) as GameNodeId), puzzle);
-    let topmost_position = node_to_position(&((puzzle.height - 1) as GameNodeId), puzzle);
-
-    // Get the required scale and fit to include all unlocked puzzles in the window
-    let distance_x = rightmost_position.0 - focus_point.x + TILE_NODE_SPRITE_SIZE + WINDOW_PADDING;
-    let distance_y = topmost_position.1 - focus_point.y + TILE_NODE_SPRITE_SIZE + WINDOW_PADDING;
+    // Get the required scale and fit to include all nodes in the window
+    let distance_x = max_x - focus_point.x + TILE_NODE_SPRITE_SIZE + WINDOW_PADDING;
+    let distance_y = max_y - focus_point.y + TILE_NODE_SPRITE_SIZE + WINDOW_PADDING;
 
     let scale = (distance_x / (window.width() / 2.0))
         .max(distance_y / (window.height() / 2.0));
 
     camera_projection.scale = scale;
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use crate::{
+        scenes::puzzle::util::get_puzzle_background_texture,
+        structs::immutable::puzzle::puzzle::Puzzle, texture::Texture,
+    };
+
+    #[test]
+    fn test_background_texture_next_to_hole_returns_none() {
+        let mut puzzle = Puzzle::new(3, 3);
+        puzzle.nodes.retain(|node| node.id != 0);
+
+        // Tiles around the bottom left corner only border the hole
+        assert!(get_puzzle_background_texture(0, 0, &puzzle).is_none());
+        assert!(get_puzzle_background_texture(0, 1, &puzzle).is_none());
+        assert!(get_puzzle_background_texture(1, 0, &puzzle).is_none());
+    }
+
+    #[test]
+    fn test_background_texture_between_hole_and_node_returns_side() {
+        let mut puzzle = Puzzle::new(3, 3);
+        puzzle.nodes.retain(|node| node.id != 0);
+
+        assert!(get_puzzle_background_texture(2, 1, &puzzle) == Some(Texture::BgTileSideLeft));
+        assert!(get_puzzle_background_texture(1, 2, &puzzle) == Some(Texture::BgTileSideBottom));
+    }
+
+    #[test]
+    fn test_background_texture_between_nodes_returns_between() {
+        let puzzle = Puzzle::new(3, 3);
+
+        assert!(get_puzzle_background_texture(2, 2, &puzzle) == Some(Texture::BgTileBetweenCross));
+        assert!(get_puzzle_background_texture(0, 0, &puzzle) == Some(Texture::BgTileBottomLeft));
+    }
+}
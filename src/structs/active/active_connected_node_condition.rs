@@ -4,17 +4,14 @@ pub mod active_connected_node_condition {
         sprite::{Sprite, SpriteBundle},
     };
 
-    use crate::{
-        structs::{
-            active::{
-                active_identifier::active_identifier::ActiveIdentifier, traits::traits::Satisfiable,
-            },
-            immutable::connected_node_condition::connected_node_condition::{
-                ConditionClass, ConnectedNodeCondition,
-            },
+    use crate::structs::{
+        active::{
+            active_identifier::active_identifier::ActiveIdentifier, traits::traits::Satisfiable,
+        },
+        immutable::{
+            connected_node_condition::connected_node_condition::ConnectedNodeCondition,
+            palette::palette::ClassColors,
         },
-        COLOR_CDTN_BLUE_SAT, COLOR_CDTN_BLUE_UNSAT, COLOR_CDTN_GREEN_SAT, COLOR_CDTN_GREEN_UNSAT,
-        COLOR_CDTN_PURPLE_SAT, COLOR_CDTN_PURPLE_UNSAT,
     };
 
     #[derive(Clone)]
@@ -24,6 +21,8 @@ pub mod active_connected_node_condition {
         pub sprite: SpriteBundle,
         pub sprite_entity_id: Entity,
         pub satisfied: bool,
+        /// Colors of the class, from the palette of the puzzle.
+        pub colors: ClassColors,
     }
 
     impl Satisfiable for ActiveConnectedNodeCondition {
@@ -37,29 +36,7 @@ pub mod active_connected_node_condition {
 
         fn update_sprites(&mut self, sprites: Vec<&mut Sprite>) {
             for sprite in sprites {
-                sprite.color = match self.condition.condition_class() {
-                    ConditionClass::Blue => {
-                        if self.satisfied {
-                            COLOR_CDTN_BLUE_SAT
-                        } else {
-                            COLOR_CDTN_BLUE_UNSAT
-                        }
-                    }
-                    ConditionClass::Purple => {
-                        if self.satisfied {
-                            COLOR_CDTN_PURPLE_SAT
-                        } else {
-                            COLOR_CDTN_PURPLE_UNSAT
-                        }
-                    }
-                    ConditionClass::Green => {
-                        if self.satisfied {
-                            COLOR_CDTN_GREEN_SAT
-                        } else {
-                            COLOR_CDTN_GREEN_UNSAT
-                        }
-                    }
-                }
+                sprite.color = self.colors.get(self.satisfied);
             }
         }
     }
@@ -4,15 +4,13 @@ pub mod active_connected_set_rule {
         sprite::{Sprite, SpriteBundle},
     };
 
-    use crate::{
-        structs::{
-            active::{
-                active_identifier::active_identifier::ActiveIdentifier, traits::traits::Satisfiable,
-            },
-            immutable::connected_set_rule::connected_set_rule::{ConnectedSetRule, RuleClass},
+    use crate::structs::{
+        active::{
+            active_identifier::active_identifier::ActiveIdentifier, traits::traits::Satisfiable,
+        },
+        immutable::{
+            connected_set_rule::connected_set_rule::ConnectedSetRule, palette::palette::ClassColors,
         },
-        COLOR_RULE_ORANGE_SAT, COLOR_RULE_ORANGE_UNSAT, COLOR_RULE_RED_SAT, COLOR_RULE_RED_UNSAT,
-        COLOR_RULE_YELLOW_SAT, COLOR_RULE_YELLOW_UNSAT,
     };
 
     #[derive(Clone)]
@@ -22,6 +20,8 @@ pub mod active_connected_set_rule {
         pub sprite: SpriteBundle,
         pub sprite_entity_id: Entity,
         pub satisfied: bool,
+        /// Colors of the class, from the palette of the puzzle.
+        pub colors: ClassColors,
     }
 
     impl Satisfiable for ActiveConnectedSetRule {
@@ -35,29 +35,7 @@ pub mod active_connected_set_rule {
 
         fn update_sprites(&mut self, sprites: Vec<&mut Sprite>) {
             for sprite in sprites {
-                sprite.color = match self.rule.rule_class() {
-                    RuleClass::Yellow => {
-                        if self.satisfied {
-                            COLOR_RULE_YELLOW_SAT
-                        } else {
-                            COLOR_RULE_YELLOW_UNSAT
-                        }
-                    }
-                    RuleClass::Orange => {
-                        if self.satisfied {
-                            COLOR_RULE_ORANGE_SAT
-                        } else {
-                            COLOR_RULE_ORANGE_UNSAT
-                        }
-                    }
-                    RuleClass::Red => {
-                        if self.satisfied {
-                            COLOR_RULE_RED_SAT
-                        } else {
-                            COLOR_RULE_RED_UNSAT
-                        }
-                    }
-                }
+                sprite.color = self.colors.get(self.satisfied);
             }
         }
     }
@@ -11,7 +11,9 @@ pub mod active_line {
         pub active_id: ActiveIdentifier,
         pub start_node: ActiveNode,
         pub end_node: ActiveNode,
-        pub sprite: SpriteBundle,
-        pub sprite_entity_id: Entity,
+        pub sprites: Vec<SpriteBundle>,
+        pub sprite_entity_ids: Vec<Entity>,
+        /// Whether the puzzle fixes the line in place, so it can't be removed.
+        pub fixed: bool,
     }
 }
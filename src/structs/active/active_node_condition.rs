@@ -11,7 +11,8 @@ pub mod active_node_condition {
                 active_node::active_node::ActiveNode, traits::traits::Satisfiable,
             },
             immutable::{
-                node_condition::node_condition::NodeCondition, solution::solution::Solution,
+                node_condition::node_condition::NodeCondition, puzzle::puzzle::Puzzle,
+                solution::solution::Solution,
             },
         },
         COLOR_CDTN_SAT, COLOR_CDTN_UNSAT,
@@ -27,8 +28,13 @@ pub mod active_node_condition {
     }
 
     impl ActiveNodeCondition {
-        pub fn check_satisfied(&self, node: &ActiveNode, solution: &Solution) -> bool {
-            return self.condition.is_satisfied(&node.node, solution);
+        pub fn check_satisfied(
+            &self,
+            node: &ActiveNode,
+            solution: &Solution,
+            puzzle: &Puzzle,
+        ) -> bool {
+            return self.condition.is_satisfied(&node.node, solution, puzzle);
         }
     }
 
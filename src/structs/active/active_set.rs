@@ -8,6 +8,7 @@ pub mod active_set {
         active::{
             active_connected_set_rule::active_connected_set_rule::ActiveConnectedSetRule,
             active_identifier::active_identifier::ActiveIdentifier,
+            active_meta_set_rule::active_meta_set_rule::ActiveMetaSetRule,
             active_set_rule::active_set_rule::ActiveSetRule, traits::traits::Satisfiable,
         },
         immutable::game_set::game_set::GameSet,
@@ -21,6 +22,7 @@ pub mod active_set {
         pub sprite_entity_ids: Vec<Entity>,
         pub active_set_rules: Vec<ActiveSetRule>,
         pub active_connected_set_rules: Vec<ActiveConnectedSetRule>,
+        pub active_meta_set_rules: Vec<ActiveMetaSetRule>,
         pub satisfied: bool,
     }
 
@@ -2,6 +2,7 @@ pub mod active_connected_node_condition;
 pub mod active_connected_set_rule;
 pub mod active_identifier;
 pub mod active_line;
+pub mod active_meta_set_rule;
 pub mod active_node;
 pub mod active_node_condition;
 pub mod active_set;
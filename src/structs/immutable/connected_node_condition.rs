@@ -1,14 +1,21 @@
 pub mod connected_node_condition {
-    use bevy::{color::Color, math::Vec2, sprite::Sprite};
+    use bevy::{math::Vec2, sprite::Sprite};
     use serde::{Deserialize, Serialize};
 
     use crate::{
-        logic::connected_condition_checks::connected_condition_checks::{
-            is_degree_equal, is_distance_equal,
+        logic::{
+            connected_condition_checks::connected_condition_checks::{
+                is_degree_distinct, is_degree_equal, is_distance_distinct, is_distance_equal,
+                is_same_component_path,
+            },
+            diagnostic::diagnostic::CheckResult,
         },
-        structs::immutable::{game_node::game_node::GameNode, solution::solution::Solution},
-        CDTN_RULE_SPRITE_SIZE, COLOR_CDTN_BLUE_UNSAT, COLOR_CDTN_GREEN_UNSAT,
-        COLOR_CDTN_PURPLE_UNSAT,
+        structs::immutable::{
+            game_node::game_node::GameNode,
+            palette::palette::{ClassData, Palette},
+            solution::solution::Solution,
+        },
+        CDTN_RULE_SPRITE_SIZE,
     };
 
     /// ConnectedNodeCondition applies a condition to a single node in a puzzle
@@ -21,6 +28,15 @@ pub mod connected_node_condition {
         DegreeEqual(ConditionClass),
         /// All nodes of this ConditionClass must have the same minimum distance to each other.
         DistanceEqual(ConditionClass),
+        /// This node's degree must differ from every other node with this condition of the
+        /// same ConditionClass.
+        DegreeDistinct(ConditionClass),
+        /// No two pairs of nodes of this ConditionClass may have the same minimum distance
+        /// to each other.
+        DistanceDistinct(ConditionClass),
+        /// All nodes of this ConditionClass must lie on a single path, which visits no node
+        /// twice.
+        SameComponentPath(ConditionClass),
     }
 
     impl ConnectedNodeCondition {
@@ -28,13 +44,16 @@ pub mod connected_node_condition {
             match self {
                 ConnectedNodeCondition::DegreeEqual(condition_class) => condition_class,
                 ConnectedNodeCondition::DistanceEqual(condition_class) => condition_class,
+                ConnectedNodeCondition::DegreeDistinct(condition_class) => condition_class,
+                ConnectedNodeCondition::DistanceDistinct(condition_class) => condition_class,
+                ConnectedNodeCondition::SameComponentPath(condition_class) => condition_class,
             }
         }
 
-        pub fn sprite(&self) -> Sprite {
+        pub fn sprite(&self, palette: &Palette) -> Sprite {
             Sprite {
                 custom_size: Some(Vec2::new(CDTN_RULE_SPRITE_SIZE, CDTN_RULE_SPRITE_SIZE)),
-                color: self.condition_class().color().clone(),
+                color: palette.condition_colors(self.condition_class()).get(false),
                 ..Default::default()
             }
         }
@@ -42,6 +61,11 @@ pub mod connected_node_condition {
         /// Takes the connected node condition, nodes with that condition of matching class, and a solution, then
         /// returns if it is satisfied or not for those nodes (reflexive).
         pub fn is_satisfied(&self, nodes: Vec<&GameNode>, solution: &Solution) -> bool {
+            self.check(nodes, solution).satisfied
+        }
+
+        /// Checks the condition like `is_satisfied`, also explaining why it is (un)satisfied.
+        pub fn check(&self, nodes: Vec<&GameNode>, solution: &Solution) -> CheckResult {
             match self {
                 ConnectedNodeCondition::DegreeEqual(_condition_class) => {
                     is_degree_equal(nodes, solution)
@@ -49,25 +73,82 @@ pub mod connected_node_condition {
                 ConnectedNodeCondition::DistanceEqual(_condition_class) => {
                     is_distance_equal(nodes, solution)
                 }
+                ConnectedNodeCondition::DegreeDistinct(_condition_class) => {
+                    is_degree_distinct(nodes, solution)
+                }
+                ConnectedNodeCondition::DistanceDistinct(_condition_class) => {
+                    is_distance_distinct(nodes, solution)
+                }
+                ConnectedNodeCondition::SameComponentPath(_condition_class) => {
+                    is_same_component_path(nodes, solution)
+                }
             }
         }
     }
 
-    /// ConditionClass is the class of the ConnectedNodeCondition.
+    /// ConditionClass is the class of the ConnectedNodeCondition, an id whose colors come
+    /// from the palette of the puzzle.
     #[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
-    pub enum ConditionClass {
-        Blue,
-        Purple,
-        Green,
-    }
+    #[serde(try_from = "ClassData")]
+    pub struct ConditionClass(pub u8);
 
-    impl ConditionClass {
-        pub fn color(&self) -> &Color {
-            match self {
-                ConditionClass::Blue => &COLOR_CDTN_BLUE_UNSAT,
-                ConditionClass::Purple => &COLOR_CDTN_PURPLE_UNSAT,
-                ConditionClass::Green => &COLOR_CDTN_GREEN_UNSAT,
-            }
+    /// Names of the original condition classes, which older puzzles use in place of their ids.
+    pub(crate) const CONDITION_CLASS_NAMES: [&str; 3] = ["Blue", "Purple", "Green"];
+
+    impl TryFrom<ClassData> for ConditionClass {
+        type Error = String;
+
+        fn try_from(data: ClassData) -> Result<Self, Self::Error> {
+            data.into_id(&CONDITION_CLASS_NAMES).map(ConditionClass)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::structs::immutable::connected_node_condition::connected_node_condition::{
+        ConditionClass, ConnectedNodeCondition,
+    };
+
+    #[test]
+    fn test_deserialize_legacy_class_name_returns_class_id() {
+        let conditions: Vec<ConnectedNodeCondition> =
+            serde_json::from_str(r#"[{"DistanceEqual":"Blue"},{"DegreeEqual":"Green"}]"#).unwrap();
+
+        assert_eq!(
+            conditions,
+            [
+                ConnectedNodeCondition::DistanceEqual(ConditionClass(0)),
+                ConnectedNodeCondition::DegreeEqual(ConditionClass(2))
+            ]
+            .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_class_id_beyond_original_classes_returns_class_id() {
+        let condition: ConnectedNodeCondition =
+            serde_json::from_str(r#"{"DegreeDistinct":7}"#).unwrap();
+
+        assert_eq!(
+            condition,
+            ConnectedNodeCondition::DegreeDistinct(ConditionClass(7))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_unknown_class_name_returns_error() {
+        assert!(
+            serde_json::from_str::<ConnectedNodeCondition>(r#"{"DegreeEqual":"Cyan"}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn test_serialize_class_returns_class_id() {
+        assert_eq!(
+            serde_json::to_string(&ConnectedNodeCondition::DistanceEqual(ConditionClass(1)))
+                .unwrap(),
+            r#"{"DistanceEqual":1}"#
+        );
+    }
+}
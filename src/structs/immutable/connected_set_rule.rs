@@ -1,12 +1,22 @@
 pub mod connected_set_rule {
-    use bevy::{color::Color, math::Vec2, sprite::Sprite};
+    use bevy::{math::Vec2, sprite::Sprite};
     use serde::{Deserialize, Serialize};
 
     use crate::{
-        logic::connected_rule_checks::connected_rule_checks::are_homomorphic,
-        structs::immutable::{game_set::game_set::GameSet, solution::solution::Solution},
-        CDTN_RULE_SPRITE_SIZE, COLOR_RULE_ORANGE_UNSAT, COLOR_RULE_RED_UNSAT,
-        COLOR_RULE_YELLOW_UNSAT,
+        logic::{
+            connected_rule_checks::connected_rule_checks::{
+                are_congruent, are_edge_counts_equal, are_homomorphic, are_isomorphic,
+                are_mutually_disconnected, HomomorphismCache,
+            },
+            diagnostic::diagnostic::CheckResult,
+        },
+        structs::immutable::{
+            game_set::game_set::GameSet,
+            palette::palette::{ClassData, Palette},
+            puzzle::puzzle::Puzzle,
+            solution::solution::Solution,
+        },
+        CDTN_RULE_SPRITE_SIZE,
     };
 
     /// ConnectedSetRule implies connectivity between rules across
@@ -19,45 +29,133 @@ pub mod connected_set_rule {
         /// This set must be homomorphic to every other set with a rule
         /// of the same rule class.
         Homomorphic(RuleClass),
+        /// This set must be isomorphic to every other set with a rule of the same rule class,
+        /// i.e. have the same shape including the lines it lacks.
+        Isomorphic(RuleClass),
+        /// The lines within this set must form the same pattern on the grid as those within
+        /// every other set with a rule of the same rule class, up to translation, rotation and
+        /// reflection.
+        Congruent(RuleClass),
+        /// This set must contain as many lines as every other set with a rule of the same rule
+        /// class.
+        EdgeCountEqual(RuleClass),
+        /// This set must not be joined to any other set with a rule of the same rule class by a
+        /// path of lines outside the sets.
+        MutuallyDisconnected(RuleClass),
     }
 
     impl ConnectedSetRule {
         pub fn rule_class(&self) -> &RuleClass {
             match self {
-                ConnectedSetRule::Homomorphic(rule_class) => rule_class,
+                ConnectedSetRule::Homomorphic(rule_class)
+                | ConnectedSetRule::Isomorphic(rule_class)
+                | ConnectedSetRule::Congruent(rule_class)
+                | ConnectedSetRule::EdgeCountEqual(rule_class)
+                | ConnectedSetRule::MutuallyDisconnected(rule_class) => rule_class,
             }
         }
 
-        pub fn sprite(&self) -> Sprite {
+        /// Returns true if the rule only depends on the lines within its sets, so it can be
+        /// decided once those lines are.
+        pub fn is_within_sets(&self) -> bool {
+            !matches!(self, ConnectedSetRule::MutuallyDisconnected(_))
+        }
+
+        pub fn sprite(&self, palette: &Palette) -> Sprite {
             Sprite {
                 custom_size: Some(Vec2::new(CDTN_RULE_SPRITE_SIZE, CDTN_RULE_SPRITE_SIZE)),
-                color: self.rule_class().color().clone(),
+                color: palette.rule_colors(self.rule_class()).get(false),
                 ..Default::default()
             }
         }
 
-        pub fn is_satisfied(&self, sets: Vec<&GameSet>, solution: &Solution) -> bool {
+        /// Checks the rule over every set sharing it. The puzzle places the sets on the grid.
+        pub fn is_satisfied(
+            &self,
+            sets: Vec<&GameSet>,
+            solution: &Solution,
+            puzzle: &Puzzle,
+        ) -> bool {
             match self {
-                ConnectedSetRule::Homomorphic(_rule_class) => are_homomorphic(sets, solution),
+                ConnectedSetRule::Homomorphic(_rule_class) => {
+                    are_homomorphic(sets, solution).satisfied
+                }
+                ConnectedSetRule::Isomorphic(_rule_class) => {
+                    are_isomorphic(sets, solution).satisfied
+                }
+                ConnectedSetRule::Congruent(_rule_class) => {
+                    are_congruent(sets, solution, puzzle).satisfied
+                }
+                ConnectedSetRule::EdgeCountEqual(_rule_class) => {
+                    are_edge_counts_equal(sets, solution).satisfied
+                }
+                ConnectedSetRule::MutuallyDisconnected(_rule_class) => {
+                    are_mutually_disconnected(sets, solution).satisfied
+                }
+            }
+        }
+
+        /// Checks the rule like `is_satisfied`, also explaining why it is (un)satisfied, and
+        /// reuses results in the cache for sets whose lines were checked before.
+        pub fn check_with_cache(
+            &self,
+            sets: Vec<&GameSet>,
+            solution: &Solution,
+            puzzle: &Puzzle,
+            cache: &mut HomomorphismCache,
+        ) -> CheckResult {
+            match self {
+                ConnectedSetRule::Homomorphic(_rule_class) => {
+                    cache.are_homomorphic(&sets, solution)
+                }
+                ConnectedSetRule::Isomorphic(_rule_class) => cache.are_isomorphic(&sets, solution),
+                ConnectedSetRule::Congruent(_rule_class) => are_congruent(sets, solution, puzzle),
+                ConnectedSetRule::EdgeCountEqual(_rule_class) => {
+                    are_edge_counts_equal(sets, solution)
+                }
+                ConnectedSetRule::MutuallyDisconnected(_rule_class) => {
+                    are_mutually_disconnected(sets, solution)
+                }
             }
         }
     }
 
-    /// RuleClass is the class of the ConnectedSetRule.
+    /// RuleClass is the class of the ConnectedSetRule, an id whose colors come from the
+    /// palette of the puzzle.
     #[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
-    pub enum RuleClass {
-        Yellow,
-        Orange,
-        Red,
-    }
+    #[serde(try_from = "ClassData")]
+    pub struct RuleClass(pub u8);
 
-    impl RuleClass {
-        pub fn color(&self) -> &Color {
-            match self {
-                RuleClass::Yellow => &COLOR_RULE_YELLOW_UNSAT,
-                RuleClass::Orange => &COLOR_RULE_ORANGE_UNSAT,
-                RuleClass::Red => &COLOR_RULE_RED_UNSAT,
-            }
+    /// Names of the original rule classes, which older puzzles use in place of their ids.
+    pub(crate) const RULE_CLASS_NAMES: [&str; 3] = ["Yellow", "Orange", "Red"];
+
+    impl TryFrom<ClassData> for RuleClass {
+        type Error = String;
+
+        fn try_from(data: ClassData) -> Result<Self, Self::Error> {
+            data.into_id(&RULE_CLASS_NAMES).map(RuleClass)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::structs::immutable::connected_set_rule::connected_set_rule::{
+        ConnectedSetRule, RuleClass,
+    };
+
+    #[test]
+    fn test_deserialize_legacy_class_name_returns_class_id() {
+        let rules: Vec<ConnectedSetRule> =
+            serde_json::from_str(r#"[{"Homomorphic":"Red"},{"Isomorphic":4}]"#).unwrap();
+
+        assert_eq!(
+            rules,
+            [
+                ConnectedSetRule::Homomorphic(RuleClass(2)),
+                ConnectedSetRule::Isomorphic(RuleClass(4))
+            ]
+            .to_vec()
+        );
+    }
+}
@@ -1,51 +1,144 @@
 pub mod game_line {
-    use std::hash::{Hash, Hasher};
+    use std::{error::Error, fmt};
 
     use serde::{Deserialize, Serialize};
 
-    use crate::structs::active::active_node::active_node::ActiveNode;
+    use crate::structs::{
+        active::active_node::active_node::ActiveNode, immutable::game_node::game_node::GameNodeId,
+    };
 
-    #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
-    // TODO Make node ids private and override new() to enforce a < b.
+    /// A directionless line between two distinct nodes, where node a always has the smaller id.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+    #[serde(try_from = "GameLineData")]
     pub struct GameLine {
-        pub node_a_id: u16,
-        pub node_b_id: u16,
+        node_a_id: GameNodeId,
+        node_b_id: GameNodeId,
     }
 
-    // Enforce reflexive property on lines (they are directionless).
-    impl PartialEq for GameLine {
-        fn eq(&self, other: &Self) -> bool {
-            let ret = (self.node_a_id == other.node_a_id && self.node_b_id == other.node_b_id)
-                || (self.node_a_id == other.node_b_id && self.node_b_id == other.node_a_id);
-            ret
+    /// Reasons a line can't be drawn.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum LineError {
+        /// Both ends of the line are the same node.
+        SameNode(GameNodeId),
+        /// The node isn't in the puzzle.
+        UnknownNode(GameNodeId),
+        /// The nodes aren't adjacent in the puzzle grid.
+        NotAdjacent(GameNodeId, GameNodeId),
+        /// The puzzle forbids drawing a line between the nodes.
+        Forbidden(GameNodeId, GameNodeId),
+    }
+
+    impl fmt::Display for LineError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                LineError::SameNode(node_id) => {
+                    write!(f, "line starts and ends at node {}", node_id)
+                }
+                LineError::UnknownNode(node_id) => {
+                    write!(f, "node {} is not in the puzzle", node_id)
+                }
+                LineError::NotAdjacent(node_a_id, node_b_id) => {
+                    write!(f, "nodes {} and {} are not adjacent", node_a_id, node_b_id)
+                }
+                LineError::Forbidden(node_a_id, node_b_id) => write!(
+                    f,
+                    "line between nodes {} and {} is forbidden",
+                    node_a_id, node_b_id
+                ),
+            }
         }
     }
 
-    impl Eq for GameLine {}
+    impl Error for LineError {}
+
+    /// Line as stored in json, before its node ids are normalised.
+    #[derive(Deserialize)]
+    struct GameLineData {
+        node_a_id: GameNodeId,
+        node_b_id: GameNodeId,
+    }
 
-    // Hash lines (smallest, largest) node id, such that hash sets don't double index.
-    impl Hash for GameLine {
-        fn hash<H: Hasher>(&self, state: &mut H) {
-            let (min, max) = if self.node_a_id < self.node_b_id {
-                (self.node_a_id, self.node_b_id)
-            } else {
-                (self.node_b_id, self.node_a_id)
-            };
-            min.hash(state);
-            max.hash(state);
+    impl TryFrom<GameLineData> for GameLine {
+        type Error = LineError;
+
+        fn try_from(data: GameLineData) -> Result<Self, Self::Error> {
+            GameLine::new(data.node_a_id, data.node_b_id)
         }
     }
 
-    pub fn connections_to_lines(active_node: &ActiveNode) -> Vec<GameLine> {
-        let mut lines: Vec<GameLine> = Vec::new();
+    impl GameLine {
+        /// Creates a line between two nodes in either order.
+        ///
+        /// # Parameters
+        ///
+        /// - `node_a_id`: One end of the line.
+        /// - `node_b_id`: The other end of the line.
+        ///
+        /// # Returns
+        ///
+        /// Returns the line with the smaller node id first, or an error if both ids are the same.
+        pub fn new(node_a_id: GameNodeId, node_b_id: GameNodeId) -> Result<Self, LineError> {
+            if node_a_id == node_b_id {
+                return Err(LineError::SameNode(node_a_id));
+            }
+            Ok(GameLine {
+                node_a_id: node_a_id.min(node_b_id),
+                node_b_id: node_a_id.max(node_b_id),
+            })
+        }
 
-        for connection in &active_node.connections {
-            lines.push(GameLine {
-                node_a_id: active_node.node.id,
-                node_b_id: *connection,
-            });
+        /// Returns the smaller node id of the line.
+        pub fn node_a_id(&self) -> GameNodeId {
+            self.node_a_id
         }
 
-        lines
+        /// Returns the larger node id of the line.
+        pub fn node_b_id(&self) -> GameNodeId {
+            self.node_b_id
+        }
+
+        /// Returns true if either end of the line is the node.
+        pub fn contains(&self, node_id: &GameNodeId) -> bool {
+            self.node_a_id == *node_id || self.node_b_id == *node_id
+        }
+    }
+
+    pub fn connections_to_lines(active_node: &ActiveNode) -> Vec<GameLine> {
+        active_node
+            .connections
+            .iter()
+            .filter_map(|connection| GameLine::new(active_node.node.id, *connection).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::structs::immutable::game_line::game_line::{GameLine, LineError};
+
+    #[test]
+    fn test_new_orders_node_ids() {
+        let line = GameLine::new(3, 1).unwrap();
+
+        assert_eq!(line.node_a_id(), 1);
+        assert_eq!(line.node_b_id(), 3);
+        assert_eq!(line, GameLine::new(1, 3).unwrap());
+    }
+
+    #[test]
+    fn test_new_same_node_returns_error() {
+        assert_eq!(GameLine::new(2, 2), Err(LineError::SameNode(2)));
+    }
+
+    #[test]
+    fn test_deserialize_orders_node_ids() {
+        let line: GameLine = serde_json::from_str(r#"{"node_a_id":5,"node_b_id":4}"#).unwrap();
+
+        assert_eq!(line, GameLine::new(4, 5).unwrap());
+    }
+
+    #[test]
+    fn test_deserialize_same_node_returns_error() {
+        assert!(serde_json::from_str::<GameLine>(r#"{"node_a_id":5,"node_b_id":5}"#).is_err());
     }
 }
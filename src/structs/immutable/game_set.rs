@@ -3,7 +3,8 @@ pub mod game_set {
 
     use crate::structs::immutable::{
         connected_set_rule::connected_set_rule::ConnectedSetRule, game_node::game_node::GameNodeId,
-        set_rule::set_rule::SetRule,
+        meta_set_rule::meta_set_rule::MetaSetRule, set_rule::set_rule::SetRule,
+        solution::solution::Solution,
     };
 
     /// Set contains nodes over which it applies SetRules.
@@ -13,6 +14,31 @@ pub mod game_set {
         pub nodes: Vec<GameNodeId>,
         pub rules: Vec<SetRule>,
         pub connected_rules: Vec<ConnectedSetRule>,
+        /// Rules changing how the set rules combine, e.g. Xor. Without any, every set rule must
+        /// be met.
+        #[serde(default)]
+        pub meta_rules: Vec<MetaSetRule>,
         pub bounded: bool,
     }
+
+    impl GameSet {
+        /// Returns true if conditions of nodes within the set only consider lines within the set,
+        /// either because the set is bounded or because it has the Scope rule.
+        pub fn is_scoped(&self) -> bool {
+            self.bounded || self.rules.contains(&SetRule::Scope)
+        }
+
+        /// Checks the set rules of the set, combined by its meta rules if it has any.
+        pub fn are_rules_satisfied(&self, solution: &Solution) -> bool {
+            if self.meta_rules.is_empty() {
+                self.rules
+                    .iter()
+                    .all(|rule| rule.is_satisfied(self, solution))
+            } else {
+                self.meta_rules
+                    .iter()
+                    .all(|meta_rule| meta_rule.is_satisfied(self, solution))
+            }
+        }
+    }
 }
@@ -1,10 +1,102 @@
 pub mod meta_set_rule {
+    use bevy::{math::Vec2, sprite::Sprite};
     use serde::{Deserialize, Serialize};
 
+    use crate::{
+        logic::diagnostic::diagnostic::{CheckResult, Diagnostic},
+        structs::immutable::{game_set::game_set::GameSet, solution::solution::Solution},
+        CDTN_RULE_SPRITE_SIZE, COLOR_RULE_UNSAT,
+    };
+
     /// MetaSetRule applies rules on conditions or rules within the set.
-    #[derive(Serialize, Deserialize, Clone, Debug)]
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
     pub enum MetaSetRule {
         /// Exactly one set rule must be met for the set to be satisfied.
         Xor,
     }
+
+    impl MetaSetRule {
+        pub fn sprite(&self) -> Sprite {
+            Sprite {
+                custom_size: Some(Vec2::new(CDTN_RULE_SPRITE_SIZE, CDTN_RULE_SPRITE_SIZE)),
+                color: COLOR_RULE_UNSAT,
+                ..Default::default()
+            }
+        }
+
+        /// Checks the meta rule against the set rules of the set.
+        ///
+        /// # Parameters
+        ///
+        /// - `set`: The set whose rules the meta rule applies to.
+        /// - `solution`: The solution to check the set rules against.
+        ///
+        /// # Returns
+        ///
+        /// Returns true if the set rules combine as the meta rule requires.
+        pub fn is_satisfied(&self, set: &GameSet, solution: &Solution) -> bool {
+            self.check(set, solution).satisfied
+        }
+
+        /// Checks the meta rule like `is_satisfied`, also explaining why it is (un)satisfied.
+        pub fn check(&self, set: &GameSet, solution: &Solution) -> CheckResult {
+            match self {
+                MetaSetRule::Xor => {
+                    let satisfied_rules = set
+                        .rules
+                        .iter()
+                        .filter(|rule| rule.is_constraint() && rule.is_satisfied(set, solution))
+                        .count();
+                    CheckResult::new(
+                        satisfied_rules == 1,
+                        Diagnostic::SatisfiedRules(satisfied_rules),
+                    )
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::structs::immutable::{
+        game_line::game_line::GameLine, game_set::game_set::GameSet,
+        meta_set_rule::meta_set_rule::MetaSetRule, set_rule::set_rule::SetRule,
+        solution::solution::Solution,
+    };
+
+    fn get_test_set() -> GameSet {
+        GameSet {
+            id: 0,
+            nodes: [0, 1].to_vec(),
+            rules: [SetRule::Disconnected, SetRule::Leaf].to_vec(),
+            connected_rules: Vec::new(),
+            meta_rules: [MetaSetRule::Xor].to_vec(),
+            bounded: false,
+        }
+    }
+
+    #[test]
+    fn test_xor_one_rule_satisfied_returns_true() {
+        // Leaf holds, Disconnected doesn't as the set nodes are connected
+        let solution = Solution::from([GameLine::new(0, 1).unwrap(), GameLine::new(1, 2).unwrap()]);
+
+        assert!(MetaSetRule::Xor.is_satisfied(&get_test_set(), &solution));
+        assert!(get_test_set().are_rules_satisfied(&solution));
+    }
+
+    #[test]
+    fn test_xor_both_rules_satisfied_returns_false() {
+        let solution = Solution::from([GameLine::new(1, 2).unwrap()]);
+
+        assert!(!MetaSetRule::Xor.is_satisfied(&get_test_set(), &solution));
+        assert!(!get_test_set().are_rules_satisfied(&solution));
+    }
+
+    #[test]
+    fn test_xor_no_rules_satisfied_returns_false() {
+        let solution = Solution::from([GameLine::new(0, 1).unwrap()]);
+
+        assert!(!MetaSetRule::Xor.is_satisfied(&get_test_set(), &solution));
+    }
 }
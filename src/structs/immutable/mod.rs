@@ -3,6 +3,7 @@ pub mod game_set;
 
 pub mod connected_node_condition;
 pub mod node_condition;
+pub mod palette;
 
 pub mod connected_set_rule;
 pub mod game_line;
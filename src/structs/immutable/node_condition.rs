@@ -4,10 +4,16 @@ pub mod node_condition {
     use serde::{Deserialize, Serialize};
 
     use crate::{
-        logic::condition_checks::condition_checks::{
-            is_branch_equal, is_cycle, is_internal, is_leaf,
+        logic::{
+            condition_checks::condition_checks::{
+                is_branch_equal, is_cut_vertex, is_cycle, is_degree, is_internal, is_leaf,
+                is_straight,
+            },
+            diagnostic::diagnostic::CheckResult,
+        },
+        structs::immutable::{
+            game_node::game_node::GameNode, puzzle::puzzle::Puzzle, solution::solution::Solution,
         },
-        structs::immutable::{game_node::game_node::GameNode, solution::solution::Solution},
         CDTN_RULE_SPRITE_SIZE, COLOR_CDTN_UNSAT,
     };
 
@@ -23,6 +29,12 @@ pub mod node_condition {
         Internal,
         /// This node must be contained within a cycle.
         Cycle,
+        /// This node has exactly the given number of lines connected to it.
+        Degree(u8),
+        /// Removing this node must split its network in two or more.
+        CutVertex,
+        /// This node has exactly two lines connected to it, in opposite directions.
+        Straight,
     }
 
     impl NodeCondition {
@@ -36,7 +48,13 @@ pub mod node_condition {
 
         // This takes static instead of active objects since this logic has to be re-used
         // to validate puzzle answers which aren't being actively displayed.
-        pub fn is_satisfied(&self, node: &GameNode, solution: &Solution) -> bool {
+        pub fn is_satisfied(&self, node: &GameNode, solution: &Solution, puzzle: &Puzzle) -> bool {
+            self.check(node, solution, puzzle).satisfied
+        }
+
+        /// Checks the condition like `is_satisfied`, also explaining why it is (un)satisfied. The
+        /// puzzle gives the directions of lines.
+        pub fn check(&self, node: &GameNode, solution: &Solution, puzzle: &Puzzle) -> CheckResult {
             match self {
                 // NodeCondition::BranchEqual(bounded) => is_branch_equal(node, solution),
                 // NodeCondition::Leaf(bounded) => is_leaf(node, solution),
@@ -44,7 +62,39 @@ pub mod node_condition {
                 NodeCondition::Leaf => is_leaf(node, solution),
                 NodeCondition::Internal => is_internal(node, solution),
                 NodeCondition::Cycle => is_cycle(node, solution),
+                NodeCondition::Degree(degree) => is_degree(node, solution, *degree),
+                NodeCondition::CutVertex => is_cut_vertex(node, solution),
+                NodeCondition::Straight => is_straight(node, solution, puzzle),
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::structs::immutable::node_condition::node_condition::NodeCondition;
+
+    #[test]
+    fn test_deserialize_new_conditions_returns_conditions() {
+        let conditions: Vec<NodeCondition> =
+            serde_json::from_str(r#"[{"Degree":3},"CutVertex","Straight"]"#).unwrap();
+
+        assert_eq!(
+            conditions,
+            [
+                NodeCondition::Degree(3),
+                NodeCondition::CutVertex,
+                NodeCondition::Straight
+            ]
+            .to_vec()
+        );
+    }
+
+    #[test]
+    fn test_serialize_degree_returns_degree_object() {
+        assert_eq!(
+            serde_json::to_string(&NodeCondition::Degree(2)).unwrap(),
+            r#"{"Degree":2}"#
+        );
+    }
+}
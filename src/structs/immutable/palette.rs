@@ -0,0 +1,167 @@
+pub mod palette {
+    use bevy::color::{Color, Srgba};
+    use serde::{Deserialize, Serialize};
+
+    use crate::structs::immutable::{
+        connected_node_condition::connected_node_condition::ConditionClass,
+        connected_set_rule::connected_set_rule::RuleClass,
+    };
+
+    /// Colors of the condition and rule classes, by class id. Classes beyond the end of a list
+    /// reuse its colors from the start.
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    pub struct Palette {
+        pub condition_classes: Vec<ClassColors>,
+        pub rule_classes: Vec<ClassColors>,
+    }
+
+    impl Default for Palette {
+        fn default() -> Self {
+            serde_json::from_str(include_str!("../../../assets/themes/palette.json"))
+                .expect("default palette should be valid")
+        }
+    }
+
+    impl Palette {
+        /// Returns the colors of the condition class.
+        pub fn condition_colors(&self, class: &ConditionClass) -> ClassColors {
+            get_class_colors(&self.condition_classes, class.0)
+        }
+
+        /// Returns the colors of the rule class.
+        pub fn rule_colors(&self, class: &RuleClass) -> ClassColors {
+            get_class_colors(&self.rule_classes, class.0)
+        }
+    }
+
+    fn get_class_colors(colors: &[ClassColors], id: u8) -> ClassColors {
+        match colors.len() {
+            0 => ClassColors {
+                satisfied: HexColor(Color::BLACK),
+                unsatisfied: HexColor(Color::WHITE),
+            },
+            len => colors[id as usize % len],
+        }
+    }
+
+    /// Colors of a class, depending on whether its condition or rule is satisfied.
+    #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+    pub struct ClassColors {
+        pub satisfied: HexColor,
+        pub unsatisfied: HexColor,
+    }
+
+    impl ClassColors {
+        pub fn get(&self, satisfied: bool) -> Color {
+            if satisfied {
+                self.satisfied.0
+            } else {
+                self.unsatisfied.0
+            }
+        }
+    }
+
+    /// Color stored in json as a hex string, e.g. "#87CEEB".
+    #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(try_from = "String", into = "String")]
+    pub struct HexColor(pub Color);
+
+    impl TryFrom<String> for HexColor {
+        type Error = String;
+
+        fn try_from(hex: String) -> Result<Self, Self::Error> {
+            Srgba::hex(&hex)
+                .map(|color| HexColor(Color::Srgba(color)))
+                .map_err(|_| format!("invalid hex color {}", hex))
+        }
+    }
+
+    impl From<HexColor> for String {
+        fn from(color: HexColor) -> Self {
+            color.0.to_srgba().to_hex()
+        }
+    }
+
+    /// Class as stored in json, either its id or the name of one of the original classes.
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    pub(crate) enum ClassData {
+        Id(u8),
+        Name(String),
+    }
+
+    impl ClassData {
+        /// Resolves the class to its id, where a name is the id of its position in the names.
+        pub(crate) fn into_id(self, names: &[&str]) -> Result<u8, String> {
+            match self {
+                ClassData::Id(id) => Ok(id),
+                ClassData::Name(name) => names
+                    .iter()
+                    .position(|legacy_name| *legacy_name == name)
+                    .map(|id| id as u8)
+                    .ok_or_else(|| format!("unknown class {}", name)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::color::Color;
+
+    use crate::structs::immutable::{
+        connected_node_condition::connected_node_condition::ConditionClass,
+        palette::palette::{HexColor, Palette},
+    };
+
+    #[test]
+    fn test_default_palette_has_original_classes() {
+        let palette = Palette::default();
+
+        assert!(palette.condition_classes.len() >= 3);
+        assert!(palette.rule_classes.len() >= 3);
+    }
+
+    #[test]
+    fn test_condition_colors_beyond_palette_wraps_around() {
+        let palette = Palette::default();
+        let len = palette.condition_classes.len() as u8;
+
+        assert_eq!(
+            palette.condition_colors(&ConditionClass(len + 1)),
+            palette.condition_colors(&ConditionClass(1))
+        );
+    }
+
+    #[test]
+    fn test_condition_colors_of_puzzle_palette_returns_class_colors() {
+        let palette: Palette = serde_json::from_str(
+            r##"{
+                "condition_classes": [
+                    { "satisfied": "#000000", "unsatisfied": "#FFFFFF" },
+                    { "satisfied": "#FF0000", "unsatisfied": "#00FF00" }
+                ],
+                "rule_classes": []
+            }"##,
+        )
+        .unwrap();
+
+        let colors = palette.condition_colors(&ConditionClass(1));
+
+        assert_eq!(colors.get(true), Color::srgb_u8(0xFF, 0, 0));
+        assert_eq!(colors.get(false), Color::srgb_u8(0, 0xFF, 0));
+    }
+
+    #[test]
+    fn test_hex_color_round_trips() {
+        let color: HexColor = serde_json::from_str(r##""#87CEEB""##).unwrap();
+
+        assert_eq!(color.0, Color::srgb_u8(0x87, 0xCE, 0xEB));
+        assert_eq!(serde_json::to_string(&color).unwrap(), r##""#87CEEB""##);
+    }
+
+    #[test]
+    fn test_hex_color_invalid_returns_error() {
+        assert!(serde_json::from_str::<HexColor>(r#""blue""#).is_err());
+    }
+}
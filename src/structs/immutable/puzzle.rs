@@ -1,15 +1,108 @@
 pub mod puzzle {
-    use serde::Deserialize;
+    use serde::{Deserialize, Serialize};
     use uuid::Uuid;
 
-    use crate::structs::immutable::{game_node::game_node::GameNode, game_set::game_set::GameSet};
+    use crate::{
+        logic::{topology::topology::Topology, util::get_adjacent_nodes},
+        structs::immutable::{
+            game_line::game_line::{GameLine, LineError},
+            game_node::game_node::{GameNode, GameNodeId},
+            game_set::game_set::GameSet,
+            palette::palette::Palette,
+        },
+    };
 
-    #[derive(Clone, Deserialize, Debug)]
+    /// A puzzle on a width x height grid, where node ids count up each column from the bottom
+    /// left. Positions of the grid without a node are holes, shaping the board.
+    #[derive(Clone, Serialize, Deserialize, Debug)]
     pub struct Puzzle {
+        /// Version of the json format the puzzle was written in, 0 for puzzles from before
+        /// versioning. Older documents are migrated to the current version when loaded.
+        #[serde(default)]
+        pub format_version: u32,
         pub uuid: Uuid,
         pub width: u8,
         pub height: u8,
         pub nodes: Vec<GameNode>,
         pub sets: Vec<GameSet>,
+        /// Whether both diagonal lines within the same grid cell may be drawn, crossing each other.
+        #[serde(default)]
+        pub allow_crossings: bool,
+        /// Colors of the condition and rule classes, the default theme unless the puzzle has
+        /// its own.
+        #[serde(default)]
+        pub palette: Palette,
+        /// Grid the nodes lie on, deciding which of them lines may join. Square with diagonals
+        /// unless the puzzle names another.
+        #[serde(default)]
+        pub topology: Topology,
+        /// Whether the left edge of the board joins the right and the top edge joins the bottom,
+        /// so lines may wrap around them.
+        #[serde(default)]
+        pub toroidal: bool,
+        /// Lines drawn from the start which can't be removed, e.g. to guide a tutorial.
+        #[serde(default)]
+        pub fixed_lines: Vec<GameLine>,
+        /// Lines which can never be drawn.
+        #[serde(default)]
+        pub forbidden_lines: Vec<GameLine>,
+    }
+
+    impl Puzzle {
+        /// Version of the json format written by this version of the game.
+        pub const FORMAT_VERSION: u32 = 1;
+
+        /// Returns a width x height board on the default grid, with a node without conditions
+        /// at every position and no sets, for generators and tests to fill in.
+        pub fn new(width: u8, height: u8) -> Self {
+            Puzzle {
+                format_version: Puzzle::FORMAT_VERSION,
+                uuid: Uuid::new_v4(),
+                width,
+                height,
+                nodes: (0..width as u16 * height as u16)
+                    .map(|id| GameNode {
+                        id,
+                        conditions: Vec::new(),
+                        connected_conditions: Vec::new(),
+                    })
+                    .collect(),
+                sets: Vec::new(),
+                allow_crossings: false,
+                palette: Palette::default(),
+                topology: Topology::default(),
+                toroidal: false,
+                fixed_lines: Vec::new(),
+                forbidden_lines: Vec::new(),
+            }
+        }
+
+        /// Returns the node with the id, or None if the puzzle leaves out its position.
+        pub fn get_node(&self, node_id: GameNodeId) -> Option<&GameNode> {
+            // Nodes of boards without holes are usually stored in id order
+            match self.nodes.get(node_id as usize) {
+                Some(node) if node.id == node_id => Some(node),
+                _ => self.nodes.iter().find(|node| node.id == node_id),
+            }
+        }
+
+        /// Returns true if the puzzle has a node with the id, rather than a hole.
+        pub fn has_node(&self, node_id: GameNodeId) -> bool {
+            self.get_node(node_id).is_some()
+        }
+
+        /// Checks that the line joins two adjacent nodes of the puzzle, so it lies on the grid.
+        /// Forbidden lines still lie on the grid, even though they can't be drawn.
+        pub fn check_line(&self, line: &GameLine) -> Result<(), LineError> {
+            for node_id in [line.node_a_id(), line.node_b_id()] {
+                if !self.has_node(node_id) {
+                    return Err(LineError::UnknownNode(node_id));
+                }
+            }
+            if !get_adjacent_nodes(&line.node_a_id(), self).contains(&line.node_b_id()) {
+                return Err(LineError::NotAdjacent(line.node_a_id(), line.node_b_id()));
+            }
+            Ok(())
+        }
     }
 }
@@ -3,19 +3,34 @@ pub mod set_rule {
     use serde::{Deserialize, Serialize};
 
     use crate::{
-        logic::rule_checks::rule_checks::{is_disconnected, is_leaf},
+        logic::{
+            diagnostic::diagnostic::{CheckResult, Diagnostic},
+            rule_checks::rule_checks::{
+                is_acyclic, is_closed, is_connected, is_disconnected, is_edge_count, is_leaf,
+            },
+        },
         structs::immutable::{game_set::game_set::GameSet, solution::solution::Solution},
         CDTN_RULE_SPRITE_SIZE, COLOR_RULE_UNSAT,
     };
 
     /// SetRule applies rules to a set of nodes which it wraps in a puzzle, possibly also impacting
     /// their conditions (e.g. Scope).
-    #[derive(Serialize, Deserialize, Clone, Debug)]
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
     pub enum SetRule {
         /// None of the nodes in the set may directly connect to any node also in the set.
         Disconnected,
         /// Only one connection may be made between a node within the set and any node outside the set.
         Leaf,
+        /// Conditions of nodes within the set only consider lines between nodes also in the set.
+        Scope,
+        /// Lines between nodes in the set must join all of them into a single network.
+        Connected,
+        /// Lines between nodes in the set may not form a cycle.
+        Acyclic,
+        /// Exactly the given number of lines must join nodes within the set.
+        EdgeCount(u8),
+        /// No connection may be made between a node within the set and any node outside the set.
+        Closed,
     }
 
     impl SetRule {
@@ -27,10 +42,27 @@ pub mod set_rule {
             }
         }
 
+        /// Returns true if the rule places a requirement on the set's lines, rather than only
+        /// changing how the conditions within the set are checked.
+        pub fn is_constraint(&self) -> bool {
+            !matches!(self, SetRule::Scope)
+        }
+
         pub fn is_satisfied(&self, set: &GameSet, solution: &Solution) -> bool {
+            self.check(set, solution).satisfied
+        }
+
+        /// Checks the rule like `is_satisfied`, also explaining why it is (un)satisfied.
+        pub fn check(&self, set: &GameSet, solution: &Solution) -> CheckResult {
             match self {
                 SetRule::Disconnected => is_disconnected(set, solution),
                 SetRule::Leaf => is_leaf(set, solution),
+                // Scope restricts the conditions within the set rather than the set itself
+                SetRule::Scope => CheckResult::new(true, Diagnostic::NoRequirement),
+                SetRule::Connected => is_connected(set, solution),
+                SetRule::Acyclic => is_acyclic(set, solution),
+                SetRule::EdgeCount(count) => is_edge_count(set, solution, *count),
+                SetRule::Closed => is_closed(set, solution),
             }
         }
     }
@@ -1,65 +1,357 @@
 pub mod solution {
-    use std::collections::{HashMap, HashSet};
+    use std::collections::{HashMap, HashSet, VecDeque};
+
+    use serde::Serialize;
 
     use crate::structs::{
         active::active_node::active_node::ActiveNode,
         immutable::{
-            game_line::game_line::{connections_to_lines, GameLine},
+            game_line::game_line::{connections_to_lines, GameLine, LineError},
             game_node::game_node::GameNodeId,
             game_set::game_set::GameSet,
+            puzzle::puzzle::Puzzle,
         },
     };
 
-    /// A proposed solution to a puzzle.
-    pub type Solution = Vec<GameLine>;
+    /// A proposed solution to a puzzle, as a set of lines ordered by node ids.
+    ///
+    /// Solutions are built from lines by `for_puzzle`, which checks that the lines can be drawn
+    /// in a puzzle. Lines the game drew itself, e.g. in the solver, skip the checks through
+    /// `from_lines_unchecked`. Solutions are serialized as their lines, and aren't deserialized
+    /// directly, so lines read back are passed through `for_puzzle`.
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+    #[serde(into = "Vec<GameLine>")]
+    pub struct Solution {
+        lines: Vec<GameLine>,
+    }
 
     /// A hash of node ids to connecting nodes.
     pub type AdjacencyMatrix = HashMap<GameNodeId, HashSet<GameNodeId>>;
 
-    pub fn active_nodes_to_solution(active_nodes: &Vec<ActiveNode>) -> Solution {
-        let mut lines_set = HashSet::new();
+    impl Solution {
+        /// Creates a solution without any lines.
+        pub fn new() -> Self {
+            Solution { lines: Vec::new() }
+        }
 
-        for active_node in active_nodes {
-            connections_to_lines(active_node).iter().for_each(|&line| {
-                lines_set.insert(line);
-            });
+        /// Creates a solution for a puzzle, checking that every line is between adjacent nodes
+        /// of the puzzle and isn't forbidden. Duplicate lines are only kept once.
+        ///
+        /// # Parameters
+        ///
+        /// - `puzzle`: The puzzle the lines are drawn in.
+        /// - `lines`: The lines of the solution, in any order.
+        ///
+        /// # Returns
+        ///
+        /// Returns the solution, or an error for the first line which can't be drawn.
+        pub fn for_puzzle(
+            puzzle: &Puzzle,
+            lines: impl IntoIterator<Item = GameLine>,
+        ) -> Result<Self, LineError> {
+            let solution = Solution::from_lines_unchecked(lines);
+            for line in solution.iter() {
+                puzzle.check_line(line)?;
+                if puzzle.forbidden_lines.contains(line) {
+                    return Err(LineError::Forbidden(line.node_a_id(), line.node_b_id()));
+                }
+            }
+            Ok(solution)
         }
 
-        lines_set
-            .iter()
-            .cloned()
-            .map(|game_line_ref| game_line_ref.clone())
-            .collect()
-    }
+        /// Creates a solution from lines the game drew itself, without checking them against a
+        /// puzzle. Duplicate lines are only kept once.
+        pub(crate) fn from_lines_unchecked(lines: impl IntoIterator<Item = GameLine>) -> Self {
+            let mut lines: Vec<GameLine> = lines.into_iter().collect();
+            lines.sort();
+            lines.dedup();
+            Solution { lines }
+        }
 
-    /// Converts a solution to a hash of node ids to connecting nodes. It is reflexive, so if
-    /// node A is connected to node B, then node B is connected to node A, represented by
-    /// {0: [1], 1: [0]} in the hash.
-    ///
-    /// TODO solution should be struct, impl Solution .to_adjacency_list fn
-    pub fn solution_to_adjacency_matrix(solution: &Solution) -> AdjacencyMatrix {
-        let mut graph: HashMap<GameNodeId, HashSet<u16>> = HashMap::new();
-        for line in solution {
-            graph
-                .entry(line.node_a_id)
-                .or_insert(HashSet::new())
-                .insert(line.node_b_id);
+        pub fn lines(&self) -> &[GameLine] {
+            &self.lines
+        }
+
+        pub fn iter(&self) -> std::slice::Iter<'_, GameLine> {
+            self.lines.iter()
+        }
+
+        pub fn len(&self) -> usize {
+            self.lines.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.lines.is_empty()
+        }
+
+        pub fn contains(&self, line: &GameLine) -> bool {
+            self.lines.binary_search(line).is_ok()
+        }
+
+        /// Adds a line to the solution, returning false if it was already drawn.
+        pub fn insert(&mut self, line: GameLine) -> bool {
+            match self.lines.binary_search(&line) {
+                Ok(_) => false,
+                Err(idx) => {
+                    self.lines.insert(idx, line);
+                    true
+                }
+            }
+        }
+
+        /// Removes a line from the solution, returning false if it wasn't drawn.
+        pub fn remove(&mut self, line: &GameLine) -> bool {
+            match self.lines.binary_search(line) {
+                Ok(idx) => {
+                    self.lines.remove(idx);
+                    true
+                }
+                Err(_) => false,
+            }
+        }
+
+        /// Converts the solution to a hash of node ids to connecting nodes. It is reflexive, so
+        /// if node A is connected to node B, then node B is connected to node A, represented by
+        /// {0: [1], 1: [0]} in the hash.
+        pub fn adjacency(&self) -> AdjacencyMatrix {
+            let mut graph: AdjacencyMatrix = HashMap::new();
+            for line in self.lines.iter() {
+                graph
+                    .entry(line.node_a_id())
+                    .or_default()
+                    .insert(line.node_b_id());
+                graph
+                    .entry(line.node_b_id())
+                    .or_default()
+                    .insert(line.node_a_id());
+            }
             graph
-                .entry(line.node_b_id)
-                .or_insert(HashSet::new())
-                .insert(line.node_a_id);
-        }
-        graph
-    }
-
-    /// Filters a solution to remove any lines which aren't connecting nodes within the set.
-    pub fn filter_solution_to_set(set: &GameSet, solution: &Solution) -> Solution {
-        solution
-            .iter()
-            .cloned()
-            .filter(|line| {
-                set.nodes.contains(&line.node_a_id) && set.nodes.contains(&line.node_b_id)
-            })
-            .collect()
+        }
+
+        /// Returns the number of lines connected to the node.
+        pub fn degree(&self, node_id: &GameNodeId) -> usize {
+            self.lines
+                .iter()
+                .filter(|line| line.contains(node_id))
+                .count()
+        }
+
+        /// Filters the solution to remove any lines which aren't connecting nodes within the set.
+        pub fn filter_to_set(&self, set: &GameSet) -> Solution {
+            Solution {
+                lines: self
+                    .lines
+                    .iter()
+                    .filter(|line| {
+                        set.nodes.contains(&line.node_a_id())
+                            && set.nodes.contains(&line.node_b_id())
+                    })
+                    .cloned()
+                    .collect(),
+            }
+        }
+
+        /// Returns the networks of the solution, each as its node ids in ascending order. Nodes
+        /// without any lines aren't part of a network. Networks are ordered by smallest node id.
+        pub fn components(&self) -> Vec<Vec<GameNodeId>> {
+            let adj_matrix = self.adjacency();
+            let mut node_ids: Vec<GameNodeId> = adj_matrix.keys().cloned().collect();
+            node_ids.sort();
+
+            let mut visited: HashSet<GameNodeId> = HashSet::new();
+            let mut components: Vec<Vec<GameNodeId>> = Vec::new();
+            for node_id in node_ids {
+                if !visited.insert(node_id) {
+                    continue;
+                }
+                let mut component: Vec<GameNodeId> = Vec::new();
+                let mut queue: VecDeque<GameNodeId> = VecDeque::from([node_id]);
+                while let Some(curr_node) = queue.pop_front() {
+                    component.push(curr_node);
+                    for &neighbor in adj_matrix.get(&curr_node).into_iter().flatten() {
+                        if visited.insert(neighbor) {
+                            queue.push_back(neighbor);
+                        }
+                    }
+                }
+                component.sort();
+                components.push(component);
+            }
+            components
+        }
+    }
+
+    /// Converts lines into a solution without checking them, to write solutions in tests.
+    #[cfg(test)]
+    impl From<Vec<GameLine>> for Solution {
+        fn from(lines: Vec<GameLine>) -> Self {
+            Solution::from_lines_unchecked(lines)
+        }
+    }
+
+    /// Converts lines into a solution without checking them, to write solutions in tests.
+    #[cfg(test)]
+    impl<const N: usize> From<[GameLine; N]> for Solution {
+        fn from(lines: [GameLine; N]) -> Self {
+            Solution::from_lines_unchecked(lines)
+        }
+    }
+
+    impl From<Solution> for Vec<GameLine> {
+        fn from(solution: Solution) -> Self {
+            solution.lines
+        }
+    }
+
+    impl<'a> IntoIterator for &'a Solution {
+        type Item = &'a GameLine;
+        type IntoIter = std::slice::Iter<'a, GameLine>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.lines.iter()
+        }
+    }
+
+    impl IntoIterator for Solution {
+        type Item = GameLine;
+        type IntoIter = std::vec::IntoIter<GameLine>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.lines.into_iter()
+        }
+    }
+
+    pub fn active_nodes_to_solution(active_nodes: &[ActiveNode]) -> Solution {
+        Solution::from_lines_unchecked(active_nodes.iter().flat_map(connections_to_lines))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::structs::immutable::{
+        game_line::game_line::{GameLine, LineError},
+        game_set::game_set::GameSet,
+        puzzle::puzzle::Puzzle,
+        solution::solution::Solution,
+    };
+
+    fn get_test_solution() -> Solution {
+        Solution::from([
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+            GameLine::new(0, 2).unwrap(),
+            GameLine::new(3, 4).unwrap(),
+        ])
+    }
+
+    #[test]
+    fn test_from_orders_and_deduplicates_lines() {
+        let solution = Solution::from([
+            GameLine::new(2, 1).unwrap(),
+            GameLine::new(0, 1).unwrap(),
+            GameLine::new(1, 2).unwrap(),
+        ]);
+
+        assert_eq!(
+            solution.lines(),
+            [GameLine::new(0, 1).unwrap(), GameLine::new(1, 2).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_for_puzzle_adjacent_lines_returns_solution() {
+        let puzzle = Puzzle::new(2, 2);
+
+        let solution = Solution::for_puzzle(
+            &puzzle,
+            [GameLine::new(0, 3).unwrap(), GameLine::new(1, 2).unwrap()],
+        );
+
+        assert_eq!(solution.map(|solution| solution.len()), Ok(2));
+    }
+
+    #[test]
+    fn test_for_puzzle_non_adjacent_line_returns_error() {
+        let puzzle = Puzzle::new(3, 1);
+
+        let solution = Solution::for_puzzle(&puzzle, [GameLine::new(0, 2).unwrap()]);
+
+        assert_eq!(solution, Err(LineError::NotAdjacent(0, 2)));
+    }
+
+    #[test]
+    fn test_for_puzzle_forbidden_line_returns_error() {
+        let mut puzzle = Puzzle::new(3, 1);
+        puzzle.forbidden_lines = [GameLine::new(1, 2).unwrap()].to_vec();
+
+        let solution = Solution::for_puzzle(&puzzle, [GameLine::new(1, 2).unwrap()]);
+
+        assert_eq!(solution, Err(LineError::Forbidden(1, 2)));
+    }
+
+    #[test]
+    fn test_for_puzzle_unknown_node_returns_error() {
+        let puzzle = Puzzle::new(2, 1);
+
+        let solution = Solution::for_puzzle(&puzzle, [GameLine::new(1, 2).unwrap()]);
+
+        assert_eq!(solution, Err(LineError::UnknownNode(2)));
+    }
+
+    #[test]
+    fn test_adjacency_is_reflexive() {
+        let adj_matrix = get_test_solution().adjacency();
+
+        assert_eq!(adj_matrix[&0], HashSet::from([1, 2]));
+        assert_eq!(adj_matrix[&4], HashSet::from([3]));
+        assert!(!adj_matrix.contains_key(&5));
+    }
+
+    #[test]
+    fn test_degree_counts_lines_of_node() {
+        let solution = get_test_solution();
+
+        assert_eq!(solution.degree(&1), 2);
+        assert_eq!(solution.degree(&3), 1);
+        assert_eq!(solution.degree(&5), 0);
+    }
+
+    #[test]
+    fn test_filter_to_set_keeps_only_lines_within_set() {
+        let set = GameSet {
+            id: 0,
+            nodes: [0, 1, 3].to_vec(),
+            rules: Vec::new(),
+            connected_rules: Vec::new(),
+            meta_rules: Vec::new(),
+            bounded: false,
+        };
+
+        assert_eq!(
+            get_test_solution().filter_to_set(&set),
+            Solution::from([GameLine::new(0, 1).unwrap()])
+        );
+    }
+
+    #[test]
+    fn test_components_returns_each_network() {
+        assert_eq!(
+            get_test_solution().components(),
+            [[0, 1, 2].to_vec(), [3, 4].to_vec()].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_serialize_returns_lines() {
+        let solution = get_test_solution();
+
+        let json = serde_json::to_string(&solution).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<Vec<GameLine>>(&json).unwrap(),
+            solution.lines()
+        );
     }
 }
@@ -0,0 +1,74 @@
+pub mod clipboard {
+    use std::{
+        io::{self, Write},
+        process::{Command, Stdio},
+    };
+
+    /// Commands printing the system clipboard, tried in order until one succeeds.
+    #[cfg(target_os = "macos")]
+    const PASTE_COMMANDS: &[&[&str]] = &[&["pbpaste"]];
+    #[cfg(target_os = "windows")]
+    const PASTE_COMMANDS: &[&[&str]] =
+        &[&["powershell", "-NoProfile", "-Command", "Get-Clipboard"]];
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    const PASTE_COMMANDS: &[&[&str]] = &[
+        &["wl-paste", "--no-newline"],
+        &["xclip", "-selection", "clipboard", "-out"],
+        &["xsel", "--clipboard", "--output"],
+    ];
+
+    /// Commands setting the system clipboard to their input, tried in order until one succeeds.
+    #[cfg(target_os = "macos")]
+    const COPY_COMMANDS: &[&[&str]] = &[&["pbcopy"]];
+    #[cfg(target_os = "windows")]
+    const COPY_COMMANDS: &[&[&str]] = &[&["clip"]];
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    const COPY_COMMANDS: &[&[&str]] = &[
+        &["wl-copy"],
+        &["xclip", "-selection", "clipboard", "-in"],
+        &["xsel", "--clipboard", "--input"],
+    ];
+
+    /// Returns the text on the system clipboard, read through the clipboard tool of the
+    /// platform as winit doesn't give access to it.
+    pub fn read_clipboard() -> io::Result<String> {
+        let mut last_error = io::Error::new(io::ErrorKind::NotFound, "no clipboard tool found");
+        for args in PASTE_COMMANDS {
+            match Command::new(args[0]).args(&args[1..]).output() {
+                Ok(output) if output.status.success() => {
+                    return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+                }
+                Ok(output) => {
+                    last_error = io::Error::other(format!("{} failed: {}", args[0], output.status))
+                }
+                Err(error) => last_error = error,
+            }
+        }
+        Err(last_error)
+    }
+
+    /// Puts the text on the system clipboard, through the clipboard tool of the platform.
+    pub fn write_clipboard(text: &str) -> io::Result<()> {
+        let mut last_error = io::Error::new(io::ErrorKind::NotFound, "no clipboard tool found");
+        for args in COPY_COMMANDS {
+            let result = Command::new(args[0])
+                .args(&args[1..])
+                .stdin(Stdio::piped())
+                .spawn()
+                .and_then(|mut child| {
+                    if let Some(mut stdin) = child.stdin.take() {
+                        stdin.write_all(text.as_bytes())?;
+                    }
+                    child.wait()
+                });
+            match result {
+                Ok(status) if status.success() => return Ok(()),
+                Ok(status) => {
+                    last_error = io::Error::other(format!("{} failed: {}", args[0], status))
+                }
+                Err(error) => last_error = error,
+            }
+        }
+        Err(last_error)
+    }
+}
@@ -1,4 +1,5 @@
 pub mod buttons;
+pub mod clipboard;
 pub mod constants;
 pub mod inputs;
 pub mod texture;
@@ -9,14 +9,28 @@ pub mod texture {
         CdtnLeaf,
         CdtnInternal,
         CdtnCycle,
+        CdtnDegree,
+        CdtnCutVertex,
+        CdtnStraight,
 
         CdtnDegreeEqual,
         CdtnDistanceEqual,
+        CdtnDegreeDistinct,
+        CdtnDistanceDistinct,
+        CdtnSameComponentPath,
 
         SetRuleDisconnected,
         SetRuleLeaf,
+        SetRuleConnected,
+        SetRuleAcyclic,
+        SetRuleEdgeCount,
+        SetRuleClosed,
 
         SetRuleHomomorphism,
+        SetRuleIsomorphism,
+        SetRuleCongruent,
+        SetRuleEdgeCountEqual,
+        SetRuleMutuallyDisconnected,
 
         SetRuleXor,
         SetRuleScope,
@@ -25,6 +39,11 @@ pub mod texture {
         LineVertical,
         LineDiagonalBottomLeftTopRight,
         LineDiagonalTopLeftBottomRight,
+        LineFixedHorizontal,
+        LineFixedVertical,
+        LineFixedDiagonalBottomLeftTopRight,
+        LineFixedDiagonalTopLeftBottomRight,
+        LineForbidden,
 
         SetTileVertical,
         SetTileHorizontal,
@@ -65,6 +84,7 @@ pub mod texture {
         BtnCheckAnswer,
         BtnClearLines,
         BtnGoBack,
+        BtnShare,
 
         Missing,
     }
@@ -85,18 +105,42 @@ pub mod texture {
                 Texture::CdtnLeaf => "../assets/textures/sprites/CDTN_LEAF.png",
                 Texture::CdtnInternal => "../assets/textures/sprites/CDTN_INTERNAL.png",
                 Texture::CdtnCycle => "../assets/textures/sprites/CDTN_CYCLE.png",
+                Texture::CdtnDegree => "../assets/textures/sprites/CDTN_DEGREE.png",
+                Texture::CdtnCutVertex => "../assets/textures/sprites/CDTN_CUT_VERTEX.png",
+                Texture::CdtnStraight => "../assets/textures/sprites/CDTN_STRAIGHT.png",
 
                 Texture::CdtnDegreeEqual => "../assets/textures/sprites/CDTN_DEGREE_EQUAL.png",
                 Texture::CdtnDistanceEqual => "../assets/textures/sprites/CDTN_DISTANCE_EQUAL.png",
+                Texture::CdtnDegreeDistinct => {
+                    "../assets/textures/sprites/CDTN_DEGREE_DISTINCT.png"
+                }
+                Texture::CdtnDistanceDistinct => {
+                    "../assets/textures/sprites/CDTN_DISTANCE_DISTINCT.png"
+                }
+                Texture::CdtnSameComponentPath => {
+                    "../assets/textures/sprites/CDTN_SAME_COMPONENT_PATH.png"
+                }
 
                 Texture::SetRuleDisconnected => {
                     "../assets/textures/sprites/SETRULE_DISCONNECTED.png"
                 }
                 Texture::SetRuleLeaf => "../assets/textures/sprites/SETRULE_LEAF.png",
+                Texture::SetRuleConnected => "../assets/textures/sprites/SETRULE_CONNECTED.png",
+                Texture::SetRuleAcyclic => "../assets/textures/sprites/SETRULE_ACYCLIC.png",
+                Texture::SetRuleEdgeCount => "../assets/textures/sprites/SETRULE_EDGE_COUNT.png",
+                Texture::SetRuleClosed => "../assets/textures/sprites/SETRULE_CLOSED.png",
 
                 Texture::SetRuleHomomorphism => {
                     "../assets/textures/sprites/SETRULE_HOMOMORPHISM.png"
                 }
+                Texture::SetRuleIsomorphism => "../assets/textures/sprites/SETRULE_ISOMORPHIC.png",
+                Texture::SetRuleCongruent => "../assets/textures/sprites/SETRULE_CONGRUENT.png",
+                Texture::SetRuleEdgeCountEqual => {
+                    "../assets/textures/sprites/SETRULE_EDGE_COUNT_EQUAL.png"
+                }
+                Texture::SetRuleMutuallyDisconnected => {
+                    "../assets/textures/sprites/SETRULE_MUTUALLY_DISCONNECTED.png"
+                }
 
                 Texture::SetRuleXor => "../assets/textures/sprites/SETRULE_XOR.png",
                 Texture::SetRuleScope => "../assets/textures/sprites/SETRULE_SCOPE.png",
@@ -109,6 +153,17 @@ pub mod texture {
                 Texture::LineDiagonalTopLeftBottomRight => {
                     "../assets/textures/sprites/LINE_DIAGONAL_TL_BR.png"
                 }
+                Texture::LineFixedHorizontal => {
+                    "../assets/textures/sprites/LINE_FIXED_HORIZONTAL.png"
+                }
+                Texture::LineFixedVertical => "../assets/textures/sprites/LINE_FIXED_VERTICAL.png",
+                Texture::LineFixedDiagonalBottomLeftTopRight => {
+                    "../assets/textures/sprites/LINE_FIXED_DIAGONAL_BL_TR.png"
+                }
+                Texture::LineFixedDiagonalTopLeftBottomRight => {
+                    "../assets/textures/sprites/LINE_FIXED_DIAGONAL_TL_BR.png"
+                }
+                Texture::LineForbidden => "../assets/textures/sprites/LINE_FORBIDDEN.png",
 
                 Texture::SetTileVertical => "../assets/textures/background/SET_TILE_VERTICAL.png",
                 Texture::SetTileHorizontal => {
@@ -179,6 +234,7 @@ pub mod texture {
                 Texture::BtnCheckAnswer => "../assets/textures/buttons/BTN_CHECK_ANSWER.png",
                 Texture::BtnClearLines => "../assets/textures/buttons/BTN_CLEAR_LINES.png",
                 Texture::BtnGoBack => "../assets/textures/buttons/BTN_GO_BACK.png",
+                Texture::BtnShare => "../assets/textures/buttons/BTN_SHARE.png",
 
                 Texture::Missing => "",
             }